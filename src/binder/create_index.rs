@@ -7,7 +7,7 @@ use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::index::IndexType;
-use sqlparser::ast::{ObjectName, OrderByExpr};
+use sqlparser::ast::{Expr, ObjectName, OrderByExpr};
 use std::sync::Arc;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
@@ -33,25 +33,50 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             .context
             .table_and_bind(table_name.clone(), None, None)?;
         let plan = TableScanOperator::build(table_name.clone(), table);
-        let mut columns = Vec::with_capacity(exprs.len());
+        let mut index_exprs = Vec::with_capacity(exprs.len());
 
         for expr in exprs {
-            // TODO: Expression Index
-            match self.bind_expr(&expr.expr)? {
-                ScalarExpression::ColumnRef(column) => columns.push(column),
+            let mut expr = self.bind_index_expr(&expr.expr)?;
+            expr.constant_calculation()?;
+
+            index_exprs.push(expr);
+        }
+        let mut include_columns = Vec::with_capacity(self.extension.include.len());
+
+        for ident in self.extension.include.clone() {
+            match self.bind_expr(&Expr::Identifier(ident))? {
+                ScalarExpression::ColumnRef(column)
+                    if !index_exprs.contains(&ScalarExpression::ColumnRef(column.clone()))
+                        && !include_columns.contains(&column) =>
+                {
+                    include_columns.push(column)
+                }
                 expr => {
                     return Err(DatabaseError::UnsupportedStmt(format!(
-                        "'CREATE INDEX' by {}",
+                        "'CREATE INDEX' include {}",
                         expr
                     )))
                 }
             }
         }
 
+        // simplified as the filters of queries, so that they can be matched when choosing indexes
+        let predicate = if let Some(predicate) = self.extension.predicate.clone() {
+            let mut predicate = self.bind_index_expr(&predicate)?;
+            predicate.simplify()?;
+            predicate.constant_calculation()?;
+
+            Some(predicate)
+        } else {
+            None
+        };
+
         Ok(LogicalPlan::new(
             Operator::CreateIndex(CreateIndexOperator {
                 table_name,
-                columns,
+                exprs: index_exprs,
+                include_columns,
+                predicate,
                 index_name,
                 if_not_exists,
                 ty,
@@ -59,4 +84,19 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             vec![plan],
         ))
     }
+
+    fn bind_index_expr(&mut self, expr: &Expr) -> Result<ScalarExpression, DatabaseError> {
+        let expr = self.bind_expr(expr)?;
+
+        if expr.has_agg_call()
+            || self.context.sub_queries_at_now().is_some()
+            || expr.referenced_columns(true).is_empty()
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "'CREATE INDEX' by {}",
+                expr
+            )));
+        }
+        Ok(expr)
+    }
 }
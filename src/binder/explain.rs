@@ -1,11 +1,27 @@
 use crate::binder::Binder;
 use crate::errors::DatabaseError;
+use crate::planner::operator::explain::{ExplainFormat, ExplainOperator};
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use sqlparser::ast::AnalyzeFormat;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
-    pub(crate) fn bind_explain(&mut self, plan: LogicalPlan) -> Result<LogicalPlan, DatabaseError> {
-        Ok(LogicalPlan::new(Operator::Explain, vec![plan]))
+    pub(crate) fn bind_explain(
+        &mut self,
+        plan: LogicalPlan,
+        analyze: bool,
+        format: Option<AnalyzeFormat>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let format = match format {
+            None | Some(AnalyzeFormat::TEXT) => ExplainFormat::Text,
+            Some(AnalyzeFormat::JSON) => ExplainFormat::Json,
+            Some(AnalyzeFormat::GRAPHVIZ) => ExplainFormat::Dot,
+        };
+
+        Ok(LogicalPlan::new(
+            Operator::Explain(ExplainOperator { analyze, format }),
+            vec![plan],
+        ))
     }
 }
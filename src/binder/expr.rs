@@ -5,7 +5,7 @@ use crate::expression::agg::AggKind;
 use itertools::Itertools;
 use sqlparser::ast::{
     BinaryOperator, CharLengthUnits, DataType, Expr, Function, FunctionArg, FunctionArgExpr, Ident,
-    Query, UnaryOperator,
+    Query, UnaryOperator, Value,
 };
 use std::slice;
 use std::sync::Arc;
@@ -17,7 +17,7 @@ use crate::expression::function::FunctionSummary;
 use crate::expression::{AliasType, ScalarExpression};
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use crate::types::value::{DataValue, Utf8Type};
+use crate::types::value::{DataValue, Utf8Type, ValueRef};
 use crate::types::LogicalType;
 
 macro_rules! try_alias {
@@ -47,7 +47,14 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             }
             Expr::CompoundIdentifier(idents) => self.bind_column_ref_from_identifiers(idents, None),
             Expr::BinaryOp { left, right, op } => self.bind_binary_op_internal(left, right, op),
-            Expr::Value(v) => Ok(ScalarExpression::Constant(Arc::new(v.into()))),
+            Expr::Value(v) => {
+                let value: ValueRef = Arc::new(v.into());
+
+                if matches!(v, Value::Number(..) | Value::SingleQuotedString(_)) {
+                    self.context.literals.push(value.clone());
+                }
+                Ok(ScalarExpression::Constant(value))
+            }
             Expr::Function(func) => self.bind_function(func),
             Expr::Nested(expr) => self.bind_expr(expr),
             Expr::UnaryOp { expr, op } => self.bind_unary_op_internal(expr, op),
@@ -436,11 +443,6 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     }
 
     fn bind_function(&mut self, func: &Function) -> Result<ScalarExpression, DatabaseError> {
-        if !matches!(self.context.step_now(), QueryBindStep::From) {
-            return Err(DatabaseError::UnsupportedStmt(
-                "`TableFunction` cannot bind in non-From step".to_string(),
-            ));
-        }
         let mut args = Vec::with_capacity(func.args.len());
 
         for arg in func.args.iter() {
@@ -511,7 +513,8 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 if args.len() != 1 {
                     return Err(DatabaseError::MisMatch("number of avg() parameters", "1"));
                 }
-                let ty = args[0].return_type();
+                // the sum is divided into a double
+                let ty = LogicalType::Double;
 
                 return Ok(ScalarExpression::AggCall {
                     distinct: func.distinct,
@@ -605,6 +608,11 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             }));
         }
         if let Some(function) = self.context.table_functions.get(&summary) {
+            if !matches!(self.context.step_now(), QueryBindStep::From) {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`TableFunction` cannot bind in non-From step".to_string(),
+                ));
+            }
             return Ok(ScalarExpression::TableFunction(TableFunction {
                 args,
                 inner: function.clone(),
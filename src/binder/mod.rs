@@ -18,6 +18,7 @@ mod update;
 
 use sqlparser::ast::{Ident, ObjectName, ObjectType, SetExpr, Statement};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
@@ -25,9 +26,11 @@ use crate::catalog::{TableCatalog, TableName};
 use crate::db::{ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::parser::StatementExtension;
 use crate::planner::operator::join::JoinType;
 use crate::planner::LogicalPlan;
 use crate::storage::{TableCache, Transaction};
+use crate::types::value::ValueRef;
 
 pub enum InputRefType {
     AggCall,
@@ -46,6 +49,18 @@ pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
         | Statement::CreateIndex { .. }
         | Statement::AlterTable { .. }
         | Statement::Drop { .. } => Ok(CommandType::DDL),
+        // the statement is executed by `EXPLAIN ANALYZE`, but DDL is not allowed to, as it would
+        // run without the exclusive metadata lock
+        Statement::Explain {
+            analyze: true,
+            statement,
+            ..
+        } => match command_type(statement)? {
+            CommandType::DDL => Err(DatabaseError::UnsupportedStmt(
+                "`EXPLAIN ANALYZE` of DDL".to_string(),
+            )),
+            command_type => Ok(command_type),
+        },
         Statement::Query(_)
         | Statement::Explain { .. }
         | Statement::ExplainTable { .. }
@@ -103,6 +118,9 @@ pub struct BinderContext<'a, T: Transaction> {
 
     temp_table_id: Arc<AtomicUsize>,
     pub(crate) allow_default: bool,
+    // the constants bound from the numeric and the string literals of the statement, in the
+    // order they are bound, which the plan cache substitutes to reuse the plan
+    literals: Vec<ValueRef>,
 }
 
 impl<'a, T: Transaction> BinderContext<'a, T> {
@@ -128,6 +146,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             sub_queries: Default::default(),
             temp_table_id,
             allow_default: false,
+            literals: vec![],
         }
     }
 
@@ -238,12 +257,29 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
 
 pub struct Binder<'a, 'b, T: Transaction> {
     context: BinderContext<'a, T>,
+    extension: StatementExtension,
     pub(crate) parent: Option<&'b Binder<'a, 'b, T>>,
 }
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub fn new(context: BinderContext<'a, T>, parent: Option<&'b Binder<'a, 'b, T>>) -> Self {
-        Binder { context, parent }
+        Binder {
+            context,
+            extension: StatementExtension::default(),
+            parent,
+        }
+    }
+
+    /// Takes the constants bound from the numeric and the string literals of the statement,
+    /// except the ones of the subqueries.
+    pub(crate) fn take_literals(&mut self) -> Vec<ValueRef> {
+        mem::take(&mut self.context.literals)
+    }
+
+    /// Binds the statement with the clauses that are parsed by the dialect of FnckSQL.
+    pub fn with_extension(mut self, extension: StatementExtension) -> Self {
+        self.extension = extension;
+        self
     }
 
     pub fn bind(&mut self, stmt: &Statement) -> Result<LogicalPlan, DatabaseError> {
@@ -312,10 +348,15 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 options,
                 ..
             } => self.bind_copy(source.clone(), *to, target.clone(), options)?,
-            Statement::Explain { statement, .. } => {
+            Statement::Explain {
+                statement,
+                analyze,
+                format,
+                ..
+            } => {
                 let plan = self.bind(statement)?;
 
-                self.bind_explain(plan)?
+                self.bind_explain(plan, *analyze, *format)?
             }
             Statement::ExplainTable {
                 describe_alias: true,
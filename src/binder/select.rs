@@ -593,33 +593,52 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                     children.output_schema(),
                     plan.output_schema(),
                 )?;
-
-                // combine multiple filter exprs into one BinaryExpr
-                let join_filter = filter
-                    .into_iter()
-                    .reduce(|acc, expr| ScalarExpression::Binary {
-                        op: BinaryOperator::And,
-                        left_expr: Box::new(acc),
-                        right_expr: Box::new(expr),
-                        evaluator: None,
-                        ty: LogicalType::Boolean,
+                // a semi or anti join only checks whether the subquery matches, so the
+                // predicates on the outer query alone are applied above it
+                let mut outer_filter = vec![];
+                if matches!(join_ty, JoinType::LeftSemi | JoinType::LeftAnti) {
+                    let right_schema = plan.output_schema().clone();
+
+                    (filter, outer_filter) = filter.into_iter().partition(|expr| {
+                        expr.referenced_columns(false).iter().any(|column| {
+                            right_schema
+                                .iter()
+                                .any(|right_column| right_column.summary() == column.summary())
+                        })
                     });
+                }
 
                 children = LJoinOperator::build(
                     children,
                     plan,
                     JoinCondition::On {
                         on: on_keys,
-                        filter: join_filter,
+                        filter: Self::combine_conjuncts(filter),
                     },
                     join_ty,
                 );
+                if let Some(predicate) = Self::combine_conjuncts(outer_filter) {
+                    children = FilterOperator::build(predicate, children, false);
+                }
             }
             return Ok(children);
         }
         Ok(FilterOperator::build(predicate, children, false))
     }
 
+    // combine multiple filter exprs into one BinaryExpr
+    fn combine_conjuncts(exprs: Vec<ScalarExpression>) -> Option<ScalarExpression> {
+        exprs
+            .into_iter()
+            .reduce(|acc, expr| ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr: Box::new(acc),
+                right_expr: Box::new(expr),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            })
+    }
+
     fn bind_having(
         &mut self,
         children: LogicalPlan,
@@ -6,6 +6,7 @@ use std::{slice, vec};
 
 use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
 use crate::types::index::{IndexMeta, IndexMetaRef, IndexType};
 use crate::types::tuple::SchemaRef;
 use crate::types::{ColumnId, LogicalType};
@@ -31,9 +32,9 @@ pub struct TableMeta {
 
 impl TableCatalog {
     pub(crate) fn get_unique_index(&self, col_id: &ColumnId) -> Option<&IndexMetaRef> {
-        self.indexes
-            .iter()
-            .find(|meta| matches!(meta.ty, IndexType::Unique) && &meta.column_ids[0] == col_id)
+        self.indexes.iter().find(|meta| {
+            matches!(meta.ty, IndexType::Unique) && meta.column_ids.first() == Some(col_id)
+        })
     }
 
     #[allow(dead_code)]
@@ -117,6 +118,9 @@ impl TableCatalog {
         &mut self,
         name: String,
         column_ids: Vec<ColumnId>,
+        exprs: Option<Vec<ScalarExpression>>,
+        include_column_ids: Vec<ColumnId>,
+        predicate: Option<ScalarExpression>,
         ty: IndexType,
     ) -> Result<&IndexMeta, DatabaseError> {
         for index in self.indexes.iter() {
@@ -130,6 +134,9 @@ impl TableCatalog {
         let index = IndexMeta {
             id: index_id,
             column_ids,
+            exprs,
+            include_column_ids,
+            predicate,
             table_name: self.name.clone(),
             pk_ty,
             name,
@@ -1,26 +1,32 @@
 use crate::binder::{command_type, Binder, BinderContext, CommandType};
 use crate::catalog::TableCatalog;
 use crate::errors::DatabaseError;
-use crate::execution::{build_write, try_collect};
+use crate::execution::{build_write, try_collect, ExecutionConfig};
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::table::TableFunctionImpl;
 use crate::expression::function::FunctionSummary;
 use crate::function::current_date::CurrentDate;
 use crate::function::numbers::Numbers;
-use crate::optimizer::heuristic::batch::HepBatchStrategy;
-use crate::optimizer::heuristic::optimizer::HepOptimizer;
-use crate::optimizer::rule::implementation::ImplementationRuleImpl;
-use crate::optimizer::rule::normalization::NormalizationRuleImpl;
-use crate::parser::parse_sql;
+use crate::function::year::Year;
+use crate::optimizer::core::hint::OptimizerHints;
+use crate::optimizer::core::statistics_tracker::{StatisticsChanges, StatisticsTracker};
+use crate::optimizer::heuristic::pipeline::OptimizerPipeline;
+use crate::parser::{parse_sql_with_extensions, StatementExtension};
+use crate::planner::operator::analyze::AnalyzeOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::plan_cache::PlanCache;
 use crate::planner::LogicalPlan;
 use crate::storage::rocksdb::RocksStorage;
 use crate::storage::{StatisticsMetaCache, Storage, TableCache, Transaction};
 use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::ValueRef;
 use crate::utils::lru::ShardingLruCache;
 use ahash::HashMap;
 use parking_lot::{ArcRwLockReadGuard, ArcRwLockWriteGuard, RawRwLock, RwLock};
 use sqlparser::ast::Statement;
 use std::hash::RandomState;
+use std::mem;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
@@ -38,6 +44,10 @@ pub struct DataBaseBuilder {
     path: PathBuf,
     scala_functions: ScalaFunctions,
     table_functions: TableFunctions,
+    execution_config: ExecutionConfig,
+    optimizer_pipeline: OptimizerPipeline,
+    plan_cache_size: usize,
+    auto_analyze_ratio: Option<f64>,
 }
 
 impl DataBaseBuilder {
@@ -46,8 +56,13 @@ impl DataBaseBuilder {
             path: path.into(),
             scala_functions: Default::default(),
             table_functions: Default::default(),
+            execution_config: Default::default(),
+            optimizer_pipeline: Default::default(),
+            plan_cache_size: 128,
+            auto_analyze_ratio: None,
         };
         builder = builder.register_scala_function(CurrentDate::new());
+        builder = builder.register_scala_function(Year::new());
         builder = builder.register_table_function(Numbers::new());
         builder
     }
@@ -66,10 +81,69 @@ impl DataBaseBuilder {
         self
     }
 
+    /// Limits the bytes of tuples each `ORDER BY` keeps in memory, beyond which sorted runs
+    /// are spilled to temporary files and merged at the end.
+    pub fn sort_memory_budget(mut self, bytes: usize) -> Self {
+        self.execution_config.sort_memory_budget = Some(bytes);
+        self
+    }
+
+    /// Limits the bytes each hash join and hash aggregation keeps in its hashtable, beyond
+    /// which the inputs are hash partitioned to temporary files and processed partition by
+    /// partition.
+    pub fn hash_memory_budget(mut self, bytes: usize) -> Self {
+        self.execution_config.hash_memory_budget = Some(bytes);
+        self
+    }
+
+    /// Directory of the temporary files written by spilling executors, defaults to
+    /// [`std::env::temp_dir`].
+    pub fn spill_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.execution_config.spill_path = path.into();
+        self
+    }
+
+    /// Replaces the batches and the rules of the optimizer, which are edited from
+    /// [`OptimizerPipeline::default`] to add rewrites or to bisect the rules.
+    pub fn optimizer_pipeline(mut self, pipeline: OptimizerPipeline) -> Self {
+        self.optimizer_pipeline = pipeline;
+        self
+    }
+
+    /// Number of optimized plans cached by the statement text, `0` disables the cache so that
+    /// every statement is bound and optimized again.
+    pub fn plan_cache_size(mut self, size: usize) -> Self {
+        self.plan_cache_size = size;
+        self
+    }
+
+    /// Analyzes a table again before the next statement once the rows inserted, updated or
+    /// deleted since its last `ANALYZE` reach `ratio` of its rows, e.g. `0.1` for a tenth.
+    /// Meanwhile the changes of the indexes are applied to the cached statistics only, they are
+    /// not saved so a restart falls back to the statistics of the last `ANALYZE`. A table failed
+    /// to be analyzed is retried after a backoff, the ratio must be greater than `0`.
+    pub fn auto_analyze_ratio(mut self, ratio: f64) -> Self {
+        self.auto_analyze_ratio = Some(ratio);
+        self.execution_config.track_statistics = true;
+        self
+    }
+
     pub fn build(self) -> Result<Database<RocksStorage>, DatabaseError> {
+        if let Some(ratio) = self.auto_analyze_ratio.filter(|ratio| !(*ratio > 0.0)) {
+            return Err(DatabaseError::InvalidConfig(
+                "auto_analyze_ratio",
+                ratio.to_string(),
+            ));
+        }
         let storage = RocksStorage::new(self.path)?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let plan_cache = (self.plan_cache_size > 0)
+            .then(|| PlanCache::new(self.plan_cache_size).map(Arc::new))
+            .transpose()?;
+        let statistics_tracker = self
+            .auto_analyze_ratio
+            .map(|ratio| Arc::new(StatisticsTracker::new(ratio)));
 
         Ok(Database {
             storage,
@@ -78,6 +152,10 @@ impl DataBaseBuilder {
             mdl: Arc::new(RwLock::new(())),
             meta_cache,
             table_cache,
+            execution_config: Arc::new(self.execution_config),
+            optimizer_pipeline: Arc::new(self.optimizer_pipeline),
+            plan_cache,
+            statistics_tracker,
         })
     }
 }
@@ -89,45 +167,130 @@ pub struct Database<S: Storage> {
     mdl: Arc<RwLock<()>>,
     pub(crate) meta_cache: Arc<StatisticsMetaCache>,
     pub(crate) table_cache: Arc<ShardingLruCache<String, TableCatalog>>,
+    pub(crate) execution_config: Arc<ExecutionConfig>,
+    optimizer_pipeline: Arc<OptimizerPipeline>,
+    plan_cache: Option<Arc<PlanCache>>,
+    statistics_tracker: Option<Arc<StatisticsTracker>>,
 }
 
 impl<S: Storage> Database<S> {
     /// Run SQL queries.
     pub fn run<T: AsRef<str>>(&self, sql: T) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
         // parse
-        let stmts = parse_sql(sql)?;
+        let stmts = parse_sql_with_extensions(sql)?;
         if stmts.is_empty() {
             return Err(DatabaseError::EmptyStatement);
         }
-        let stmt = &stmts[0];
-        let _guard = if matches!(command_type(stmt)?, CommandType::DDL) {
+        let (stmt, extension) = &stmts[0];
+        let is_ddl = matches!(command_type(stmt)?, CommandType::DDL);
+        let _guard = if is_ddl {
             MetaDataLock::Write(self.mdl.write_arc())
         } else {
             MetaDataLock::Read(self.mdl.read_arc())
         };
+        if !is_ddl {
+            self.auto_analyze()?;
+        }
         let mut transaction = self.storage.transaction()?;
-        let mut plan = Self::build_plan(
+        let mut plan = Self::cached_plan(
             stmt,
+            extension,
             &self.table_cache,
             &self.meta_cache,
             &transaction,
             &self.scala_functions,
             &self.table_functions,
+            &self.optimizer_pipeline,
+            self.plan_cache.as_deref(),
         )?;
 
         let schema = plan.output_schema().clone();
         let iterator = build_write(
             plan,
-            (&self.table_cache, &self.meta_cache),
+            (&self.table_cache, &self.meta_cache, &self.execution_config),
             &mut transaction,
         );
-        let tuples = try_collect(iterator)?;
+        let tuples = try_collect(iterator);
+        // the catalogs and the statistics of the cached plans may be changed, even by the
+        // statements that failed halfway
+        if is_ddl || matches!(stmt, Statement::Analyze { .. }) {
+            if let Some(plan_cache) = &self.plan_cache {
+                plan_cache.invalidate();
+            }
+        }
+        let tuples = tuples?;
+        let statistics_changes = mem::take(transaction.statistics_changes());
 
         transaction.commit()?;
+        commit_statistics(
+            self.statistics_tracker.as_deref(),
+            self.plan_cache.as_deref(),
+            statistics_changes,
+            &self.meta_cache,
+        );
 
         Ok((schema, tuples))
     }
 
+    /// Analyzes the tables whose statistics are stale, before the statement is planned by
+    /// them. A table failed to be analyzed is analyzed again by the next statement.
+    fn auto_analyze(&self) -> Result<(), DatabaseError> {
+        let Some(statistics_tracker) = &self.statistics_tracker else {
+            return Ok(());
+        };
+        let transaction = self.storage.transaction()?;
+        let table_names =
+            statistics_tracker.stale_tables(|table_name| transaction.row_count(table_name))?;
+        drop(transaction);
+
+        for table_name in table_names.iter() {
+            let mut transaction = self.storage.transaction()?;
+            let Some(table) = transaction
+                .table(&self.table_cache, table_name.clone())
+                .cloned()
+            else {
+                statistics_tracker.forget(table_name);
+                continue;
+            };
+            let plan = LogicalPlan::new(
+                Operator::Analyze(AnalyzeOperator {
+                    table_name: table_name.clone(),
+                    index_metas: table.indexes.clone(),
+                }),
+                vec![TableScanOperator::build(table_name.clone(), &table)],
+            );
+            let iterator = build_write(
+                plan,
+                (&self.table_cache, &self.meta_cache, &self.execution_config),
+                &mut transaction,
+            );
+            if let Err(err) = try_collect(iterator) {
+                log::warn!("failed to analyze the table `{}`: {}", table_name, err);
+                statistics_tracker.analyze_failed(table_name);
+                continue;
+            }
+            let statistics_changes = mem::take(transaction.statistics_changes());
+
+            match transaction.commit() {
+                // the cached plans are invalidated below
+                Ok(()) => {
+                    statistics_tracker.commit(statistics_changes, &self.meta_cache);
+                }
+                Err(err) => {
+                    log::warn!("failed to commit the analyze of `{}`: {}", table_name, err);
+                    statistics_tracker.analyze_failed(table_name);
+                }
+            }
+        }
+        if !table_names.is_empty() {
+            if let Some(plan_cache) = &self.plan_cache {
+                plan_cache.invalidate();
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn new_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
         let guard = self.mdl.read_arc();
         let transaction = self.storage.transaction()?;
@@ -139,17 +302,67 @@ impl<S: Storage> Database<S> {
             _guard: guard,
             meta_cache: self.meta_cache.clone(),
             table_cache: self.table_cache.clone(),
+            execution_config: self.execution_config.clone(),
+            optimizer_pipeline: self.optimizer_pipeline.clone(),
+            plan_cache: self.plan_cache.clone(),
+            statistics_tracker: self.statistics_tracker.clone(),
         })
     }
 
-    pub(crate) fn build_plan(
+    /// Returns the cached plan of the statement, or builds the plan and caches it. Only the
+    /// plans of the queries are cached.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn cached_plan(
         stmt: &Statement,
+        extension: &StatementExtension,
         table_cache: &TableCache,
         meta_cache: &StatisticsMetaCache,
         transaction: &<S as Storage>::TransactionType<'_>,
         scala_functions: &ScalaFunctions,
         table_functions: &TableFunctions,
+        optimizer_pipeline: &OptimizerPipeline,
+        plan_cache: Option<&PlanCache>,
     ) -> Result<LogicalPlan, DatabaseError> {
+        for warning in OptimizerHints::warnings(&extension.hints) {
+            log::warn!("{}", warning);
+        }
+        let build_plan = || {
+            Self::build_plan(
+                stmt,
+                extension,
+                table_cache,
+                meta_cache,
+                transaction,
+                scala_functions,
+                table_functions,
+                optimizer_pipeline,
+            )
+        };
+        let Some(plan_cache) = plan_cache.filter(|_| matches!(stmt, Statement::Query(_))) else {
+            return build_plan().map(|(plan, _)| plan);
+        };
+        let key = PlanCache::key(stmt, extension);
+
+        if let Some(plan) = plan_cache.get(&key) {
+            return Ok(plan);
+        }
+        let version = plan_cache.version();
+        let (plan, literals) = build_plan()?;
+
+        Ok(plan_cache.put(key, version, plan, literals))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn build_plan(
+        stmt: &Statement,
+        extension: &StatementExtension,
+        table_cache: &TableCache,
+        meta_cache: &StatisticsMetaCache,
+        transaction: &<S as Storage>::TransactionType<'_>,
+        scala_functions: &ScalaFunctions,
+        table_functions: &TableFunctions,
+        optimizer_pipeline: &OptimizerPipeline,
+    ) -> Result<(LogicalPlan, Vec<ValueRef>), DatabaseError> {
         let mut binder = Binder::new(
             BinderContext::new(
                 table_cache,
@@ -159,7 +372,8 @@ impl<S: Storage> Database<S> {
                 Arc::new(AtomicUsize::new(0)),
             ),
             None,
-        );
+        )
+        .with_extension(extension.clone());
         /// Build a logical plan.
         ///
         /// SELECT a,b FROM t1 ORDER BY a LIMIT 1;
@@ -169,92 +383,35 @@ impl<S: Storage> Database<S> {
         ///       Project(a,b)
         let source_plan = binder.bind(stmt)?;
         // println!("source_plan plan: {:#?}", source_plan);
+        let literals = binder.take_literals();
+        // the constants held by the binder would be counted as used elsewhere by the plan cache
+        drop(binder);
 
-        let best_plan = Self::default_optimizer(source_plan)
+        let best_plan = optimizer_pipeline
+            .optimizer(source_plan)
+            .hints(OptimizerHints::new(&extension.hints))
             .find_best(Some(&transaction.meta_loader(meta_cache)))?;
         // println!("best_plan plan: {:#?}", best_plan);
 
-        Ok(best_plan)
+        Ok((best_plan, literals))
     }
+}
 
-    pub(crate) fn default_optimizer(source_plan: LogicalPlan) -> HepOptimizer {
-        HepOptimizer::new(source_plan)
-            .batch(
-                "Column Pruning".to_string(),
-                HepBatchStrategy::once_topdown(),
-                vec![NormalizationRuleImpl::ColumnPruning],
-            )
-            .batch(
-                "Simplify Filter".to_string(),
-                HepBatchStrategy::fix_point_topdown(10),
-                vec![
-                    NormalizationRuleImpl::SimplifyFilter,
-                    NormalizationRuleImpl::ConstantCalculation,
-                ],
-            )
-            .batch(
-                "Predicate Pushdown".to_string(),
-                HepBatchStrategy::fix_point_topdown(10),
-                vec![
-                    NormalizationRuleImpl::PushPredicateThroughJoin,
-                    NormalizationRuleImpl::PushPredicateIntoScan,
-                ],
-            )
-            .batch(
-                "Limit Pushdown".to_string(),
-                HepBatchStrategy::fix_point_topdown(10),
-                vec![
-                    NormalizationRuleImpl::LimitProjectTranspose,
-                    NormalizationRuleImpl::PushLimitThroughJoin,
-                    NormalizationRuleImpl::PushLimitIntoTableScan,
-                ],
-            )
-            .batch(
-                "Combine Operators".to_string(),
-                HepBatchStrategy::fix_point_topdown(10),
-                vec![
-                    NormalizationRuleImpl::CollapseProject,
-                    NormalizationRuleImpl::CollapseGroupByAgg,
-                    NormalizationRuleImpl::CombineFilter,
-                ],
-            )
-            .batch(
-                "Expression Remapper".to_string(),
-                HepBatchStrategy::once_topdown(),
-                vec![
-                    NormalizationRuleImpl::ExpressionRemapper,
-                    // TIPS: This rule is necessary
-                    NormalizationRuleImpl::EvaluatorBind,
-                ],
-            )
-            .implementations(vec![
-                // DQL
-                ImplementationRuleImpl::SimpleAggregate,
-                ImplementationRuleImpl::GroupByAggregate,
-                ImplementationRuleImpl::Dummy,
-                ImplementationRuleImpl::Filter,
-                ImplementationRuleImpl::HashJoin,
-                ImplementationRuleImpl::Limit,
-                ImplementationRuleImpl::Projection,
-                ImplementationRuleImpl::SeqScan,
-                ImplementationRuleImpl::IndexScan,
-                ImplementationRuleImpl::FunctionScan,
-                ImplementationRuleImpl::Sort,
-                ImplementationRuleImpl::Values,
-                // DML
-                ImplementationRuleImpl::Analyze,
-                ImplementationRuleImpl::CopyFromFile,
-                ImplementationRuleImpl::CopyToFile,
-                ImplementationRuleImpl::Delete,
-                ImplementationRuleImpl::Insert,
-                ImplementationRuleImpl::Update,
-                // DLL
-                ImplementationRuleImpl::AddColumn,
-                ImplementationRuleImpl::CreateTable,
-                ImplementationRuleImpl::DropColumn,
-                ImplementationRuleImpl::DropTable,
-                ImplementationRuleImpl::Truncate,
-            ])
+/// Applies the statistics changes of a committed transaction, which invalidates the cached
+/// plans chosen by the statistics before.
+fn commit_statistics(
+    statistics_tracker: Option<&StatisticsTracker>,
+    plan_cache: Option<&PlanCache>,
+    statistics_changes: StatisticsChanges,
+    meta_cache: &StatisticsMetaCache,
+) {
+    let Some(statistics_tracker) = statistics_tracker else {
+        return;
+    };
+    if statistics_tracker.commit(statistics_changes, meta_cache) {
+        if let Some(plan_cache) = plan_cache {
+            plan_cache.invalidate();
+        }
     }
 }
 
@@ -265,37 +422,63 @@ pub struct DBTransaction<'a, S: Storage + 'a> {
     _guard: ArcRwLockReadGuard<RawRwLock, ()>,
     pub(crate) meta_cache: Arc<StatisticsMetaCache>,
     pub(crate) table_cache: Arc<ShardingLruCache<String, TableCatalog>>,
+    pub(crate) execution_config: Arc<ExecutionConfig>,
+    optimizer_pipeline: Arc<OptimizerPipeline>,
+    plan_cache: Option<Arc<PlanCache>>,
+    statistics_tracker: Option<Arc<StatisticsTracker>>,
 }
 
 impl<S: Storage> DBTransaction<'_, S> {
     pub fn run<T: AsRef<str>>(&mut self, sql: T) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
-        let stmts = parse_sql(sql)?;
+        let stmts = parse_sql_with_extensions(sql)?;
         if stmts.is_empty() {
             return Err(DatabaseError::EmptyStatement);
         }
-        let stmt = &stmts[0];
+        let (stmt, extension) = &stmts[0];
         if matches!(command_type(stmt)?, CommandType::DDL) {
             return Err(DatabaseError::UnsupportedStmt(
                 "`DDL` is not allowed to execute within a transaction".to_string(),
             ));
         }
-        let mut plan = Database::<S>::build_plan(
+        let mut plan = Database::<S>::cached_plan(
             stmt,
+            extension,
             &self.table_cache,
             &self.meta_cache,
             &self.inner,
             &self.scala_functions,
             &self.table_functions,
+            &self.optimizer_pipeline,
+            self.plan_cache.as_deref(),
         )?;
 
         let schema = plan.output_schema().clone();
-        let executor = build_write(plan, (&self.table_cache, &self.meta_cache), &mut self.inner);
+        let executor = build_write(
+            plan,
+            (&self.table_cache, &self.meta_cache, &self.execution_config),
+            &mut self.inner,
+        );
+        let tuples = try_collect(executor);
+        // `ANALYZE` replaces the statistics before the transaction commits
+        if matches!(stmt, Statement::Analyze { .. }) {
+            if let Some(plan_cache) = &self.plan_cache {
+                plan_cache.invalidate();
+            }
+        }
 
-        Ok((schema, try_collect(executor)?))
+        Ok((schema, tuples?))
     }
 
-    pub fn commit(self) -> Result<(), DatabaseError> {
+    pub fn commit(mut self) -> Result<(), DatabaseError> {
+        let statistics_changes = mem::take(self.inner.statistics_changes());
+
         self.inner.commit()?;
+        commit_statistics(
+            self.statistics_tracker.as_deref(),
+            self.plan_cache.as_deref(),
+            statistics_changes,
+            &self.meta_cache,
+        );
 
         Ok(())
     }
@@ -305,6 +488,8 @@ impl<S: Storage> DBTransaction<'_, S> {
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::db::{DataBaseBuilder, DatabaseError};
+    use crate::parser::parse_sql_with_extensions;
+    use crate::planner::plan_cache::PlanCache;
     use crate::storage::{Storage, TableCache, Transaction};
     use crate::types::tuple::{create_table, Tuple};
     use crate::types::value::DataValue;
@@ -412,6 +597,80 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_plan_cache() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let plan_cache = fnck_sql.plan_cache.clone().unwrap();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values(0, 0), (1, 1)")?;
+
+        let (schema, _) = fnck_sql.run("select * from t1 where a > 0")?;
+        debug_assert_eq!(schema.len(), 2);
+        // the whitespaces and the case of the keywords are normalized
+        let stmts = parse_sql_with_extensions("SELECT *   FROM t1\nWHERE a > 0")?;
+        let key = PlanCache::key(&stmts[0].0, &stmts[0].1);
+        debug_assert!(plan_cache.get(&key).is_some());
+        let (_, tuples) = fnck_sql.run("SELECT *   FROM t1\nWHERE a > 0")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        // the point lookups only differing in the keys share the plan re-bound to the keys
+        let point_lookup = |key: i32| -> Result<Vec<Tuple>, DatabaseError> {
+            let sql = format!("select b from t1 where a = {}", key);
+            let stmts = parse_sql_with_extensions(&sql)?;
+            debug_assert!(plan_cache
+                .get(&PlanCache::key(&stmts[0].0, &stmts[0].1))
+                .is_some());
+
+            Ok(fnck_sql.run(sql)?.1)
+        };
+        let _ = fnck_sql.run("select b from t1 where a = 0")?;
+        let tuples = point_lookup(1)?;
+        debug_assert_eq!(tuples.len(), 1);
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(1))));
+        debug_assert!(point_lookup(2)?.is_empty());
+        // the writes are not cached
+        let _ = fnck_sql.run("insert into t1 values(2, 2)")?;
+        let stmts = parse_sql_with_extensions("insert into t1 values(2, 2)")?;
+        debug_assert!(plan_cache
+            .get(&PlanCache::key(&stmts[0].0, &stmts[0].1))
+            .is_none());
+        let tuples = point_lookup(2)?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(2))));
+
+        // the plan embeds the columns of `t1`, which are changed by the DDL
+        let _ = fnck_sql.run("alter table t1 add column c int")?;
+        debug_assert!(plan_cache.get(&key).is_none());
+        let (schema, tuples) = fnck_sql.run("select * from t1 where a > 0")?;
+        debug_assert_eq!(schema.len(), 3);
+        debug_assert_eq!(tuples[0].values.len(), 3);
+
+        let _ = fnck_sql.run("analyze table t1")?;
+        debug_assert!(plan_cache.get(&key).is_none());
+
+        // the plans are chosen by the statistics updated by the writes
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path().join("tracked"))
+            .auto_analyze_ratio(100.0)
+            .build()?;
+        let plan_cache = fnck_sql.plan_cache.clone().unwrap();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values(0, 0), (1, 1)")?;
+        let _ = fnck_sql.run("analyze table t1")?;
+        let _ = fnck_sql.run("select * from t1 where a > 0")?;
+        debug_assert!(plan_cache.get(&key).is_some());
+        let _ = fnck_sql.run("insert into t1 values(2, 2)")?;
+        debug_assert!(plan_cache.get(&key).is_none());
+
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path().join("uncached"))
+            .plan_cache_size(0)
+            .build()?;
+        debug_assert!(fnck_sql.plan_cache.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_transaction_sql() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -474,4 +733,98 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_auto_analyze() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .auto_analyze_ratio(0.5)
+            .build()?;
+        let statistics_tracker = fnck_sql.statistics_tracker.clone().unwrap();
+        let table_name = Arc::new("t1".to_string());
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let index_id = fnck_sql
+            .storage
+            .transaction()?
+            .table(&fnck_sql.table_cache, table_name.clone())
+            .unwrap()
+            .indexes[0]
+            .id;
+        let values_len = || {
+            fnck_sql
+                .meta_cache
+                .get(&(table_name.clone(), index_id))
+                .map(|statistics_meta| statistics_meta.histogram().values_len())
+        };
+        let stale_tables = || {
+            let transaction = fnck_sql.storage.transaction()?;
+            statistics_tracker.stale_tables(|table_name| transaction.row_count(table_name))
+        };
+
+        let _ = fnck_sql.run("insert into t1 values (0, 0), (1, 1), (2, 2), (3, 3)")?;
+        debug_assert_eq!(values_len(), None);
+        debug_assert_eq!(stale_tables()?, vec![table_name.clone()]);
+        // analyzed before the statement
+        let _ = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(values_len(), Some(4));
+        debug_assert!(stale_tables()?.is_empty());
+
+        // the cached statistics follow the changes below the ratio
+        let _ = fnck_sql.run("insert into t1 values (4, 4)")?;
+        debug_assert_eq!(values_len(), Some(5));
+        debug_assert!(stale_tables()?.is_empty());
+
+        let _ = fnck_sql.run("delete from t1 where a < 3")?;
+        debug_assert_eq!(values_len(), Some(2));
+        debug_assert_eq!(stale_tables()?, vec![table_name.clone()]);
+        let _ = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(values_len(), Some(2));
+        debug_assert!(stale_tables()?.is_empty());
+
+        // the changes of a transaction count once it commits
+        let mut transaction = fnck_sql.new_transaction()?;
+        let _ = transaction.run("update t1 set b = 0")?;
+        let _ = transaction.run("insert into t1 values (5, 5)")?;
+        debug_assert!(stale_tables()?.is_empty());
+        transaction.commit()?;
+        debug_assert_eq!(values_len(), Some(3));
+        debug_assert_eq!(stale_tables()?, vec![table_name.clone()]);
+
+        // `ANALYZE` starts counting again
+        let _ = fnck_sql.run("analyze table t1")?;
+        debug_assert!(stale_tables()?.is_empty());
+
+        // the dropped tables are forgotten
+        let _ = fnck_sql.run("insert into t1 values (6, 6), (7, 7), (8, 8)")?;
+        let _ = fnck_sql.run("drop table t1")?;
+        let _ = fnck_sql.run("select 1")?;
+        debug_assert!(stale_tables()?.is_empty());
+
+        // a table failed to be analyzed is not analyzed again before its backoff
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values (0, 0)")?;
+        debug_assert_eq!(stale_tables()?, vec![table_name.clone()]);
+        statistics_tracker.analyze_failed(&table_name);
+        debug_assert!(stale_tables()?.is_empty());
+        let _ = fnck_sql.run("analyze table t1")?;
+        let _ = fnck_sql.run("insert into t1 values (1, 1)")?;
+        debug_assert_eq!(stale_tables()?, vec![table_name.clone()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_auto_analyze_ratio() {
+        for ratio in [0.0, -0.5, f64::NAN] {
+            let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+            let result = DataBaseBuilder::path(temp_dir.path())
+                .auto_analyze_ratio(ratio)
+                .build();
+            debug_assert!(matches!(
+                result,
+                Err(DatabaseError::InvalidConfig("auto_analyze_ratio", _))
+            ));
+        }
+    }
 }
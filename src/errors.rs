@@ -36,6 +36,8 @@ pub enum DatabaseError {
     DuplicateColumn(String),
     #[error("index: {0} already exists")]
     DuplicateIndex(String),
+    #[error("{0}: {1} already exists")]
+    DuplicateName(&'static str, String),
     #[error("duplicate primary key")]
     DuplicatePrimaryKey,
     #[error("the column has been declared unique and the value already exists")]
@@ -56,6 +58,8 @@ pub enum DatabaseError {
     Incomparable(LogicalType, LogicalType),
     #[error("invalid column: {0}")]
     InvalidColumn(String),
+    #[error("invalid config {0}: {1}")]
+    InvalidConfig(&'static str, String),
     #[error("invalid index")]
     InvalidIndex,
     #[error("invalid table: {0}")]
@@ -144,6 +148,8 @@ pub enum DatabaseError {
     UnsupportedUnaryOperator(LogicalType, UnaryOperator),
     #[error("unsupported binary operator: {0} cannot support {1} for calculations")]
     UnsupportedBinaryOperator(LogicalType, BinaryOperator),
+    #[error("unsupported storage format version: {0}, expected {1}")]
+    UnsupportedStorageVersion(u32, u32),
     #[error("unsupported statement: {0}")]
     UnsupportedStmt(String),
     #[error("values length not match, expect {0}, got {1}")]
@@ -1,4 +1,4 @@
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache};
 use crate::types::index::{Index, IndexType};
@@ -28,7 +28,7 @@ impl From<(AddColumnOperator, LogicalPlan)> for AddColumn {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
     fn execute_mut(
         mut self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -1,6 +1,6 @@
 use crate::execution::dql::projection::Projection;
 use crate::execution::DatabaseError;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::LogicalPlan;
@@ -28,7 +28,7 @@ impl From<(CreateIndexOperator, LogicalPlan)> for CreateIndex {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
     fn execute_mut(
         mut self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -37,25 +37,43 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                 let CreateIndexOperator {
                     table_name,
                     index_name,
-                    columns,
+                    exprs,
+                    include_columns,
+                    predicate,
                     if_not_exists,
                     ty,
                 } = self.op;
 
-                let (column_ids, column_exprs): (Vec<ColumnId>, Vec<ScalarExpression>) = columns
-                    .into_iter()
-                    .filter_map(|column| {
-                        column
-                            .id()
-                            .map(|id| (id, ScalarExpression::ColumnRef(column)))
+                // the index on plain columns is still stored by the ids of the columns
+                let (column_ids, index_exprs) = match exprs
+                    .iter()
+                    .map(|expr| match expr {
+                        ScalarExpression::ColumnRef(column) => column.id(),
+                        _ => None,
                     })
-                    .unzip();
+                    .collect::<Option<Vec<ColumnId>>>()
+                {
+                    Some(column_ids) => (column_ids, None),
+                    None => (vec![], Some(exprs.clone())),
+                };
+                let (include_column_ids, include_exprs): (Vec<ColumnId>, Vec<ScalarExpression>) =
+                    include_columns
+                        .into_iter()
+                        .filter_map(|column| {
+                            column
+                                .id()
+                                .map(|id| (id, ScalarExpression::ColumnRef(column)))
+                        })
+                        .unzip();
                 let schema = self.input.output_schema().clone();
                 let index_id = match transaction.add_index_meta(
                     cache.0,
                     &table_name,
                     index_name,
                     column_ids,
+                    index_exprs,
+                    include_column_ids,
+                    predicate.clone(),
                     ty,
                 ) {
                     Ok(index_id) => index_id,
@@ -68,12 +86,23 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     }
                     err => throw!(err),
                 };
+                let mut column_exprs = exprs;
+                let mut predicate = predicate;
+
+                for expr in column_exprs.iter_mut().chain(predicate.iter_mut()) {
+                    throw!(expr.bind_evaluator());
+                }
                 let mut index_values = Vec::new();
                 let mut coroutine = build_read(self.input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let mut tuple: Tuple = throw!(tuple);
 
+                    if let Some(predicate) = &predicate {
+                        if !throw!(throw!(predicate.eval(&tuple, &schema)).is_true()) {
+                            continue;
+                        }
+                    }
                     let tuple_id = if let Some(tuple_id) = tuple.id.take() {
                         tuple_id
                     } else {
@@ -82,11 +111,12 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     index_values.push((
                         tuple_id,
                         throw!(Projection::projection(&tuple, &column_exprs, &schema)),
+                        throw!(Projection::projection(&tuple, &include_exprs, &schema)),
                     ));
                 }
                 drop(coroutine);
-                for (tuple_id, values) in index_values {
-                    let index = Index::new(index_id, &values, ty);
+                for (tuple_id, values, include_values) in index_values {
+                    let index = Index::new(index_id, &values, ty).with_include(&include_values);
                     throw!(transaction.add_index(table_name.as_str(), index, &tuple_id));
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
@@ -1,5 +1,5 @@
 use crate::errors::DatabaseError;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
@@ -24,7 +24,7 @@ impl From<(DropColumnOperator, LogicalPlan)> for DropColumn {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
     fn execute_mut(
         mut self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
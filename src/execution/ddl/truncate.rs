@@ -1,4 +1,4 @@
-use crate::execution::{Executor, WriteExecutor};
+use crate::execution::{ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
@@ -17,7 +17,7 @@ impl From<TruncateOperator> for Truncate {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Truncate {
     fn execute_mut(
         self,
-        _: (&'a TableCache, &'a StatisticsMetaCache),
+        (_, _, config): (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -25,6 +25,13 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Truncate {
             move || {
                 let TruncateOperator { table_name } = self.op;
 
+                if config.track_statistics {
+                    // the tables whose rows are not counted are taken as modified
+                    let rows = throw!(transaction.row_count(&table_name)).unwrap_or(1);
+                    transaction
+                        .statistics_changes()
+                        .modify_rows(&table_name, rows);
+                }
                 throw!(transaction.drop_data(&table_name));
 
                 yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
@@ -1,7 +1,7 @@
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::optimizer::core::histogram::HistogramBuilder;
 use crate::optimizer::core::statistics_meta::StatisticsMeta;
 use crate::planner::operator::analyze::AnalyzeOperator;
@@ -19,7 +19,7 @@ use std::ops::CoroutineState;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{fmt, fs};
+use std::{cmp, fmt, fs};
 
 const DEFAULT_NUM_OF_BUCKETS: usize = 100;
 const DEFAULT_STATISTICS_META_PATH: &str = "fnck_sql_statistics_metas";
@@ -51,7 +51,7 @@ impl From<(AnalyzeOperator, LogicalPlan)> for Analyze {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
     fn execute_mut(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -74,16 +74,24 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     builders.push((
                         index.id,
                         throw!(index.column_exprs(&table)),
+                        throw!(index.predicate_expr()),
                         throw!(HistogramBuilder::new(index, None)),
                     ));
                 }
 
+                let mut rows = 0;
                 let mut coroutine = build_read(input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple = throw!(tuple);
+                    rows += 1;
 
-                    for (_, exprs, builder) in builders.iter_mut() {
+                    for (_, exprs, predicate, builder) in builders.iter_mut() {
+                        if let Some(predicate) = predicate {
+                            if !throw!(throw!(predicate.eval(&tuple, &schema)).is_true()) {
+                                continue;
+                            }
+                        }
                         let values = throw!(Projection::projection(&tuple, exprs, &schema));
 
                         if values.len() == 1 {
@@ -94,6 +102,12 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     }
                 }
                 drop(coroutine);
+                // folds the row count changes into the base count, and starts counting the tables created
+                // before the rows were counted
+                throw!(transaction.reset_row_count(&table_name, Some(rows)));
+                if cache.2.track_statistics {
+                    transaction.statistics_changes().analyze(&table_name);
+                }
                 let mut values = Vec::with_capacity(builders.len());
                 let ts = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
@@ -106,9 +120,11 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     .join(ts.to_string());
                 throw!(fs::create_dir_all(&dir_path).map_err(DatabaseError::IO));
 
-                for (index_id, _, builder) in builders {
+                for (index_id, _, _, builder) in builders {
                     let path: String = dir_path.join(index_id.to_string()).to_string_lossy().into();
-                    let (histogram, sketch) = throw!(builder.build(DEFAULT_NUM_OF_BUCKETS));
+                    // the tables with fewer values get a bucket of each value
+                    let number_of_buckets = cmp::min(DEFAULT_NUM_OF_BUCKETS, builder.values_len());
+                    let (histogram, sketch) = throw!(builder.build(number_of_buckets));
                     let meta = StatisticsMeta::new(histogram, sketch);
 
                     throw!(meta.to_file(&path));
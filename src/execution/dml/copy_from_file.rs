@@ -1,6 +1,6 @@
 use crate::binder::copy::FileFormat;
 use crate::errors::DatabaseError;
-use crate::execution::{Executor, WriteExecutor};
+use crate::execution::{ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
@@ -10,6 +10,7 @@ use std::fs::File;
 use std::io::BufReader;
 use std::sync::mpsc;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::thread;
 
 pub struct CopyFromFile {
@@ -26,7 +27,7 @@ impl From<CopyFromFileOperator> for CopyFromFile {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyFromFile {
     fn execute_mut(
         self,
-        _: (&'a TableCache, &'a StatisticsMetaCache),
+        (_, _, config): (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -46,6 +47,11 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyFromFile {
                     size += 1;
                 }
                 throw!(handle.join().unwrap());
+                if config.track_statistics {
+                    transaction
+                        .statistics_changes()
+                        .modify_rows(&Arc::new(table_name), size);
+                }
 
                 let handle = thread::spawn(move || return_result(size, tx1));
                 while let Ok(chunk) = rx1.recv() {
@@ -189,8 +195,10 @@ mod tests {
         let storage = db.storage;
         let mut transaction = storage.transaction()?;
 
-        let mut coroutine =
-            executor.execute_mut((&db.table_cache, &db.meta_cache), &mut transaction);
+        let mut coroutine = executor.execute_mut(
+            (&db.table_cache, &db.meta_cache, &db.execution_config),
+            &mut transaction,
+        );
         let tuple = match Pin::new(&mut coroutine).resume(()) {
             CoroutineState::Yielded(tuple) => tuple,
             CoroutineState::Complete(()) => unreachable!(),
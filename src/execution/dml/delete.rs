@@ -1,7 +1,7 @@
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::LogicalPlan;
@@ -11,6 +11,7 @@ use crate::types::index::{Index, IndexId, IndexType};
 use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::ValueRef;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
@@ -30,7 +31,7 @@ impl From<(DeleteOperator, LogicalPlan)> for Delete {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
     fn execute_mut(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -55,24 +56,31 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                     let tuple: Tuple = throw!(tuple);
 
                     for index_meta in table.indexes() {
-                        if let Some(Value {
-                            exprs, value_rows, ..
-                        }) = indexes.get_mut(&index_meta.id)
-                        {
-                            value_rows.push(throw!(Projection::projection(&tuple, exprs, &schema)));
-                        } else {
-                            let exprs = throw!(index_meta.column_exprs(&table));
-                            let values = throw!(Projection::projection(&tuple, &exprs, &schema));
+                        let Value {
+                            exprs,
+                            predicate,
+                            value_rows,
+                            ..
+                        } = match indexes.entry(index_meta.id) {
+                            Entry::Occupied(entry) => entry.into_mut(),
+                            Entry::Vacant(entry) => entry.insert(Value {
+                                exprs: throw!(index_meta.column_exprs(&table)),
+                                predicate: throw!(index_meta.predicate_expr()),
+                                value_rows: vec![],
+                                index_ty: index_meta.ty,
+                            }),
+                        };
 
-                            indexes.insert(
-                                index_meta.id,
-                                Value {
-                                    exprs,
-                                    value_rows: vec![values],
-                                    index_ty: index_meta.ty,
-                                },
-                            );
+                        // the tuples out of the partial index have no entries to delete
+                        if let Some(predicate) = predicate {
+                            if !throw!(throw!(predicate.eval(&tuple, &schema)).is_true()) {
+                                continue;
+                            }
                         }
+                        value_rows.push((
+                            tuple_ids.len(),
+                            throw!(Projection::projection(&tuple, exprs, &schema)),
+                        ));
                     }
                     tuple_ids.push(tuple.id.unwrap());
                 }
@@ -86,14 +94,27 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                     },
                 ) in indexes
                 {
-                    for (i, values) in value_rows.into_iter().enumerate() {
+                    for (i, values) in value_rows {
                         throw!(transaction.del_index(
                             &table_name,
                             &Index::new(index_id, &values, index_ty),
                             Some(&tuple_ids[i]),
                         ));
+                        if cache.2.track_statistics {
+                            transaction.statistics_changes().change_index(
+                                &table_name,
+                                index_id,
+                                &values,
+                                false,
+                            );
+                        }
                     }
                 }
+                if cache.2.track_statistics {
+                    transaction
+                        .statistics_changes()
+                        .modify_rows(&table_name, tuple_ids.len());
+                }
                 for tuple_id in tuple_ids {
                     throw!(transaction.delete(&table_name, tuple_id));
                 }
@@ -105,6 +126,8 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
 
 struct Value {
     exprs: Vec<ScalarExpression>,
-    value_rows: Vec<Vec<ValueRef>>,
+    predicate: Option<ScalarExpression>,
+    /// The values of the index, with the position of the tuple id
+    value_rows: Vec<(usize, Vec<ValueRef>)>,
     index_ty: IndexType,
 }
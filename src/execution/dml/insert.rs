@@ -1,7 +1,7 @@
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
@@ -43,7 +43,7 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
     fn execute_mut(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -103,21 +103,56 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                         });
                     }
                     drop(coroutine);
+                    // the tuples are filled in the order of the columns of the table
+                    let table_schema = table_catalog.schema_ref();
+
                     for index_meta in table_catalog.indexes() {
                         let exprs = throw!(index_meta.column_exprs(&table_catalog));
+                        let include_exprs = throw!(index_meta.include_exprs(&table_catalog));
+                        let predicate = throw!(index_meta.predicate_expr());
 
                         for tuple in tuples.iter() {
-                            let values = throw!(Projection::projection(tuple, &exprs, &schema));
-                            let index = Index::new(index_meta.id, &values, index_meta.ty);
+                            if let Some(predicate) = &predicate {
+                                if !throw!(throw!(predicate.eval(tuple, table_schema)).is_true()) {
+                                    continue;
+                                }
+                            }
+                            let values =
+                                throw!(Projection::projection(tuple, &exprs, table_schema));
+                            let include_values =
+                                throw!(Projection::projection(tuple, &include_exprs, table_schema));
+                            let index = Index::new(index_meta.id, &values, index_meta.ty)
+                                .with_include(&include_values);
 
                             throw!(transaction.add_index(
                                 &table_name,
                                 index,
                                 tuple.id.as_ref().unwrap()
                             ));
+                            if cache.2.track_statistics {
+                                transaction.statistics_changes().change_index(
+                                    &table_name,
+                                    index_meta.id,
+                                    &values,
+                                    true,
+                                );
+                            }
                         }
                     }
+                    if cache.2.track_statistics {
+                        transaction
+                            .statistics_changes()
+                            .modify_rows(&table_name, tuples.len());
+                    }
                     for tuple in tuples {
+                        // `append` only counts the tuples it does not overwrite
+                        if is_overwrite
+                            && !throw!(
+                                transaction.contains_tuple(&table_name, tuple.id.as_ref().unwrap())
+                            )
+                        {
+                            transaction.change_row_count(&table_name, 1);
+                        }
                         throw!(transaction.append(&table_name, tuple, &types, is_overwrite));
                     }
                 }
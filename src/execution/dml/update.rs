@@ -1,6 +1,6 @@
 use crate::catalog::TableName;
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, WriteExecutor};
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
@@ -35,7 +35,7 @@ impl From<(UpdateOperator, LogicalPlan, LogicalPlan)> for Update {
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
     fn execute_mut(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -77,8 +77,15 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                     let mut index_metas = Vec::new();
                     for index_meta in table_catalog.indexes() {
                         let exprs = throw!(index_meta.column_exprs(&table_catalog));
+                        let include_exprs = throw!(index_meta.include_exprs(&table_catalog));
+                        let predicate = throw!(index_meta.predicate_expr());
 
                         for tuple in tuples.iter() {
+                            if let Some(predicate) = &predicate {
+                                if !throw!(throw!(predicate.eval(tuple, &input_schema)).is_true()) {
+                                    continue;
+                                }
+                            }
                             let values =
                                 throw!(Projection::projection(tuple, &exprs, &input_schema));
                             let index = Index::new(index_meta.id, &values, index_meta.ty);
@@ -87,8 +94,21 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                                 &index,
                                 Some(tuple.id.as_ref().unwrap())
                             ));
+                            if cache.2.track_statistics {
+                                transaction.statistics_changes().change_index(
+                                    &table_name,
+                                    index_meta.id,
+                                    &values,
+                                    false,
+                                );
+                            }
                         }
-                        index_metas.push((index_meta, exprs));
+                        index_metas.push((index_meta, exprs, include_exprs, predicate));
+                    }
+                    if cache.2.track_statistics {
+                        transaction
+                            .statistics_changes()
+                            .modify_rows(&table_name, tuples.len());
                     }
                     for mut tuple in tuples {
                         let mut is_overwrite = true;
@@ -104,15 +124,35 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                                 tuple.values[i] = value.clone();
                             }
                         }
-                        for (index_meta, exprs) in index_metas.iter() {
+                        for (index_meta, exprs, include_exprs, predicate) in index_metas.iter() {
+                            if let Some(predicate) = predicate {
+                                if !throw!(throw!(predicate.eval(&tuple, &input_schema)).is_true())
+                                {
+                                    continue;
+                                }
+                            }
                             let values =
                                 throw!(Projection::projection(&tuple, exprs, &input_schema));
-                            let index = Index::new(index_meta.id, &values, index_meta.ty);
+                            let include_values = throw!(Projection::projection(
+                                &tuple,
+                                include_exprs,
+                                &input_schema
+                            ));
+                            let index = Index::new(index_meta.id, &values, index_meta.ty)
+                                .with_include(&include_values);
                             throw!(transaction.add_index(
                                 &table_name,
                                 index,
                                 tuple.id.as_ref().unwrap()
                             ));
+                            if cache.2.track_statistics {
+                                transaction.statistics_changes().change_index(
+                                    &table_name,
+                                    index_meta.id,
+                                    &values,
+                                    true,
+                                );
+                            }
                         }
 
                         throw!(transaction.append(&table_name, tuple, &types, is_overwrite));
@@ -32,23 +32,14 @@ impl Accumulator for AvgAccumulator {
     }
 
     fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
-        let mut value = self.inner.evaluate()?;
-        let value_ty = value.logical_type();
-
         if self.count == 0 {
-            return Ok(Arc::new(DataValue::init(&value_ty)));
+            return Ok(Arc::new(DataValue::init(&LogicalType::Double)));
         }
-        let quantity = if value_ty.is_signed_numeric() {
-            DataValue::Int64(Some(self.count as i64))
-        } else {
-            DataValue::UInt32(Some(self.count as u32))
-        };
-        let quantity_ty = quantity.logical_type();
+        let value = DataValue::clone(self.inner.evaluate()?.as_ref()).cast(&LogicalType::Double)?;
+        let quantity = DataValue::Float64(Some(self.count as f64));
+        let evaluator =
+            EvaluatorFactory::binary_create(LogicalType::Double, BinaryOperator::Divide)?;
 
-        if value_ty != quantity_ty {
-            value = Arc::new(DataValue::clone(&value).cast(&quantity_ty)?)
-        }
-        let evaluator = EvaluatorFactory::binary_create(quantity_ty, BinaryOperator::Divide)?;
         Ok(Arc::new(evaluator.0.binary_eval(&value, &quantity)))
     }
 }
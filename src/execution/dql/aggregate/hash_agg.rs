@@ -1,7 +1,8 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::{create_accumulators, Accumulator};
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::spill::{values_size, SpillPartitions, SpillReader, MAX_SPILL_DEPTH};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::LogicalPlan;
@@ -11,7 +12,11 @@ use crate::types::tuple::{SchemaRef, Tuple};
 use crate::types::value::ValueRef;
 use ahash::HashMap;
 use itertools::Itertools;
+use std::collections::VecDeque;
+use std::iter;
+use std::mem;
 use std::ops::{Coroutine, CoroutineState};
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 
 pub struct HashAggExecutor {
@@ -42,7 +47,7 @@ impl From<(AggregateOperator, LogicalPlan)> for HashAggExecutor {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -53,18 +58,53 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
                     groupby_exprs,
                     mut input,
                 } = self;
-
-                let mut agg_status =
-                    HashAggStatus::new(input.output_schema().clone(), agg_calls, groupby_exprs);
+                let (_, _, config) = cache;
+                let schema_ref = input.output_schema().clone();
 
                 let mut coroutine = build_read(input, cache, transaction);
+                let mut tuples: Box<dyn Iterator<Item = Result<Tuple, DatabaseError>> + 'a> =
+                    Box::new(iter::from_fn(move || {
+                        match Pin::new(&mut coroutine).resume(()) {
+                            CoroutineState::Yielded(tuple) => Some(tuple),
+                            CoroutineState::Complete(()) => None,
+                        }
+                    }));
+                let mut depth = 0;
+                // external aggregation: groups that did not fit into memory are spilled
+                // to partitions and aggregated one partition at a time
+                let mut spilled_partitions = VecDeque::new();
 
-                while let CoroutineState::Yielded(result) = Pin::new(&mut coroutine).resume(()) {
-                    throw!(agg_status.update(throw!(result)));
-                }
+                loop {
+                    let mut agg_status = HashAggStatus::new(
+                        schema_ref.clone(),
+                        agg_calls.clone(),
+                        groupby_exprs.clone(),
+                    );
+                    if depth < MAX_SPILL_DEPTH {
+                        if let Some(budget) = config.hash_memory_budget {
+                            agg_status.with_spill(budget, &config.spill_path, depth);
+                        }
+                    }
+
+                    for tuple in tuples {
+                        throw!(agg_status.update(throw!(tuple)));
+                    }
+                    for tuple in throw!(agg_status.as_tuples()) {
+                        yield Ok(tuple);
+                    }
+                    if let Some(partitions) = throw!(agg_status.spilled()) {
+                        spilled_partitions.extend(
+                            partitions
+                                .into_iter()
+                                .map(|partition| (partition, depth + 1)),
+                        );
+                    }
 
-                for tuple in throw!(agg_status.as_tuples()) {
-                    yield Ok(tuple);
+                    let Some((partition, partition_depth)) = spilled_partitions.pop_front() else {
+                        break;
+                    };
+                    tuples = Box::new(partition);
+                    depth = partition_depth;
                 }
             },
         )
@@ -79,6 +119,13 @@ pub(crate) struct HashAggStatus {
 
     group_columns: Vec<ColumnRef>,
     group_hash_accs: HashMap<Vec<ValueRef>, Vec<Box<dyn Accumulator>>>,
+
+    memory_budget: usize,
+    groups_size: usize,
+    spill_path: PathBuf,
+    depth: usize,
+    // tuples of the groups that arrived after the memory budget was exceeded
+    partitions: Option<SpillPartitions>,
 }
 
 impl HashAggStatus {
@@ -93,9 +140,23 @@ impl HashAggStatus {
             groupby_exprs,
             group_columns: vec![],
             group_hash_accs: Default::default(),
+            memory_budget: usize::MAX,
+            groups_size: 0,
+            spill_path: PathBuf::new(),
+            depth: 0,
+            partitions: None,
         }
     }
 
+    /// Once the groups kept in memory exceed `memory_budget`, input tuples of groups
+    /// not seen yet are partitioned to `spill_path` instead, to be aggregated after the
+    /// in-memory groups were output.
+    pub(crate) fn with_spill(&mut self, memory_budget: usize, spill_path: &Path, depth: usize) {
+        self.memory_budget = memory_budget;
+        self.spill_path = spill_path.to_path_buf();
+        self.depth = depth;
+    }
+
     pub(crate) fn update(&mut self, tuple: Tuple) -> Result<(), DatabaseError> {
         // 1. build group and agg columns for hash_agg columns.
         // Tips: AggCall First
@@ -127,6 +188,24 @@ impl HashAggStatus {
             .map(|expr| expr.eval(&tuple, &self.schema_ref))
             .try_collect()?;
 
+        if !self.group_hash_accs.contains_key(&group_keys) {
+            if self.groups_size > self.memory_budget {
+                if self.partitions.is_none() {
+                    self.partitions = Some(SpillPartitions::new(
+                        &self.spill_path,
+                        &self.schema_ref,
+                        self.depth,
+                    )?);
+                }
+                if let Some(partitions) = self.partitions.as_mut() {
+                    partitions.write(&group_keys, &tuple)?;
+                }
+                return Ok(());
+            }
+            self.groups_size += values_size(&group_keys)
+                + self.agg_calls.len() * mem::size_of::<Box<dyn Accumulator>>();
+        }
+
         for (acc, value) in self
             .group_hash_accs
             .entry(group_keys)
@@ -140,6 +219,14 @@ impl HashAggStatus {
         Ok(())
     }
 
+    /// Partitions holding the input of groups that did not fit into memory.
+    pub(crate) fn spilled(&mut self) -> Result<Option<Vec<SpillReader>>, DatabaseError> {
+        self.partitions
+            .take()
+            .map(|partitions| partitions.finish(&self.schema_ref))
+            .transpose()
+    }
+
     pub(crate) fn as_tuples(&mut self) -> Result<Vec<Tuple>, DatabaseError> {
         self.group_hash_accs
             .drain()
@@ -162,8 +249,8 @@ mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::errors::DatabaseError;
     use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
-    use crate::execution::dql::test::build_integers;
-    use crate::execution::{try_collect, ReadExecutor};
+    use crate::execution::dql::test::{assert_same_results, build_integers};
+    use crate::execution::{try_collect, ExecutionConfig, ReadExecutor};
     use crate::expression::agg::AggKind;
     use crate::expression::ScalarExpression;
     use crate::planner::operator::aggregate::AggregateOperator;
@@ -185,6 +272,7 @@ mod test {
     fn test_hash_agg() -> Result<(), DatabaseError> {
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
 
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path()).unwrap();
@@ -236,12 +324,13 @@ mod test {
             }),
             childrens: vec![],
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         };
 
         let tuples = try_collect(
             HashAggExecutor::from((operator, input))
-                .execute((&table_cache, &meta_cache), &transaction),
+                .execute((&table_cache, &meta_cache, &execution_config), &transaction),
         )?;
 
         println!(
@@ -261,4 +350,30 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_external_hash_agg() -> Result<(), DatabaseError> {
+        let values = (0..1000)
+            .map(|i| {
+                if i % 13 == 0 {
+                    format!("({i}, null, {})", i % 7)
+                } else {
+                    format!("({i}, {}, {})", (i * 7919) % 300, i % 7)
+                }
+            })
+            .join(", ");
+
+        assert_same_results(
+            |builder| Ok(builder.hash_memory_budget(1024)),
+            &[
+                "create table t1 (a int primary key, b int, c int)".to_string(),
+                format!("insert into t1 values {values}"),
+            ],
+            &[
+                "select b, count(*), sum(a), max(c) from t1 group by b order by b".to_string(),
+                "select b, c, count(distinct a) from t1 group by b, c order by b, c".to_string(),
+                "select distinct c, b from t1 order by c, b".to_string(),
+            ],
+        )
+    }
 }
@@ -2,6 +2,7 @@ mod avg;
 mod count;
 pub mod hash_agg;
 mod min_max;
+pub mod row_count;
 pub mod simple_agg;
 mod sum;
 
@@ -28,7 +29,10 @@ pub trait Accumulator: Send + Sync {
 
 fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, DatabaseError> {
     if let ScalarExpression::AggCall {
-        kind, ty, distinct, ..
+        kind,
+        ty,
+        distinct,
+        args,
     } = expr
     {
         Ok(match (kind, distinct) {
@@ -38,7 +42,7 @@ fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, D
             (AggKind::Sum, true) => Box::new(DistinctSumAccumulator::new(ty)?),
             (AggKind::Min, _) => Box::new(MinMaxAccumulator::new(ty, false)),
             (AggKind::Max, _) => Box::new(MinMaxAccumulator::new(ty, true)),
-            (AggKind::Avg, _) => Box::new(AvgAccumulator::new(ty)?),
+            (AggKind::Avg, _) => Box::new(AvgAccumulator::new(&args[0].return_type())?),
         })
     } else {
         unreachable!(
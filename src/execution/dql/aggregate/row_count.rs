@@ -0,0 +1,62 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::simple_agg::SimpleAggExecutor;
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Answers `COUNT(*)` by the row count of the table, and counts the tuples of the scan if the
+/// rows of the table are not counted yet.
+pub struct RowCountExecutor {
+    op: AggregateOperator,
+    input: LogicalPlan,
+}
+
+impl From<(AggregateOperator, LogicalPlan)> for RowCountExecutor {
+    fn from((op, input): (AggregateOperator, LogicalPlan)) -> Self {
+        RowCountExecutor { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for RowCountExecutor {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let RowCountExecutor { op, input } = self;
+
+                let Operator::TableScan(scan_op) = &input.operator else {
+                    unreachable!("the row count is only of the table scan")
+                };
+                if let Some(rows) = throw!(transaction.row_count(&scan_op.table_name)) {
+                    // `COUNT(*)` is an integer as the one counted by the aggregate
+                    let rows = throw!(i32::try_from(rows).map_err(DatabaseError::from));
+
+                    yield Ok(Tuple {
+                        id: None,
+                        values: vec![Arc::new(DataValue::Int32(Some(rows)))],
+                    });
+                    return;
+                }
+                let mut coroutine =
+                    SimpleAggExecutor::from((op, input)).execute(cache, transaction);
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    yield tuple;
+                }
+            },
+        )
+    }
+}
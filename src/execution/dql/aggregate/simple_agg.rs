@@ -1,5 +1,5 @@
 use crate::execution::dql::aggregate::create_accumulators;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::LogicalPlan;
@@ -28,7 +28,7 @@ impl From<(AggregateOperator, LogicalPlan)> for SimpleAggExecutor {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SimpleAggExecutor {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
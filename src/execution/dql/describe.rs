@@ -1,6 +1,6 @@
 use crate::catalog::{ColumnCatalog, TableName};
 use crate::execution::DatabaseError;
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::operator::describe::DescribeOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
@@ -43,7 +43,7 @@ impl From<DescribeOperator> for Describe {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
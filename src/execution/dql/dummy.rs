@@ -1,11 +1,15 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::types::tuple::Tuple;
 
 pub struct Dummy {}
 
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Dummy {
-    fn execute(self, _: (&'a TableCache, &'a StatisticsMetaCache), _: &T) -> Executor<'a> {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        _: &T,
+    ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
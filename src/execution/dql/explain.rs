@@ -1,34 +1,380 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::profiler::{OperatorProfile, Profiler};
+use crate::execution::{build_write, ExecutionConfig, Executor, ReadExecutor, WriteExecutor};
+use crate::optimizer::core::cost_model::Estimate;
+use crate::planner::operator::explain::ExplainFormat;
+use crate::planner::operator::PhysicalOption;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::index::IndexInfo;
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, Utf8Type};
+use serde_json::{json, Value};
 use sqlparser::ast::CharLengthUnits;
+use std::ops::{Coroutine, CoroutineState};
+use std::pin::Pin;
 use std::sync::Arc;
 
 pub struct Explain {
     plan: LogicalPlan,
+    format: ExplainFormat,
 }
 
-impl From<LogicalPlan> for Explain {
-    fn from(plan: LogicalPlan) -> Self {
-        Explain { plan }
+impl From<(LogicalPlan, ExplainFormat)> for Explain {
+    fn from((plan, format): (LogicalPlan, ExplainFormat)) -> Self {
+        Explain { plan, format }
     }
 }
 
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Explain {
-    fn execute(self, _: (&'a TableCache, &'a StatisticsMetaCache), _: &T) -> Executor<'a> {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        _: &T,
+    ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let values = vec![Arc::new(DataValue::Utf8 {
-                    value: Some(self.plan.explain(0)),
-                    ty: Utf8Type::Variable(None),
-                    unit: CharLengthUnits::Characters,
-                })];
+                let plan = match self.format {
+                    ExplainFormat::Text => self.plan.explain(0),
+                    format => ExplainNode::build(&self.plan).render(format),
+                };
 
-                yield Ok(Tuple { id: None, values });
+                yield Ok(plan_tuple(plan));
             },
         )
     }
 }
+
+/// Executes the plan, whose output is dropped, and explains it with the actual rows, time and
+/// keys read of each operator.
+pub struct ExplainAnalyze {
+    plan: LogicalPlan,
+    format: ExplainFormat,
+}
+
+impl From<(LogicalPlan, ExplainFormat)> for ExplainAnalyze {
+    fn from((plan, format): (LogicalPlan, ExplainFormat)) -> Self {
+        ExplainAnalyze { plan, format }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for ExplainAnalyze {
+    fn execute_mut(
+        self,
+        (table_cache, meta_cache, config): (
+            &'a TableCache,
+            &'a StatisticsMetaCache,
+            &'a ExecutionConfig,
+        ),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let profiler = Arc::new(Profiler::new(&self.plan));
+                let config = ExecutionConfig {
+                    profiler: Some(profiler.clone()),
+                    ..config.clone()
+                };
+                let mut coroutine =
+                    build_write(self.plan, (table_cache, meta_cache, &config), transaction);
+                // the executors borrow the config, so the error is yielded after they are dropped
+                let mut result = Ok(());
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    if let Err(err) = tuple {
+                        result = Err(err);
+                        break;
+                    }
+                }
+                drop(coroutine);
+                throw!(result);
+
+                yield Ok(plan_tuple(profiler.explain().render(self.format)));
+            },
+        )
+    }
+}
+
+fn plan_tuple(plan: String) -> Tuple {
+    let values = vec![Arc::new(DataValue::Utf8 {
+        value: Some(plan),
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    })];
+
+    Tuple { id: None, values }
+}
+
+/// A node of the plan rendered by `EXPLAIN`, along with its actual execution under
+/// `EXPLAIN ANALYZE`.
+#[derive(Debug, Clone)]
+pub(crate) struct ExplainNode {
+    /// The line of the node in the text format
+    pub(crate) line: String,
+    pub(crate) operator: String,
+    pub(crate) physical_option: Option<PhysicalOption>,
+    pub(crate) estimate: Option<Estimate>,
+    pub(crate) actual: Option<OperatorProfile>,
+    pub(crate) children: Vec<ExplainNode>,
+}
+
+impl ExplainNode {
+    /// The node of the plan, without its children.
+    pub(crate) fn new(plan: &LogicalPlan) -> Self {
+        ExplainNode {
+            line: plan.explain_node(),
+            operator: plan.operator.to_string(),
+            physical_option: plan.physical_option.clone(),
+            estimate: plan.estimate,
+            actual: None,
+            children: vec![],
+        }
+    }
+
+    pub(crate) fn build(plan: &LogicalPlan) -> Self {
+        ExplainNode {
+            children: plan.childrens.iter().map(ExplainNode::build).collect(),
+            ..ExplainNode::new(plan)
+        }
+    }
+
+    pub(crate) fn render(&self, format: ExplainFormat) -> String {
+        match format {
+            ExplainFormat::Text => {
+                let mut result = String::new();
+                self.text(0, &mut result);
+                result
+            }
+            // `Value` is always serializable
+            ExplainFormat::Json => serde_json::to_string_pretty(&self.json()).unwrap(),
+            ExplainFormat::Dot => {
+                let mut result = "digraph plan {\n    node [shape=box];\n".to_string();
+                self.dot(&mut 0, &mut result);
+                result.push('}');
+                result
+            }
+        }
+    }
+
+    fn text(&self, indentation: usize, result: &mut String) {
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&format!(
+            "{:indent$}{}",
+            "",
+            self.line,
+            indent = indentation
+        ));
+        if let Some(actual) = &self.actual {
+            result.push_str(&format!(" {}", actual));
+        }
+        for child in &self.children {
+            child.text(indentation + 2, result);
+        }
+    }
+
+    fn json(&self) -> Value {
+        fn index_json(index_info: &IndexInfo) -> Value {
+            json!({
+                "name": index_info.meta.name,
+                "range": index_info.range.as_ref().map(|range| range.to_string()),
+                "covered": index_info.covered,
+            })
+        }
+        let indexes = match &self.physical_option {
+            Some(PhysicalOption::IndexScan(index_info)) => vec![index_json(index_info)],
+            Some(PhysicalOption::IndexMerge(index_merge)) => {
+                index_merge.index_infos.iter().map(index_json).collect()
+            }
+            Some(
+                PhysicalOption::IndexSeek(index_meta)
+                | PhysicalOption::IndexNestLoopJoin(index_meta),
+            ) => vec![json!({
+                "name": index_meta.name,
+                "range": null,
+                "covered": false,
+            })],
+            _ => vec![],
+        };
+        let mut node = json!({
+            "operator": self.operator,
+            "physical_option": self.physical_option.as_ref().map(|option| option.to_string()),
+            "indexes": indexes,
+            "estimate": self.estimate.map(|estimate| json!({
+                "rows": estimate.rows,
+                "cost": estimate.cost.map(|cost| json!({
+                    "total": cost.total(),
+                    "cpu": cost.cpu,
+                    "io": cost.io,
+                })),
+            })),
+            "children": self.children.iter().map(ExplainNode::json).collect::<Vec<_>>(),
+        });
+        if let Some(PhysicalOption::IndexMerge(index_merge)) = &self.physical_option {
+            node["index_merge"] = json!(if index_merge.is_union {
+                "union"
+            } else {
+                "intersection"
+            });
+        }
+        if let Some(actual) = &self.actual {
+            node["actual"] = json!({
+                "rows": actual.rows,
+                "loops": actual.loops,
+                "time_ms": actual.elapsed.as_secs_f64() * 1000.0,
+                "keys_read": actual.keys_read,
+            });
+        }
+        node
+    }
+
+    /// Writes the node and the edges to its children, the nodes are numbered in pre-order.
+    fn dot(&self, next_id: &mut usize, result: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+
+        let label = [
+            Some(self.operator.clone()),
+            self.physical_option
+                .as_ref()
+                .map(|option| format!("[{}]", option)),
+            self.estimate.map(|estimate| estimate.to_string()),
+            self.actual.map(|actual| actual.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|line| line.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect::<Vec<_>>()
+        .join("\\n");
+        result.push_str(&format!("    {} [label=\"{}\"];\n", id, label));
+
+        for child in &self.children {
+            let child_id = child.dot(next_id, result);
+            result.push_str(&format!("    {} -> {};\n", id, child_id));
+        }
+        id
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use crate::types::value::DataValue;
+    use itertools::Itertools;
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_explain_analyze() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let values = (0..100).map(|i| format!("({i}, {})", i % 10)).join(", ");
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run(format!("insert into t1 values {values}"))?;
+
+        let (_, tuples) = database.run("explain analyze select * from t1 where b = 3")?;
+        let DataValue::Utf8 {
+            value: Some(plan), ..
+        } = tuples[0].values[0].as_ref()
+        else {
+            unreachable!()
+        };
+        let line = |operator: &str| {
+            plan.lines()
+                .find(|line| line.trim_start().starts_with(operator))
+                .unwrap_or_else(|| panic!("{operator} not found: {plan}"))
+        };
+        debug_assert!(
+            line("Projection").contains("actual rows: 10, loops: 1,"),
+            "{plan}"
+        );
+        debug_assert!(
+            line("Filter").contains("actual rows: 10, loops: 1,"),
+            "{plan}"
+        );
+        debug_assert!(
+            line("TableScan").contains("actual rows: 100, loops: 1,"),
+            "{plan}"
+        );
+        debug_assert!(!line("TableScan").contains("keys read: 0)"), "{plan}");
+        debug_assert!(!plan.contains("never executed"), "{plan}");
+
+        // the statement is executed
+        let _ = database.run("explain analyze insert into t1 values (100, 0)")?;
+        let (_, tuples) = database.run("select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(101))));
+
+        debug_assert!(database
+            .run("explain analyze create table t2 (a int primary key)")
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_formats() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let plan = |sql: &str| -> Result<String, DatabaseError> {
+            let (_, tuples) = database.run(sql)?;
+            let DataValue::Utf8 {
+                value: Some(plan), ..
+            } = tuples[0].values[0].as_ref()
+            else {
+                unreachable!()
+            };
+            Ok(plan.clone())
+        };
+        fn find_index_scan(node: &Value) -> Option<&Value> {
+            if node["indexes"]
+                .as_array()
+                .is_some_and(|indexes| !indexes.is_empty())
+            {
+                return Some(node);
+            }
+            node["children"]
+                .as_array()?
+                .iter()
+                .find_map(find_index_scan)
+        }
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("insert into t1 values (0, 0), (1, 1), (2, 2)")?;
+        let _ = database.run("analyze table t1")?;
+
+        let json: Value =
+            serde_json::from_str(&plan("explain (format json) select * from t1 where a = 1")?)
+                .unwrap();
+        debug_assert!(json["operator"]
+            .as_str()
+            .is_some_and(|operator| operator.starts_with("Projection")));
+        debug_assert!(json.get("actual").is_none());
+        let scan = find_index_scan(&json).unwrap();
+        debug_assert_eq!(scan["indexes"][0]["name"], "pk_a");
+        debug_assert!(scan["indexes"][0]["range"].is_string());
+        debug_assert!(scan["physical_option"]
+            .as_str()
+            .is_some_and(|option| option.starts_with("IndexScan By pk_a")));
+
+        let json: Value = serde_json::from_str(&plan(
+            "explain (analyze, format json) select * from t1 where a = 1",
+        )?)
+        .unwrap();
+        debug_assert_eq!(json["actual"]["rows"], 1);
+        debug_assert_eq!(json["actual"]["loops"], 1);
+
+        let dot = plan("explain (format dot) select * from t1 where b = 1")?;
+        debug_assert!(dot.starts_with("digraph plan {"), "{dot}");
+        debug_assert!(dot.contains("    0 [label=\"Projection"), "{dot}");
+        debug_assert!(dot.contains("    0 -> 1;"), "{dot}");
+        debug_assert!(dot.ends_with('}'), "{dot}");
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::filter::FilterOperator;
 use crate::planner::LogicalPlan;
@@ -22,7 +22,7 @@ impl From<(FilterOperator, LogicalPlan)> for Filter {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Filter {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -1,4 +1,4 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::function::table::TableFunction;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
@@ -17,7 +17,11 @@ impl From<FunctionScanOperator> for FunctionScan {
 }
 
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for FunctionScan {
-    fn execute(self, _: (&'a TableCache, &'a StatisticsMetaCache), _: &T) -> Executor<'a> {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        _: &T,
+    ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
@@ -0,0 +1,129 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::join::runtime_filter::RuntimeFilterRef;
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
+use crate::expression::range_detacher::Range;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::index::{IndexInfo, IndexMergeInfo, IndexType};
+use crate::types::value::ValueRef;
+use ahash::RandomState;
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+pub(crate) struct IndexMergeScan {
+    op: TableScanOperator,
+    index_merge: IndexMergeInfo,
+}
+
+impl From<(TableScanOperator, IndexMergeInfo)> for IndexMergeScan {
+    fn from((op, index_merge): (TableScanOperator, IndexMergeInfo)) -> Self {
+        IndexMergeScan { op, index_merge }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexMergeScan {
+    fn execute(
+        self,
+        (table_cache, _, _): (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let TableScanOperator {
+                    table_name,
+                    columns,
+                    limit,
+                    is_reverse,
+                    runtime_filters,
+                    ..
+                } = self.op;
+                let schema = columns
+                    .iter()
+                    .map(|(_, column)| column.clone())
+                    .collect_vec();
+                let IndexMergeInfo {
+                    index_infos,
+                    is_union,
+                } = self.index_merge;
+
+                let table = throw!(transaction
+                    .table(table_cache, table_name.clone())
+                    .ok_or(DatabaseError::TableNotFound));
+                let (primary_key_index, primary_key) = throw!(table.primary_key());
+                let primary_key_column = vec![(primary_key_index, primary_key.clone())];
+                let primary_key_meta = throw!(table
+                    .indexes()
+                    .find(|index_meta| index_meta.ty == IndexType::PrimaryKey)
+                    .cloned()
+                    .ok_or(DatabaseError::PrimaryKeyNotFound));
+                let mut tuple_ids: Option<HashSet<ValueRef, RandomState>> = None;
+
+                for IndexInfo { meta, range, .. } in index_infos {
+                    let ranges = match range {
+                        Some(Range::SortedRanges(ranges)) => ranges,
+                        Some(range) => vec![range],
+                        None => unreachable!("the index of the merge must have a range"),
+                    };
+                    // the primary key is decoded from the secondary index directly
+                    let covered = meta.ty != IndexType::PrimaryKey;
+                    let mut iter = throw!(transaction.read_by_index(
+                        table_cache,
+                        table_name.clone(),
+                        (None, None),
+                        primary_key_column.clone(),
+                        meta,
+                        ranges,
+                        covered,
+                        false,
+                    ));
+                    let mut index_tuple_ids = HashSet::default();
+
+                    while let Some(tuple) = throw!(iter.next_tuple()) {
+                        index_tuple_ids.insert(tuple.values[0].clone());
+                    }
+                    tuple_ids = Some(match tuple_ids {
+                        Some(mut merged_ids) if is_union => {
+                            merged_ids.extend(index_tuple_ids);
+                            merged_ids
+                        }
+                        Some(mut merged_ids) => {
+                            merged_ids.retain(|tuple_id| index_tuple_ids.contains(tuple_id));
+                            merged_ids
+                        }
+                        None => index_tuple_ids,
+                    });
+                }
+                let ranges = tuple_ids
+                    .unwrap_or_default()
+                    .into_iter()
+                    .sorted_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                    .map(Range::Eq)
+                    .collect_vec();
+                let mut iter = throw!(transaction.read_by_index(
+                    table_cache,
+                    table_name,
+                    limit,
+                    columns,
+                    primary_key_meta,
+                    ranges,
+                    false,
+                    is_reverse,
+                ));
+
+                while let Some(tuple) = throw!(iter.next_tuple()) {
+                    if !throw!(RuntimeFilterRef::contains_all(
+                        &runtime_filters,
+                        &tuple,
+                        &schema
+                    )) {
+                        continue;
+                    }
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
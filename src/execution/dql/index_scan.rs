@@ -1,18 +1,23 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::dql::join::runtime_filter::RuntimeFilterRef;
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::range_detacher::Range;
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::index::IndexMetaRef;
+use itertools::Itertools;
 
 pub(crate) struct IndexScan {
     op: TableScanOperator,
     index_by: IndexMetaRef,
     ranges: Vec<Range>,
+    covered: bool,
 }
 
-impl From<(TableScanOperator, IndexMetaRef, Range)> for IndexScan {
-    fn from((op, index_by, range): (TableScanOperator, IndexMetaRef, Range)) -> Self {
+impl From<(TableScanOperator, IndexMetaRef, Range, bool)> for IndexScan {
+    fn from(
+        (op, index_by, range, covered): (TableScanOperator, IndexMetaRef, Range, bool),
+    ) -> Self {
         let ranges = match range {
             Range::SortedRanges(ranges) => ranges,
             range => vec![range],
@@ -22,6 +27,7 @@ impl From<(TableScanOperator, IndexMetaRef, Range)> for IndexScan {
             op,
             index_by,
             ranges,
+            covered,
         }
     }
 }
@@ -29,7 +35,7 @@ impl From<(TableScanOperator, IndexMetaRef, Range)> for IndexScan {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
     fn execute(
         self,
-        (table_cache, _): (&'a TableCache, &'a StatisticsMetaCache),
+        (table_cache, _, _): (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -39,8 +45,14 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
                     table_name,
                     columns,
                     limit,
+                    is_reverse,
+                    runtime_filters,
                     ..
                 } = self.op;
+                let schema = columns
+                    .iter()
+                    .map(|(_, column)| column.clone())
+                    .collect_vec();
 
                 let mut iter = transaction
                     .read_by_index(
@@ -50,10 +62,19 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
                         columns,
                         self.index_by,
                         self.ranges,
+                        self.covered,
+                        is_reverse,
                     )
                     .unwrap();
 
                 while let Some(tuple) = throw!(iter.next_tuple()) {
+                    if !throw!(RuntimeFilterRef::contains_all(
+                        &runtime_filters,
+                        &tuple,
+                        &schema
+                    )) {
+                        continue;
+                    }
                     yield Ok(tuple);
                 }
             },
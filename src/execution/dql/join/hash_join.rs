@@ -1,7 +1,9 @@
 use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::execution::dql::join::joins_nullable;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::dql::join::runtime_filter::{RuntimeFilterBuilder, RuntimeFilterRef};
+use crate::execution::spill::{estimated_size, SpillPartitions, MAX_SPILL_DEPTH};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::LogicalPlan;
@@ -12,6 +14,8 @@ use crate::types::value::{DataValue, ValueRef, NULL_VALUE};
 use crate::utils::bit_vector::BitVector;
 use ahash::HashMap;
 use itertools::Itertools;
+use std::collections::VecDeque;
+use std::iter;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -44,7 +48,7 @@ impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for HashJoin {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -56,60 +60,156 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
                     mut left_input,
                     mut right_input,
                 } = self;
-                let mut join_status = HashJoinStatus::new(
-                    on,
-                    ty,
-                    left_input.output_schema(),
-                    right_input.output_schema(),
-                );
-                let join_status_ptr: *mut HashJoinStatus = &mut join_status;
+                let (_, _, config) = cache;
+                let left_schema = left_input.output_schema().clone();
+                let right_schema = right_input.output_schema().clone();
+                // the probe tuples without matches are dropped, unless the join keeps them
+                let mut runtime_filter = match (&on, ty) {
+                    (
+                        JoinCondition::On { on, .. },
+                        JoinType::Inner
+                        | JoinType::LeftOuter
+                        | JoinType::LeftSemi
+                        | JoinType::LeftAnti,
+                    ) => {
+                        let right_keys = on.iter().map(|(_, key)| key.clone()).collect_vec();
+
+                        RuntimeFilterRef::attach(&mut right_input, &right_keys)
+                    }
+                    _ => None,
+                };
+
+                let mut left_coroutine = build_read(left_input, cache, transaction);
+                let mut right_coroutine = build_read(right_input, cache, transaction);
+                let mut left_tuples: SpillInput<'a> =
+                    Box::new(iter::from_fn(move || {
+                        match Pin::new(&mut left_coroutine).resume(()) {
+                            CoroutineState::Yielded(tuple) => Some(tuple),
+                            CoroutineState::Complete(()) => None,
+                        }
+                    }));
+                let mut right_tuples: SpillInput<'a> = Box::new(iter::from_fn(move || {
+                    match Pin::new(&mut right_coroutine).resume(()) {
+                        CoroutineState::Yielded(tuple) => Some(tuple),
+                        CoroutineState::Complete(()) => None,
+                    }
+                }));
+                let mut depth = 0;
+                // grace hash join: partitions that were spilled to disk are joined one by one
+                let mut spilled_partitions = VecDeque::new();
+
+                loop {
+                    let mut join_status =
+                        HashJoinStatus::new(on.clone(), ty, &left_schema, &right_schema);
+                    let join_status_ptr: *mut HashJoinStatus = &mut join_status;
+                    // only the scans of the first round read the probe side
+                    if runtime_filter.is_some() {
+                        join_status.runtime_filter = Some(RuntimeFilterBuilder::default());
+                    }
 
-                // build phase:
-                // 1.construct hashtable, one hash key may contains multiple rows indices.
-                // 2.merged all left tuples.
-                let mut coroutine = build_read(left_input, cache, transaction);
+                    // build phase:
+                    // 1.construct hashtable, one hash key may contains multiple rows indices.
+                    // 2.merged all left tuples.
+                    // 3.partition the hashtable and the rest of left tuples to disk if the
+                    //   hashtable exceeds the memory budget.
+                    let mut left_partitions = None;
 
-                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    let tuple: Tuple = throw!(tuple);
+                    for tuple in left_tuples {
+                        let tuple: Tuple = throw!(tuple);
 
-                    throw!(unsafe { (*join_status_ptr).left_build(tuple) });
-                }
+                        if let Some(partitions) = left_partitions.as_mut() {
+                            throw!(join_status.left_spill(partitions, tuple));
+                            continue;
+                        }
+                        throw!(join_status.left_build(tuple));
 
-                // probe phase
-                let mut coroutine = build_read(right_input, cache, transaction);
+                        if depth < MAX_SPILL_DEPTH
+                            && config
+                                .hash_memory_budget
+                                .is_some_and(|budget| join_status.build_size > budget)
+                        {
+                            let mut partitions = throw!(SpillPartitions::new(
+                                &config.spill_path,
+                                &left_schema,
+                                depth
+                            ));
+                            throw!(join_status.build_spill(&mut partitions));
+                            left_partitions = Some(partitions);
+                        }
+                    }
 
-                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    let tuple: Tuple = throw!(tuple);
+                    if let (Some((filter_ref, scan_keys)), Some(builder)) =
+                        (runtime_filter.take(), join_status.runtime_filter.take())
+                    {
+                        filter_ref.set(builder.finish(scan_keys));
+                    }
 
-                    unsafe {
-                        let mut coroutine = (*join_status_ptr).right_probe(tuple);
+                    if let Some(left_partitions) = left_partitions {
+                        let mut right_partitions = throw!(SpillPartitions::new(
+                            &config.spill_path,
+                            &right_schema,
+                            depth
+                        ));
+                        for tuple in right_tuples {
+                            throw!(join_status.right_spill(&mut right_partitions, throw!(tuple)));
+                        }
+                        let left_partitions = throw!(left_partitions.finish(&left_schema));
+                        let right_partitions = throw!(right_partitions.finish(&right_schema));
 
-                        while let CoroutineState::Yielded(tuple) =
-                            Pin::new(&mut coroutine).resume(())
-                        {
-                            yield tuple;
+                        for (left, right) in left_partitions.into_iter().zip_eq(right_partitions) {
+                            spilled_partitions.push_back((left, right, depth + 1));
+                        }
+                    } else {
+                        // probe phase
+                        for tuple in right_tuples {
+                            let tuple: Tuple = throw!(tuple);
+
+                            unsafe {
+                                let mut coroutine = (*join_status_ptr).right_probe(tuple);
+
+                                while let CoroutineState::Yielded(tuple) =
+                                    Pin::new(&mut coroutine).resume(())
+                                {
+                                    yield tuple;
+                                }
+                            }
                         }
-                    }
-                }
 
-                unsafe {
-                    if let Some(mut coroutine) = (*join_status_ptr).build_drop() {
-                        while let CoroutineState::Yielded(tuple) =
-                            Pin::new(&mut coroutine).resume(())
-                        {
-                            yield tuple;
+                        unsafe {
+                            if let Some(mut coroutine) = (*join_status_ptr).build_drop() {
+                                while let CoroutineState::Yielded(tuple) =
+                                    Pin::new(&mut coroutine).resume(())
+                                {
+                                    yield tuple;
+                                }
+                            };
                         }
+                    }
+
+                    let Some((left, right, partition_depth)) = spilled_partitions.pop_front()
+                    else {
+                        break;
                     };
+                    left_tuples = Box::new(left);
+                    right_tuples = Box::new(right);
+                    depth = partition_depth;
                 }
             },
         )
     }
 }
 
+type SpillInput<'a> = Box<dyn Iterator<Item = Result<Tuple, DatabaseError>> + 'a>;
+// the build tuples of each join key, whether any probe tuple has the key and the build tuples
+// matched by the probe tuples of a semi or anti join
+type BuildMap = HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, Option<BitVector>)>;
+
 pub(crate) struct HashJoinStatus {
     ty: JoinType,
     filter: Option<ScalarExpression>,
-    build_map: HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
+    build_map: BuildMap,
+    build_size: usize,
+    runtime_filter: Option<RuntimeFilterBuilder>,
 
     full_schema_ref: SchemaRef,
     left_schema_len: usize,
@@ -162,6 +262,8 @@ impl HashJoinStatus {
             ty,
             filter,
             build_map: Default::default(),
+            build_size: 0,
+            runtime_filter: None,
 
             full_schema_ref: Arc::new(join_schema),
             left_schema_len,
@@ -174,21 +276,74 @@ impl HashJoinStatus {
         let HashJoinStatus {
             on_left_keys,
             build_map,
+            build_size,
             full_schema_ref,
             left_schema_len,
+            runtime_filter,
             ..
         } = self;
         let values = Self::eval_keys(on_left_keys, &tuple, &full_schema_ref[0..*left_schema_len])?;
 
+        if let Some(builder) = runtime_filter {
+            builder.insert(&values);
+        }
+
+        *build_size += estimated_size(&tuple);
         build_map
             .entry(values)
-            .or_insert_with(|| (Vec::new(), false, false))
+            .or_insert_with(|| (Vec::new(), false, None))
             .0
             .push(tuple);
 
         Ok(())
     }
 
+    /// Moves the whole hashtable into `partitions`, keyed the same way as `left_spill`.
+    pub(crate) fn build_spill(
+        &mut self,
+        partitions: &mut SpillPartitions,
+    ) -> Result<(), DatabaseError> {
+        for (values, (tuples, _, _)) in self.build_map.drain() {
+            for tuple in tuples {
+                partitions.write(&values, &tuple)?;
+            }
+        }
+        self.build_size = 0;
+
+        Ok(())
+    }
+
+    pub(crate) fn left_spill(
+        &mut self,
+        partitions: &mut SpillPartitions,
+        tuple: Tuple,
+    ) -> Result<(), DatabaseError> {
+        let values = Self::eval_keys(
+            &self.on_left_keys,
+            &tuple,
+            &self.full_schema_ref[0..self.left_schema_len],
+        )?;
+        if let Some(builder) = self.runtime_filter.as_mut() {
+            builder.insert(&values);
+        }
+
+        partitions.write(&values, &tuple)
+    }
+
+    pub(crate) fn right_spill(
+        &self,
+        partitions: &mut SpillPartitions,
+        tuple: Tuple,
+    ) -> Result<(), DatabaseError> {
+        let values = Self::eval_keys(
+            &self.on_right_keys,
+            &tuple,
+            &self.full_schema_ref[self.left_schema_len..],
+        )?;
+
+        partitions.write(&values, &tuple)
+    }
+
     pub(crate) fn right_probe(&mut self, tuple: Tuple) -> Executor {
         Box::new(
             #[coroutine]
@@ -211,24 +366,47 @@ impl HashJoinStatus {
                 ));
                 let has_null = values.iter().any(|value| value.is_null());
 
-                if let (false, Some((tuples, is_used, is_filtered))) =
+                if let (false, Some((tuples, is_used, matched))) =
                     (has_null, build_map.get_mut(&values))
                 {
-                    let mut bits_option = None;
                     *is_used = true;
 
-                    match ty {
-                        JoinType::LeftSemi => {
-                            if *is_filtered {
-                                return;
-                            } else {
-                                bits_option = Some(BitVector::new(tuples.len()));
+                    // the build tuples matched by any probe tuple are marked, and output or
+                    // dropped after the probe phase
+                    if matches!(ty, JoinType::LeftSemi | JoinType::LeftAnti) {
+                        let bits = matched.get_or_insert_with(|| BitVector::new(tuples.len()));
+
+                        for (i, Tuple { values, .. }) in tuples.iter().enumerate() {
+                            if bits.get_bit(i) {
+                                continue;
+                            }
+                            if filter.is_none() {
+                                bits.set_bit(i, true);
+                                continue;
+                            }
+                            let tuple = Tuple {
+                                id: None,
+                                values: values
+                                    .iter()
+                                    .cloned()
+                                    .chain(tuple.values.clone())
+                                    .collect_vec(),
+                            };
+                            if throw!(Self::filter(
+                                tuple,
+                                full_schema_ref,
+                                filter,
+                                ty,
+                                *left_schema_len
+                            ))
+                            .is_some()
+                            {
+                                bits.set_bit(i, true);
                             }
                         }
-                        JoinType::LeftAnti => return,
-                        _ => (),
+                        return;
                     }
-                    for (i, Tuple { values, .. }) in tuples.iter().enumerate() {
+                    for Tuple { values, .. } in tuples.iter() {
                         let full_values = values
                             .iter()
                             .cloned()
@@ -245,22 +423,9 @@ impl HashJoinStatus {
                             ty,
                             *left_schema_len
                         )) {
-                            if let Some(bits) = bits_option.as_mut() {
-                                bits.set_bit(i, true);
-                            } else {
-                                yield Ok(tuple);
-                            }
+                            yield Ok(tuple);
                         }
                     }
-                    if let Some(bits) = bits_option {
-                        let mut cnt = 0;
-                        tuples.retain(|_| {
-                            let res = bits.get_bit(cnt);
-                            cnt += 1;
-                            res
-                        });
-                        *is_filtered = true
-                    }
                 } else if matches!(ty, JoinType::RightOuter | JoinType::Full) {
                     let empty_len = full_schema_ref.len() - right_cols_len;
                     let values = (0..empty_len)
@@ -321,8 +486,6 @@ impl HashJoinStatus {
             full_schema_ref,
             build_map,
             ty,
-            filter,
-            left_schema_len,
             ..
         } = self;
 
@@ -330,21 +493,12 @@ impl HashJoinStatus {
             JoinType::LeftOuter | JoinType::Full => {
                 Some(Self::right_null_tuple(build_map, full_schema_ref))
             }
-            JoinType::LeftSemi | JoinType::LeftAnti => Some(Self::one_side_tuple(
-                build_map,
-                full_schema_ref,
-                filter,
-                ty,
-                *left_schema_len,
-            )),
+            JoinType::LeftSemi | JoinType::LeftAnti => Some(Self::one_side_tuple(build_map, ty)),
             _ => None,
         }
     }
 
-    fn right_null_tuple<'a>(
-        build_map: &'a mut HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
-        schema: &'a Schema,
-    ) -> Executor<'a> {
+    fn right_null_tuple<'a>(build_map: &'a mut BuildMap, schema: &'a Schema) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
@@ -363,39 +517,17 @@ impl HashJoinStatus {
         )
     }
 
-    fn one_side_tuple<'a>(
-        build_map: &'a mut HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
-        schema: &'a Schema,
-        filter: &'a Option<ScalarExpression>,
-        join_ty: &'a JoinType,
-        left_schema_len: usize,
-    ) -> Executor<'a> {
+    fn one_side_tuple<'a>(build_map: &'a mut BuildMap, join_ty: &'a JoinType) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
                 let is_left_semi = matches!(join_ty, JoinType::LeftSemi);
 
-                for (_, (left_tuples, mut is_used, is_filtered)) in build_map.drain() {
-                    if is_left_semi {
-                        is_used = !is_used;
-                    }
-                    if is_used {
-                        continue;
-                    }
-                    if is_filtered {
-                        for tuple in left_tuples {
-                            yield Ok(tuple);
-                        }
-                        continue;
-                    }
-                    for tuple in left_tuples {
-                        if let Some(tuple) = throw!(Self::filter(
-                            tuple,
-                            schema,
-                            filter,
-                            join_ty,
-                            left_schema_len
-                        )) {
+                for (_, (left_tuples, _, matched)) in build_map.drain() {
+                    for (i, tuple) in left_tuples.into_iter().enumerate() {
+                        let is_matched = matched.as_ref().is_some_and(|bits| bits.get_bit(i));
+
+                        if is_matched == is_left_semi {
                             yield Ok(tuple);
                         }
                     }
@@ -421,10 +553,12 @@ impl HashJoinStatus {
 #[cfg(test)]
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
     use crate::execution::dql::join::hash_join::HashJoin;
-    use crate::execution::dql::test::build_integers;
-    use crate::execution::{try_collect, ReadExecutor};
+    use crate::execution::dql::join::runtime_filter::RuntimeFilterBuilder;
+    use crate::execution::dql::test::{assert_same_results, build_integers};
+    use crate::execution::{try_collect, ExecutionConfig, ReadExecutor};
     use crate::expression::ScalarExpression;
     use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
     use crate::planner::operator::values::ValuesOperator;
@@ -432,9 +566,11 @@ mod test {
     use crate::planner::LogicalPlan;
     use crate::storage::rocksdb::RocksStorage;
     use crate::storage::Storage;
-    use crate::types::value::DataValue;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::{DataValue, NULL_VALUE};
     use crate::types::LogicalType;
     use crate::utils::lru::ShardingLruCache;
+    use itertools::Itertools;
     use std::hash::RandomState;
     use std::sync::Arc;
     use tempfile::TempDir;
@@ -486,6 +622,7 @@ mod test {
             }),
             childrens: vec![],
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         };
 
@@ -517,6 +654,7 @@ mod test {
             }),
             childrens: vec![],
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         };
 
@@ -530,6 +668,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -539,8 +678,8 @@ mod test {
             },
             join_type: JoinType::Inner,
         };
-        let executor =
-            HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
+        let executor = HashJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         debug_assert_eq!(tuples.len(), 3);
@@ -568,6 +707,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -580,7 +720,9 @@ mod test {
         //Outer
         {
             let executor = HashJoin::from((op.clone(), left.clone(), right.clone()));
-            let tuples = try_collect(executor.execute((&table_cache, &meta_cache), &transaction))?;
+            let tuples = try_collect(
+                executor.execute((&table_cache, &meta_cache, &execution_config), &transaction),
+            )?;
 
             debug_assert_eq!(tuples.len(), 4);
 
@@ -605,8 +747,9 @@ mod test {
         {
             let mut executor = HashJoin::from((op.clone(), left.clone(), right.clone()));
             executor.ty = JoinType::LeftSemi;
-            let mut tuples =
-                try_collect(executor.execute((&table_cache, &meta_cache), &transaction))?;
+            let mut tuples = try_collect(
+                executor.execute((&table_cache, &meta_cache, &execution_config), &transaction),
+            )?;
 
             debug_assert_eq!(tuples.len(), 2);
             tuples.sort_by_key(|tuple| {
@@ -628,7 +771,9 @@ mod test {
         {
             let mut executor = HashJoin::from((op, left, right));
             executor.ty = JoinType::LeftAnti;
-            let tuples = try_collect(executor.execute((&table_cache, &meta_cache), &transaction))?;
+            let tuples = try_collect(
+                executor.execute((&table_cache, &meta_cache, &execution_config), &transaction),
+            )?;
 
             debug_assert_eq!(tuples.len(), 1);
             debug_assert_eq!(
@@ -647,6 +792,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -656,8 +802,8 @@ mod test {
             },
             join_type: JoinType::RightOuter,
         };
-        let executor =
-            HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
+        let executor = HashJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         debug_assert_eq!(tuples.len(), 4);
@@ -689,6 +835,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -698,8 +845,8 @@ mod test {
             },
             join_type: JoinType::Full,
         };
-        let executor =
-            HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
+        let executor = HashJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         debug_assert_eq!(tuples.len(), 5);
@@ -727,4 +874,117 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_grace_hash_join() -> Result<(), DatabaseError> {
+        let t1_values = (0..500)
+            .map(|i| format!("({i}, {}, 'k{i}')", i % 50))
+            .join(", ");
+        let t2_values = (0..300)
+            .map(|i| {
+                if i % 11 == 0 {
+                    format!("({i}, null)")
+                } else {
+                    format!("({i}, {})", (i * 7) % 70)
+                }
+            })
+            .join(", ");
+
+        assert_same_results(
+            |builder| Ok(builder.hash_memory_budget(1024)),
+            &[
+                "create table t1 (a int primary key, b int, c varchar)".to_string(),
+                "create table t2 (d int primary key, e int)".to_string(),
+                format!("insert into t1 values {t1_values}"),
+                format!("insert into t2 values {t2_values}"),
+            ],
+            &[
+                "select * from t1 join t2 on b = e order by a, d".to_string(),
+                "select * from t1 left join t2 on b = e and d > 100 order by a, d".to_string(),
+                "select * from t1 right join t2 on b = e order by d, a".to_string(),
+                "select * from t1 full join t2 on b = e order by a, d".to_string(),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_runtime_filter() -> Result<(), DatabaseError> {
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None);
+        let column = Arc::new(ColumnCatalog::new("c1".to_string(), true, desc));
+        let schema = vec![column.clone()];
+        let keys = vec![ScalarExpression::ColumnRef(column)];
+        let tuple = |value: Option<i32>| Tuple {
+            id: None,
+            values: vec![Arc::new(DataValue::Int32(value))],
+        };
+
+        let mut builder = RuntimeFilterBuilder::default();
+        for i in [3, 5, 9] {
+            builder.insert(&[Arc::new(DataValue::Int32(Some(i)))]);
+        }
+        builder.insert(&[NULL_VALUE.clone()]);
+        let filter = builder.finish(keys.clone());
+
+        for i in [3, 5, 9] {
+            debug_assert!(filter.contains(&tuple(Some(i)), &schema)?);
+        }
+        for value in [Some(1), Some(10), None] {
+            debug_assert!(!filter.contains(&tuple(value), &schema)?);
+        }
+        // nothing is joined with an empty build side
+        let filter = RuntimeFilterBuilder::default().finish(keys);
+        debug_assert!(!filter.contains(&tuple(Some(3)), &schema)?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("create table t2 (c int primary key, d int)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            (0..1000).map(|i| format!("({i}, {})", i % 100)).join(", ")
+        ))?;
+        let _ = database.run("insert into t2 values (0, 3), (1, 5), (2, 250), (3, null)")?;
+
+        for (sql, expected) in [
+            ("select count(*) from t2 join t1 on d = b", 20),
+            ("select count(*) from t2 join t1 on d = b and a < 500", 10),
+            ("select count(*) from t2 left join t1 on d = b", 22),
+            ("select count(*) from t2 right join t1 on d = b", 1000),
+            ("select count(*) from t2 full join t1 on d = b", 1002),
+        ] {
+            let (_, tuples) = database.run(sql)?;
+
+            debug_assert_eq!(
+                tuples[0].values,
+                vec![Arc::new(DataValue::Int32(Some(expected)))],
+                "{sql}"
+            );
+        }
+        // the scan of the probe side `t1` drops the rows out of `d` in (3, 5), unless the join
+        // keeps them
+        for (sql, scan_rows) in [
+            ("select * from t2 join t1 on d = b", 20),
+            ("select * from t2 left join t1 on d = b", 20),
+            ("select * from t2 right join t1 on d = b", 1000),
+        ] {
+            let (_, tuples) = database.run(format!("explain analyze {sql}"))?;
+            let DataValue::Utf8 {
+                value: Some(plan), ..
+            } = tuples[0].values[0].as_ref()
+            else {
+                unreachable!()
+            };
+            let scan = plan
+                .lines()
+                .find(|line| line.trim_start().starts_with("TableScan t1"))
+                .unwrap_or_else(|| panic!("{sql}: {plan}"));
+
+            debug_assert!(
+                scan.contains(&format!("actual rows: {scan_rows},")),
+                "{sql}: {plan}"
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,254 @@
+//! Defines the index nested loop join executor, it supports [`JoinType::Inner`], [`JoinType::LeftOuter`].
+//! The inner(right) input must be a table scan, it is probed through an index with every
+//! outer(left) tuple instead of being read as a whole. The index is on a single column, the
+//! composite indexes and the filtered inner inputs are not looked up.
+
+use super::full_schema;
+use crate::errors::DatabaseError;
+use crate::execution::dql::join::hash_join::HashJoinStatus;
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::index::IndexMetaRef;
+use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::{ValueRef, NULL_VALUE};
+use itertools::Itertools;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct IndexNestLoopJoin {
+    on: JoinCondition,
+    ty: JoinType,
+    left_input: LogicalPlan,
+    right_input: LogicalPlan,
+    index_meta: IndexMetaRef,
+}
+
+impl From<(JoinOperator, LogicalPlan, LogicalPlan, IndexMetaRef)> for IndexNestLoopJoin {
+    fn from(
+        (JoinOperator { on, join_type, .. }, left_input, right_input, index_meta): (
+            JoinOperator,
+            LogicalPlan,
+            LogicalPlan,
+            IndexMetaRef,
+        ),
+    ) -> Self {
+        IndexNestLoopJoin {
+            on,
+            ty: join_type,
+            left_input,
+            right_input,
+            index_meta,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexNestLoopJoin {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let IndexNestLoopJoin {
+                    on,
+                    ty,
+                    mut left_input,
+                    mut right_input,
+                    index_meta,
+                } = self;
+                if !matches!(ty, JoinType::Inner | JoinType::LeftOuter) {
+                    unreachable!("{} join should not be in IndexNestLoopJoinExecutor", ty);
+                }
+                let ((on_left_keys, on_right_keys), filter): (
+                    (Vec<ScalarExpression>, Vec<ScalarExpression>),
+                    _,
+                ) = match on {
+                    JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
+                    JoinCondition::None => unreachable!("IndexNestLoopJoin must has on condition"),
+                };
+                // the equivalent condition on the index column is used to look up inner tuples
+                let Some(probe_pos) = on_right_keys
+                    .iter()
+                    .position(|expr| expr.output_column().id() == Some(index_meta.column_ids[0]))
+                else {
+                    unreachable!("IndexNestLoopJoin must has equivalent condition on the index")
+                };
+                let left_schema = left_input.output_schema().clone();
+                let right_schema = right_input.output_schema().clone();
+                let full_schema = full_schema(&left_schema, &right_schema, &ty);
+                let left_schema_len = left_schema.len();
+                let Operator::TableScan(TableScanOperator {
+                    table_name,
+                    columns,
+                    ..
+                }) = right_input.operator
+                else {
+                    unreachable!("the inner input of IndexNestLoopJoin must be a table scan")
+                };
+                let (table_cache, _, _) = cache;
+
+                let mut coroutine = build_read(left_input, cache, transaction);
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let left_tuple: Tuple = throw!(tuple);
+                    let left_values = throw!(eval_keys(&on_left_keys, &left_tuple, &left_schema));
+                    let mut has_matched = false;
+
+                    if !left_values.iter().any(|value| value.is_null()) {
+                        let mut iter = throw!(transaction.read_by_index(
+                            table_cache,
+                            table_name.clone(),
+                            (None, None),
+                            columns.clone(),
+                            index_meta.clone(),
+                            vec![Range::Eq(left_values[probe_pos].clone())],
+                            false,
+                            false,
+                        ));
+
+                        while let Some(right_tuple) = throw!(iter.next_tuple()) {
+                            let right_values =
+                                throw!(eval_keys(&on_right_keys, &right_tuple, &right_schema));
+                            if right_values != left_values {
+                                continue;
+                            }
+                            has_matched = true;
+
+                            let values = left_tuple
+                                .values
+                                .iter()
+                                .chain(right_tuple.values.iter())
+                                .cloned()
+                                .collect_vec();
+                            let tuple = Tuple { id: None, values };
+
+                            if let Some(tuple) = throw!(HashJoinStatus::filter(
+                                tuple,
+                                &full_schema,
+                                &filter,
+                                &ty,
+                                left_schema_len
+                            )) {
+                                yield Ok(tuple);
+                            }
+                        }
+                    }
+                    if !has_matched && ty == JoinType::LeftOuter {
+                        let mut tuple = left_tuple;
+                        tuple.values.resize(full_schema.len(), NULL_VALUE.clone());
+
+                        yield Ok(tuple);
+                    }
+                }
+            },
+        )
+    }
+}
+
+fn eval_keys(
+    on_keys: &[ScalarExpression],
+    tuple: &Tuple,
+    schema: &Schema,
+) -> Result<Vec<ValueRef>, DatabaseError> {
+    on_keys
+        .iter()
+        .map(|expr| expr.eval(tuple, schema))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_nested_loop_join() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = database.run("create table t1 (id int primary key, a int, b int)")?;
+        let _ = database.run("create table t2 (id int primary key, c int, d int)")?;
+        let _ = database.run("create index t2_c_index on t2 (c)")?;
+        // same rows as t2, but without the index on `c`
+        let _ = database.run("create table t3 (id int primary key, c int, d int)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            (0..120)
+                .map(|i| format!("({i}, {}, {})", i * 5, i % 2))
+                .join(", ")
+        ))?;
+        let _ = database.run("insert into t1 values (120, null, 0)")?;
+        let t2_values = (0..1000)
+            .map(|i| format!("({i}, {}, {})", i % 500, i % 3))
+            .join(", ");
+        let _ = database.run(format!("insert into t2 values {t2_values}"))?;
+        let _ = database.run(format!("insert into t3 values {t2_values}"))?;
+        let _ = database.run("analyze table t1")?;
+        let _ = database.run("analyze table t2")?;
+
+        for (join_type, condition) in [
+            ("inner", "a = c"),
+            ("left", "a = c"),
+            ("inner", "a = c and b < d"),
+            ("left", "a = c and b < d"),
+        ] {
+            let (_, plan) = database.run(format!(
+                "explain select * from t1 {join_type} join t2 on {condition}"
+            ))?;
+            debug_assert!(
+                format!("{:?}", plan).contains("IndexNestLoopJoin By t2_c_index"),
+                "{:?}",
+                plan
+            );
+
+            let (_, plan) = database.run(format!(
+                "explain select * from t1 {join_type} join t3 on {condition}"
+            ))?;
+            debug_assert!(format!("{:?}", plan).contains("HashJoin"), "{:?}", plan);
+
+            let (_, looked_up) = database.run(format!(
+                "select a, b, c, d from t1 {join_type} join t2 on {condition} order by a, b, c, d"
+            ))?;
+            let (_, hashed) = database.run(format!(
+                "select a, b, c, d from t1 {join_type} join t3 on {condition} order by a, b, c, d"
+            ))?;
+            debug_assert!(!looked_up.is_empty());
+            debug_assert_eq!(
+                looked_up
+                    .into_iter()
+                    .map(|tuple| tuple.values)
+                    .collect_vec(),
+                hashed.into_iter().map(|tuple| tuple.values).collect_vec()
+            );
+        }
+
+        // neither the prefix of a composite index nor a filtered inner input is probed
+        let _ = database.run("create table t4 (id int primary key, c int, d int)")?;
+        let _ = database.run("create index t4_c_d_index on t4 (c, d)")?;
+        let _ = database.run(format!("insert into t4 values {t2_values}"))?;
+        let _ = database.run("analyze table t4")?;
+        for sql in [
+            "explain select * from t1 join t4 on a = c",
+            "explain select * from t1 join t2 on a = c where d = 1",
+        ] {
+            let (_, plan) = database.run(sql)?;
+            let plan = format!("{:?}", plan);
+            debug_assert!(!plan.contains("IndexNestLoopJoin"), "{}", plan);
+            debug_assert!(plan.contains("HashJoin"), "{}", plan);
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
+use crate::catalog::ColumnCatalog;
 use crate::planner::operator::join::JoinType;
+use crate::types::tuple::{Schema, SchemaRef};
+use std::sync::Arc;
 
 pub(crate) mod hash_join;
+pub(crate) mod index_nested_loop_join;
 pub(crate) mod nested_loop_join;
+pub(crate) mod runtime_filter;
+pub(crate) mod sort_merge_join;
 
 pub fn joins_nullable(join_type: &JoinType) -> (bool, bool) {
     match join_type {
@@ -12,3 +18,21 @@ pub fn joins_nullable(join_type: &JoinType) -> (bool, bool) {
         JoinType::Cross => (true, true),
     }
 }
+
+/// Output schema of the join, columns of the side that may be padded with nulls are nullable.
+pub(crate) fn full_schema(left_schema: &Schema, right_schema: &Schema, ty: &JoinType) -> SchemaRef {
+    let (left_force_nullable, right_force_nullable) = joins_nullable(ty);
+
+    let mut join_schema = Vec::with_capacity(left_schema.len() + right_schema.len());
+    for column in left_schema.iter() {
+        let mut temp = ColumnCatalog::clone(column);
+        temp.nullable = left_force_nullable;
+        join_schema.push(Arc::new(temp));
+    }
+    for column in right_schema.iter() {
+        let mut temp = ColumnCatalog::clone(column);
+        temp.nullable = right_force_nullable;
+        join_schema.push(Arc::new(temp));
+    }
+    Arc::new(join_schema)
+}
@@ -6,7 +6,7 @@ use super::joins_nullable;
 use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::LogicalPlan;
@@ -128,7 +128,7 @@ impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -349,7 +349,7 @@ mod test {
     use super::*;
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::execution::dql::test::build_integers;
-    use crate::execution::{try_collect, ReadExecutor};
+    use crate::execution::{try_collect, ExecutionConfig, ReadExecutor};
     use crate::expression::ScalarExpression;
     use crate::planner::operator::values::ValuesOperator;
     use crate::planner::operator::Operator;
@@ -424,6 +424,7 @@ mod test {
             }),
             childrens: vec![],
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         };
 
@@ -455,6 +456,7 @@ mod test {
             }),
             childrens: vec![],
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         };
 
@@ -505,6 +507,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -514,7 +517,7 @@ mod test {
             join_type: JoinType::Inner,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -533,6 +536,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -542,7 +546,7 @@ mod test {
             join_type: JoinType::LeftOuter,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         debug_assert_eq!(
@@ -573,6 +577,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -582,7 +587,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -602,6 +607,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, _) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -611,7 +617,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(3);
@@ -634,6 +640,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, _) = build_join_values(false);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -643,7 +650,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         debug_assert_eq!(tuples.len(), 16);
@@ -658,6 +665,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -667,7 +675,7 @@ mod test {
             join_type: JoinType::LeftSemi,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -685,6 +693,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -694,7 +703,7 @@ mod test {
             join_type: JoinType::LeftAnti,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(3);
@@ -714,6 +723,7 @@ mod test {
         let transaction = storage.transaction()?;
         let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
         let (keys, left, right, filter) = build_join_values(true);
         let op = JoinOperator {
             on: JoinCondition::On {
@@ -723,7 +733,7 @@ mod test {
             join_type: JoinType::RightOuter,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &meta_cache), &transaction);
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(4);
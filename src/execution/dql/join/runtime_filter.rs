@@ -0,0 +1,196 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::memo::PhysicalProperties;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::types::tuple::Tuple;
+use crate::types::value::ValueRef;
+use crate::utils::bloom_filter::BloomFilter;
+use siphasher::sip::SipHasher13;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+
+const FALSE_POSITIVE_PROBABILITY: f64 = 0.01;
+
+/// Filter built from the join keys of the build side of a `HashJoin`, the scans of the probe
+/// side drop the tuples that can't be joined by it before they reach the join.
+#[derive(Debug)]
+pub struct RuntimeFilter {
+    keys: Vec<ScalarExpression>,
+    // min and max of each key, None if the build side is empty
+    ranges: Option<Vec<(ValueRef, ValueRef)>>,
+    bloom_filter: BloomFilter,
+}
+
+impl RuntimeFilter {
+    /// Whether the keys of `tuple` may equal the keys of a tuple of the build side.
+    pub(crate) fn contains(
+        &self,
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<bool, DatabaseError> {
+        let Some(ranges) = &self.ranges else {
+            return Ok(false);
+        };
+        let mut values = Vec::with_capacity(self.keys.len());
+
+        for (key, (min, max)) in self.keys.iter().zip(ranges) {
+            let value = key.eval(tuple, schema)?;
+
+            if value.is_null()
+                || value.partial_cmp(min) == Some(Ordering::Less)
+                || value.partial_cmp(max) == Some(Ordering::Greater)
+            {
+                return Ok(false);
+            }
+            values.push(value);
+        }
+
+        Ok(self.bloom_filter.contains(hash_keys(&values)))
+    }
+}
+
+/// Collects the join keys of the build side while the hashtable is built.
+#[derive(Default)]
+pub(crate) struct RuntimeFilterBuilder {
+    hashes: Vec<u64>,
+    ranges: Option<Vec<(ValueRef, ValueRef)>>,
+}
+
+impl RuntimeFilterBuilder {
+    pub(crate) fn insert(&mut self, values: &[ValueRef]) {
+        // the keys with nulls are never joined
+        if values.iter().any(|value| value.is_null()) {
+            return;
+        }
+        match self.ranges.as_mut() {
+            Some(ranges) => {
+                for ((min, max), value) in ranges.iter_mut().zip(values) {
+                    if value.partial_cmp(min) == Some(Ordering::Less) {
+                        *min = value.clone();
+                    }
+                    if value.partial_cmp(max) == Some(Ordering::Greater) {
+                        *max = value.clone();
+                    }
+                }
+            }
+            None => {
+                self.ranges = Some(
+                    values
+                        .iter()
+                        .map(|value| (value.clone(), value.clone()))
+                        .collect(),
+                )
+            }
+        }
+        self.hashes.push(hash_keys(values));
+    }
+
+    pub(crate) fn finish(self, keys: Vec<ScalarExpression>) -> RuntimeFilter {
+        let mut bloom_filter = BloomFilter::new(self.hashes.len(), FALSE_POSITIVE_PROBABILITY);
+
+        for hash in self.hashes {
+            bloom_filter.insert(hash);
+        }
+
+        RuntimeFilter {
+            keys,
+            ranges: self.ranges,
+            bloom_filter,
+        }
+    }
+}
+
+/// Shared slot of a [`RuntimeFilter`] between a `HashJoin` and a scan of its probe side, the
+/// scan keeps all tuples until the join fills the slot after building its hashtable.
+#[derive(Clone, Default)]
+pub struct RuntimeFilterRef(Arc<OnceLock<RuntimeFilter>>);
+
+impl RuntimeFilterRef {
+    /// Attaches a slot to the scan below `plan` that outputs the columns of all `keys`, through
+    /// the operators that neither drop nor reorder the tuples by a limit. Returns the slot with
+    /// the keys evaluated on the tuples of the scan.
+    pub(crate) fn attach(
+        plan: &mut LogicalPlan,
+        keys: &[ScalarExpression],
+    ) -> Option<(Self, Vec<ScalarExpression>)> {
+        match &mut plan.operator {
+            Operator::Filter(_) | Operator::Project(_) | Operator::Sort(_) => {
+                Self::attach(plan.childrens.first_mut()?, keys)
+            }
+            Operator::TableScan(scan_op) => {
+                // the keys may refer to the positions of the join input, so they are bound to
+                // the columns of the scan again
+                let scan_keys = keys
+                    .iter()
+                    .map(|key| {
+                        let key_column = PhysicalProperties::column(key)?;
+
+                        scan_op
+                            .columns
+                            .iter()
+                            .any(|(_, column)| column.summary() == key_column.summary())
+                            .then(|| ScalarExpression::ColumnRef(key_column.clone()))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let filter_ref = RuntimeFilterRef::default();
+                scan_op.runtime_filters.push(filter_ref.clone());
+
+                Some((filter_ref, scan_keys))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set(&self, filter: RuntimeFilter) {
+        let _ = self.0.set(filter);
+    }
+
+    pub(crate) fn get(&self) -> Option<&RuntimeFilter> {
+        self.0.get()
+    }
+
+    /// Whether `tuple` passes all filled filters of `filters`.
+    pub(crate) fn contains_all(
+        filters: &[RuntimeFilterRef],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<bool, DatabaseError> {
+        for filter in filters.iter().filter_map(RuntimeFilterRef::get) {
+            if !filter.contains(tuple, schema)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl fmt::Debug for RuntimeFilterRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RuntimeFilterRef({:p})", Arc::as_ptr(&self.0))
+    }
+}
+
+impl PartialEq for RuntimeFilterRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RuntimeFilterRef {}
+
+impl Hash for RuntimeFilterRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state)
+    }
+}
+
+fn hash_keys(values: &[ValueRef]) -> u64 {
+    let mut hasher = SipHasher13::new();
+    values.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,470 @@
+//! Defines the sort-merge join executor, it supports [`JoinType::Inner`], [`JoinType::LeftOuter`],
+//! [`JoinType::RightOuter`], [`JoinType::Full`].
+//! Both inputs must already be sorted in ascending order on their equivalent keys.
+
+use super::full_schema;
+use crate::errors::DatabaseError;
+use crate::execution::dql::join::hash_join::HashJoinStatus;
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::{ValueRef, NULL_VALUE};
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct SortMergeJoin {
+    on: JoinCondition,
+    ty: JoinType,
+    left_input: LogicalPlan,
+    right_input: LogicalPlan,
+}
+
+impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for SortMergeJoin {
+    fn from(
+        (JoinOperator { on, join_type, .. }, left_input, right_input): (
+            JoinOperator,
+            LogicalPlan,
+            LogicalPlan,
+        ),
+    ) -> Self {
+        SortMergeJoin {
+            on,
+            ty: join_type,
+            left_input,
+            right_input,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SortMergeJoin {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let SortMergeJoin {
+                    on,
+                    ty,
+                    mut left_input,
+                    mut right_input,
+                } = self;
+                if !matches!(
+                    ty,
+                    JoinType::Inner | JoinType::LeftOuter | JoinType::RightOuter | JoinType::Full
+                ) {
+                    unreachable!("{} join should not be in SortMergeJoinExecutor", ty);
+                }
+                let ((on_left_keys, on_right_keys), filter): (
+                    (Vec<ScalarExpression>, Vec<ScalarExpression>),
+                    _,
+                ) = match on {
+                    JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
+                    JoinCondition::None => unreachable!("SortMergeJoin must has on condition"),
+                };
+                let left_schema = left_input.output_schema().clone();
+                let right_schema = right_input.output_schema().clone();
+                let full_schema = full_schema(&left_schema, &right_schema, &ty);
+                let left_schema_len = left_schema.len();
+
+                let mut left = MergeInput::new(
+                    build_read(left_input, cache, transaction),
+                    on_left_keys,
+                    left_schema,
+                );
+                let mut right = MergeInput::new(
+                    build_read(right_input, cache, transaction),
+                    on_right_keys,
+                    right_schema,
+                );
+                let mut left_group = throw!(left.next_group());
+                let mut right_group = throw!(right.next_group());
+
+                loop {
+                    let ordering = match (&left_group, &right_group) {
+                        (None, None) => break,
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (Some(left_group), Some(right_group)) => left_group.compare(right_group),
+                    };
+
+                    match ordering {
+                        // the left group has no matching right tuples
+                        Ordering::Less => {
+                            let Some(MergeGroup { tuples, .. }) = left_group.take() else {
+                                unreachable!()
+                            };
+                            if matches!(ty, JoinType::LeftOuter | JoinType::Full) {
+                                for mut tuple in tuples {
+                                    tuple.values.resize(full_schema.len(), NULL_VALUE.clone());
+                                    yield Ok(tuple);
+                                }
+                            }
+                            left_group = throw!(left.next_group());
+                        }
+                        // the right group has no matching left tuples
+                        Ordering::Greater => {
+                            let Some(MergeGroup { tuples, .. }) = right_group.take() else {
+                                unreachable!()
+                            };
+                            if matches!(ty, JoinType::RightOuter | JoinType::Full) {
+                                for tuple in tuples {
+                                    let values = (0..left_schema_len)
+                                        .map(|_| NULL_VALUE.clone())
+                                        .chain(tuple.values)
+                                        .collect_vec();
+                                    let tuple = Tuple { id: None, values };
+
+                                    if let Some(tuple) = throw!(HashJoinStatus::filter(
+                                        tuple,
+                                        &full_schema,
+                                        &filter,
+                                        &ty,
+                                        left_schema_len
+                                    )) {
+                                        yield Ok(tuple);
+                                    }
+                                }
+                            }
+                            right_group = throw!(right.next_group());
+                        }
+                        Ordering::Equal => {
+                            let (Some(left_tuples), Some(right_tuples)) = (
+                                left_group.take().map(|group| group.tuples),
+                                right_group.take().map(|group| group.tuples),
+                            ) else {
+                                unreachable!()
+                            };
+
+                            // same output order as the probe of `HashJoin`
+                            for (i, j) in
+                                (0..right_tuples.len()).cartesian_product(0..left_tuples.len())
+                            {
+                                let values = left_tuples[j]
+                                    .values
+                                    .iter()
+                                    .chain(right_tuples[i].values.iter())
+                                    .cloned()
+                                    .collect_vec();
+                                let tuple = Tuple { id: None, values };
+
+                                if let Some(tuple) = throw!(HashJoinStatus::filter(
+                                    tuple,
+                                    &full_schema,
+                                    &filter,
+                                    &ty,
+                                    left_schema_len
+                                )) {
+                                    yield Ok(tuple);
+                                }
+                            }
+                            left_group = throw!(left.next_group());
+                            right_group = throw!(right.next_group());
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// Consecutive tuples of one input that have the same keys.
+struct MergeGroup {
+    keys: Vec<ValueRef>,
+    tuples: Vec<Tuple>,
+}
+
+impl MergeGroup {
+    fn has_null(&self) -> bool {
+        self.keys.iter().any(|value| value.is_null())
+    }
+
+    /// Keys with null never match, so such groups are ordered before the other side
+    /// to be emitted as unmatched right away.
+    fn compare(&self, right_group: &MergeGroup) -> Ordering {
+        if self.has_null() {
+            return Ordering::Less;
+        }
+        if right_group.has_null() {
+            return Ordering::Greater;
+        }
+        for (left_value, right_value) in self.keys.iter().zip(right_group.keys.iter()) {
+            match left_value.partial_cmp(right_value) {
+                Some(Ordering::Equal) | None => continue,
+                Some(ordering) => return ordering,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+struct MergeInput<'a> {
+    executor: Executor<'a>,
+    on_keys: Vec<ScalarExpression>,
+    schema: SchemaRef,
+    next: Option<(Vec<ValueRef>, Tuple)>,
+    is_finished: bool,
+}
+
+impl<'a> MergeInput<'a> {
+    fn new(executor: Executor<'a>, on_keys: Vec<ScalarExpression>, schema: SchemaRef) -> Self {
+        MergeInput {
+            executor,
+            on_keys,
+            schema,
+            next: None,
+            is_finished: false,
+        }
+    }
+
+    fn next_tuple(&mut self) -> Result<Option<(Vec<ValueRef>, Tuple)>, DatabaseError> {
+        if let Some(next) = self.next.take() {
+            return Ok(Some(next));
+        }
+        if self.is_finished {
+            return Ok(None);
+        }
+        match Pin::new(&mut self.executor).resume(()) {
+            CoroutineState::Yielded(tuple) => {
+                let tuple = tuple?;
+                let keys = self
+                    .on_keys
+                    .iter()
+                    .map(|expr| expr.eval(&tuple, &self.schema))
+                    .try_collect()?;
+
+                Ok(Some((keys, tuple)))
+            }
+            CoroutineState::Complete(()) => {
+                self.is_finished = true;
+                Ok(None)
+            }
+        }
+    }
+
+    fn next_group(&mut self) -> Result<Option<MergeGroup>, DatabaseError> {
+        let Some((keys, tuple)) = self.next_tuple()? else {
+            return Ok(None);
+        };
+        let mut group = MergeGroup {
+            keys,
+            tuples: vec![tuple],
+        };
+
+        if !group.has_null() {
+            while let Some((keys, tuple)) = self.next_tuple()? {
+                if keys != group.keys {
+                    self.next = Some((keys, tuple));
+                    break;
+                }
+                group.tuples.push(tuple);
+            }
+        }
+        Ok(Some(group))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use crate::execution::dql::join::sort_merge_join::SortMergeJoin;
+    use crate::execution::dql::test::build_integers;
+    use crate::execution::{try_collect, ExecutionConfig, ReadExecutor};
+    use crate::expression::ScalarExpression;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+    use crate::planner::operator::values::ValuesOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::Storage;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use crate::utils::lru::ShardingLruCache;
+    use itertools::Itertools;
+    use std::hash::RandomState;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn build_values(name: &str, rows: Vec<Vec<Option<i32>>>) -> LogicalPlan {
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None);
+        let columns = (0..2)
+            .map(|i| {
+                Arc::new(ColumnCatalog::new(
+                    format!("{name}_c{i}"),
+                    true,
+                    desc.clone(),
+                ))
+            })
+            .collect_vec();
+
+        LogicalPlan {
+            operator: Operator::Values(ValuesOperator {
+                rows: rows.into_iter().map(build_integers).collect_vec(),
+                schema_ref: Arc::new(columns),
+            }),
+            childrens: vec![],
+            physical_option: None,
+            estimate: None,
+            _output_schema_ref: None,
+        }
+    }
+
+    fn join(ty: JoinType) -> Result<Vec<Vec<Option<i32>>>, DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let meta_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(128, 16, RandomState::new())?);
+        let execution_config = ExecutionConfig::default();
+
+        // inputs are sorted on the first column, nulls first
+        let mut left = build_values(
+            "t1",
+            vec![
+                vec![None, Some(0)],
+                vec![Some(0), Some(1)],
+                vec![Some(1), Some(2)],
+                vec![Some(1), Some(3)],
+                vec![Some(3), Some(4)],
+            ],
+        );
+        let mut right = build_values(
+            "t2",
+            vec![
+                vec![None, Some(5)],
+                vec![Some(1), Some(6)],
+                vec![Some(1), Some(7)],
+                vec![Some(2), Some(8)],
+                vec![Some(3), Some(9)],
+            ],
+        );
+        let on = vec![(
+            ScalarExpression::ColumnRef(left.output_schema()[0].clone()),
+            ScalarExpression::ColumnRef(right.output_schema()[0].clone()),
+        )];
+        let op = JoinOperator {
+            on: JoinCondition::On { on, filter: None },
+            join_type: ty,
+        };
+        let executor = SortMergeJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache, &execution_config), &transaction);
+
+        Ok(try_collect(executor)?
+            .into_iter()
+            .map(|tuple| {
+                tuple
+                    .values
+                    .into_iter()
+                    .map(|value| match value.as_ref() {
+                        DataValue::Int32(value) => *value,
+                        DataValue::Null => None,
+                        _ => unreachable!(),
+                    })
+                    .collect_vec()
+            })
+            .sorted()
+            .collect_vec())
+    }
+
+    #[test]
+    fn test_sort_merge_join() -> Result<(), DatabaseError> {
+        let matched = vec![
+            vec![Some(1), Some(2), Some(1), Some(6)],
+            vec![Some(1), Some(2), Some(1), Some(7)],
+            vec![Some(1), Some(3), Some(1), Some(6)],
+            vec![Some(1), Some(3), Some(1), Some(7)],
+            vec![Some(3), Some(4), Some(3), Some(9)],
+        ];
+        let left_unmatched = vec![
+            vec![None, Some(0), None, None],
+            vec![Some(0), Some(1), None, None],
+        ];
+        let right_unmatched = vec![
+            vec![None, None, None, Some(5)],
+            vec![None, None, Some(2), Some(8)],
+        ];
+        let expected = |parts: Vec<&Vec<Vec<Option<i32>>>>| {
+            parts.into_iter().flatten().cloned().sorted().collect_vec()
+        };
+
+        debug_assert_eq!(join(JoinType::Inner)?, expected(vec![&matched]));
+        debug_assert_eq!(
+            join(JoinType::LeftOuter)?,
+            expected(vec![&matched, &left_unmatched])
+        );
+        debug_assert_eq!(
+            join(JoinType::RightOuter)?,
+            expected(vec![&matched, &right_unmatched])
+        );
+        debug_assert_eq!(
+            join(JoinType::Full)?,
+            expected(vec![&matched, &left_unmatched, &right_unmatched])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_merge_join_plan() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("create table t2 (c int primary key, d int)")?;
+        // same rows as t2, but not ordered on `c`
+        let _ = database.run("create table t3 (id int primary key, c int, d int)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            (0..100)
+                .map(|i| format!("({}, {})", i * 2, i % 7))
+                .join(", ")
+        ))?;
+        let _ = database.run(format!(
+            "insert into t2 values {}",
+            (0..100)
+                .map(|i| format!("({}, {})", i * 3, i % 5))
+                .join(", ")
+        ))?;
+        let _ = database.run(format!(
+            "insert into t3 values {}",
+            (0..100)
+                .map(|i| format!("({}, {}, {})", (i * 37) % 100, i * 3, i % 5))
+                .join(", ")
+        ))?;
+
+        for join_type in ["inner", "left", "right", "full"] {
+            let (_, plan) = database.run(format!(
+                "explain select * from t1 {join_type} join t2 on a = c"
+            ))?;
+            debug_assert!(format!("{:?}", plan).contains("SortMergeJoin"));
+            let (_, plan) = database.run(format!(
+                "explain select * from t1 {join_type} join t3 on a = c"
+            ))?;
+            debug_assert!(format!("{:?}", plan).contains("HashJoin"));
+
+            let (_, merged) = database.run(format!(
+                "select a, b, c, d from t1 {join_type} join t2 on a = c and b < d order by a, c"
+            ))?;
+            let (_, hashed) = database.run(format!(
+                "select a, b, c, d from t1 {join_type} join t3 on a = c and b < d order by a, c"
+            ))?;
+            debug_assert_eq!(
+                merged.into_iter().map(|tuple| tuple.values).collect_vec(),
+                hashed.into_iter().map(|tuple| tuple.values).collect_vec()
+            );
+        }
+
+        Ok(())
+    }
+}
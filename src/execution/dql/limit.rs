@@ -1,4 +1,4 @@
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::operator::limit::LimitOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
@@ -25,7 +25,7 @@ impl From<(LimitOperator, LogicalPlan)> for Limit {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Limit {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
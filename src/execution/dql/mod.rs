@@ -4,6 +4,7 @@ pub(crate) mod dummy;
 pub(crate) mod explain;
 pub(crate) mod filter;
 pub(crate) mod function_scan;
+pub(crate) mod index_merge_scan;
 pub(crate) mod index_scan;
 pub(crate) mod join;
 pub(crate) mod limit;
@@ -11,18 +12,56 @@ pub(crate) mod projection;
 pub(crate) mod seq_scan;
 pub(crate) mod show_table;
 pub(crate) mod sort;
+pub(crate) mod top_n;
 pub(crate) mod union;
 pub(crate) mod values;
 
 #[cfg(test)]
 pub(crate) mod test {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
     use crate::types::value::{DataValue, ValueRef};
     use itertools::Itertools;
     use std::sync::Arc;
+    use tempfile::TempDir;
 
     pub(crate) fn build_integers(ints: Vec<Option<i32>>) -> Vec<ValueRef> {
         ints.into_iter()
             .map(|i| Arc::new(DataValue::Int32(i)))
             .collect_vec()
     }
+
+    /// Runs `statements` on a default database and on one configured by `configure`, which
+    /// spills into a temporary directory, then checks that every query of `queries` returns the
+    /// same tuples on both.
+    pub(crate) fn assert_same_results(
+        configure: impl FnOnce(DataBaseBuilder) -> Result<DataBaseBuilder, DatabaseError>,
+        statements: &[String],
+        queries: &[String],
+    ) -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let expected_database = DataBaseBuilder::path(temp_dir.path().join("expected")).build()?;
+        let database = configure(
+            DataBaseBuilder::path(temp_dir.path().join("configured")).spill_path(temp_dir.path()),
+        )?
+        .build()?;
+
+        for database in [&expected_database, &database] {
+            for statement in statements {
+                let _ = database.run(statement)?;
+            }
+        }
+        for query in queries {
+            let (_, expected) = expected_database.run(query)?;
+            let (_, tuples) = database.run(query)?;
+
+            debug_assert_eq!(
+                tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+                expected.into_iter().map(|tuple| tuple.values).collect_vec(),
+                "{}",
+                query
+            );
+        }
+        Ok(())
+    }
 }
@@ -1,7 +1,9 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::dql::join::runtime_filter::RuntimeFilterRef;
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
+use itertools::Itertools;
 
 pub(crate) struct SeqScan {
     op: TableScanOperator,
@@ -16,7 +18,7 @@ impl From<TableScanOperator> for SeqScan {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SeqScan {
     fn execute(
         self,
-        (table_cache, _): (&'a TableCache, &'a StatisticsMetaCache),
+        (table_cache, _, _): (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -26,14 +28,27 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SeqScan {
                     table_name,
                     columns,
                     limit,
+                    is_reverse,
+                    runtime_filters,
                     ..
                 } = self.op;
+                let schema = columns
+                    .iter()
+                    .map(|(_, column)| column.clone())
+                    .collect_vec();
 
                 let mut iter = transaction
-                    .read(table_cache, table_name, limit, columns)
+                    .read(table_cache, table_name, limit, columns, is_reverse)
                     .unwrap();
 
                 while let Some(tuple) = throw!(iter.next_tuple()) {
+                    if !throw!(RuntimeFilterRef::contains_all(
+                        &runtime_filters,
+                        &tuple,
+                        &schema
+                    )) {
+                        continue;
+                    }
                     yield Ok(tuple);
                 }
             },
@@ -1,5 +1,5 @@
 use crate::catalog::TableMeta;
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::Tuple;
@@ -12,7 +12,7 @@ pub struct ShowTables;
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowTables {
     fn execute(
         self,
-        _: (&'a TableCache, &'a StatisticsMetaCache),
+        _: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
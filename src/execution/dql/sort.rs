@@ -1,12 +1,15 @@
 use crate::errors::DatabaseError;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::spill::{estimated_size, SpillReader, SpillWriter};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
-use crate::types::tuple::{Schema, Tuple};
+use crate::types::tuple::{Schema, SchemaRef, Tuple};
+use crate::types::value::ValueRef;
 use itertools::Itertools;
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
 use std::mem;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
@@ -88,12 +91,90 @@ pub(crate) fn radix_sort<T>(mut tuples: Vec<(T, Vec<u8>)>) -> Vec<T> {
     Vec::new()
 }
 
+/// Memcomparable encoding of the sort fields of a tuple, comparing these keys as bytes
+/// gives the order required by the sort fields.
+pub(crate) fn sort_key(
+    schema: &Schema,
+    sort_fields: &[SortField],
+    tuple: &Tuple,
+) -> Result<Vec<u8>, DatabaseError> {
+    let mut full_key = Vec::new();
+
+    for SortField {
+        expr,
+        nulls_first,
+        asc,
+    } in sort_fields
+    {
+        let mut key = Vec::new();
+
+        expr.eval(tuple, schema)?.memcomparable_encode(&mut key)?;
+        if !asc {
+            for byte in key.iter_mut() {
+                *byte ^= 0xFF;
+            }
+        }
+        key.push(if *nulls_first { u8::MIN } else { u8::MAX });
+        full_key.extend(key);
+    }
+    Ok(full_key)
+}
+
+/// Compares the evaluated `sort_fields` of two tuples.
+pub(crate) fn compare_sort_values(
+    sort_fields: &[SortField],
+    values_1: &[ValueRef],
+    values_2: &[ValueRef],
+) -> Ordering {
+    let fn_nulls_first = |nulls_first: bool| {
+        if nulls_first {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    };
+    let mut ordering = Ordering::Equal;
+
+    for (
+        SortField {
+            asc, nulls_first, ..
+        },
+        (value_1, value_2),
+    ) in sort_fields.iter().zip(values_1.iter().zip(values_2.iter()))
+    {
+        ordering = match (value_1.is_null(), value_2.is_null()) {
+            (false, true) => fn_nulls_first(*nulls_first),
+            (true, false) => fn_nulls_first(*nulls_first).reverse(),
+            _ => {
+                let mut ordering = value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
+                if !*asc {
+                    ordering = ordering.reverse();
+                }
+                ordering
+            }
+        };
+        if ordering != Ordering::Equal {
+            break;
+        }
+    }
+
+    ordering
+}
+
 pub enum SortBy {
     Radix,
     Fast,
 }
 
 impl SortBy {
+    pub(crate) fn with_len(len: usize) -> SortBy {
+        if len > 256 {
+            SortBy::Radix
+        } else {
+            SortBy::Fast
+        }
+    }
+
     pub(crate) fn sorted_tuples(
         &self,
         schema: &Schema,
@@ -107,27 +188,8 @@ impl SortBy {
                 for (i, tuple) in tuples.0.iter().enumerate() {
                     debug_assert!(tuple.is_some());
 
-                    let mut full_key = Vec::new();
-
-                    for SortField {
-                        expr,
-                        nulls_first,
-                        asc,
-                    } in sort_fields
-                    {
-                        let mut key = Vec::new();
-                        let tuple = tuple.as_ref().map(|(_, tuple)| tuple).unwrap();
-
-                        expr.eval(tuple, schema)?.memcomparable_encode(&mut key)?;
-                        if !asc {
-                            for byte in key.iter_mut() {
-                                *byte ^= 0xFF;
-                            }
-                        }
-                        key.push(if *nulls_first { u8::MIN } else { u8::MAX });
-                        full_key.extend(key);
-                    }
-                    sort_keys.push((i, full_key))
+                    let tuple = tuple.as_ref().map(|(_, tuple)| tuple).unwrap();
+                    sort_keys.push((i, sort_key(schema, sort_fields, tuple)?))
                 }
                 let indices = radix_sort(sort_keys);
 
@@ -138,24 +200,20 @@ impl SortBy {
                 }))
             }
             SortBy::Fast => {
-                let fn_nulls_first = |nulls_first: bool| {
-                    if nulls_first {
-                        Ordering::Greater
-                    } else {
-                        Ordering::Less
-                    }
-                };
                 // Extract the results of calculating SortFields to avoid double calculation
                 // of data during comparison
-                let mut eval_values = vec![Vec::with_capacity(sort_fields.len()); tuples.len()];
+                let mut eval_values = Vec::with_capacity(tuples.len());
 
-                for (x, SortField { expr, .. }) in sort_fields.iter().enumerate() {
-                    for tuple in tuples.0.iter() {
-                        debug_assert!(tuple.is_some());
+                for tuple in tuples.0.iter() {
+                    debug_assert!(tuple.is_some());
 
-                        let (_, tuple) = tuple.as_ref().unwrap();
-                        eval_values[x].push(expr.eval(tuple, schema)?);
-                    }
+                    let (_, tuple) = tuple.as_ref().unwrap();
+                    eval_values.push(
+                        sort_fields
+                            .iter()
+                            .map(|SortField { expr, .. }| expr.eval(tuple, schema))
+                            .try_collect::<_, Vec<_>, _>()?,
+                    );
                 }
 
                 tuples.0.sort_by(|tuple_1, tuple_2| {
@@ -164,36 +222,8 @@ impl SortBy {
 
                     let (i_1, _) = tuple_1.as_ref().unwrap();
                     let (i_2, _) = tuple_2.as_ref().unwrap();
-                    let mut ordering = Ordering::Equal;
-
-                    for (
-                        x,
-                        SortField {
-                            asc, nulls_first, ..
-                        },
-                    ) in sort_fields.iter().enumerate()
-                    {
-                        let value_1 = &eval_values[x][*i_1];
-                        let value_2 = &eval_values[x][*i_2];
-
-                        ordering = match (value_1.is_null(), value_2.is_null()) {
-                            (false, true) => fn_nulls_first(*nulls_first),
-                            (true, false) => fn_nulls_first(*nulls_first).reverse(),
-                            _ => {
-                                let mut ordering =
-                                    value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
-                                if !*asc {
-                                    ordering = ordering.reverse();
-                                }
-                                ordering
-                            }
-                        };
-                        if ordering != Ordering::Equal {
-                            break;
-                        }
-                    }
 
-                    ordering
+                    compare_sort_values(sort_fields, &eval_values[*i_1], &eval_values[*i_2])
                 });
                 drop(eval_values);
 
@@ -208,6 +238,78 @@ impl SortBy {
     }
 }
 
+/// Writes a sorted run of the buffered tuples to a temporary file.
+fn spill_run(
+    config: &ExecutionConfig,
+    schema: &SchemaRef,
+    sort_fields: &[SortField],
+    tuples: NullableVec<(usize, Tuple)>,
+) -> Result<SpillReader, DatabaseError> {
+    let mut writer = SpillWriter::new(&config.spill_path, schema)?;
+
+    for tuple in SortBy::with_len(tuples.len()).sorted_tuples(schema, sort_fields, tuples)? {
+        writer.write(&tuple)?;
+    }
+    writer.finish(schema.clone())
+}
+
+type SortedRun = Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>;
+
+/// K-way merge of sorted runs, comparing the [`sort_key`] of the head tuple of each run.
+struct MergeIterator {
+    schema: SchemaRef,
+    sort_fields: Vec<SortField>,
+    runs: Vec<SortedRun>,
+    heads: Vec<Option<Tuple>>,
+    heap: BinaryHeap<Reverse<(Vec<u8>, usize)>>,
+}
+
+impl MergeIterator {
+    fn new(
+        schema: SchemaRef,
+        sort_fields: Vec<SortField>,
+        runs: Vec<SortedRun>,
+    ) -> Result<Self, DatabaseError> {
+        let mut merge = MergeIterator {
+            schema,
+            sort_fields,
+            heads: vec![None; runs.len()],
+            heap: BinaryHeap::with_capacity(runs.len()),
+            runs,
+        };
+        for i in 0..merge.runs.len() {
+            merge.pull(i)?;
+        }
+
+        Ok(merge)
+    }
+
+    fn pull(&mut self, i: usize) -> Result<(), DatabaseError> {
+        if let Some(tuple) = self.runs[i].next().transpose()? {
+            let key = sort_key(&self.schema, &self.sort_fields, &tuple)?;
+
+            self.heads[i] = Some(tuple);
+            // the run index keeps equal keys in the order of the runs
+            self.heap.push(Reverse((key, i)));
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for MergeIterator {
+    type Item = Result<Tuple, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((_, i)) = self.heap.pop()?;
+        let tuple = self.heads[i].take().unwrap();
+
+        if let Err(err) = self.pull(i) {
+            return Some(Err(err));
+        }
+        Some(Ok(tuple))
+    }
+}
+
 pub struct Sort {
     sort_fields: Vec<SortField>,
     limit: Option<usize>,
@@ -227,7 +329,7 @@ impl From<(SortOperator, LogicalPlan)> for Sort {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Sort {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -238,30 +340,69 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Sort {
                     limit,
                     mut input,
                 } = self;
+                let (_, _, config) = cache;
 
                 let schema = input.output_schema().clone();
                 let mut tuples = NullableVec::default();
                 let mut offset = 0;
+                let mut buffered_size = 0;
+                let mut runs: Vec<SortedRun> = Vec::new();
 
                 let mut coroutine = build_read(input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    tuples.put((offset, throw!(tuple)));
+                    let tuple = throw!(tuple);
+
+                    buffered_size += estimated_size(&tuple);
+                    tuples.put((offset, tuple));
                     offset += 1;
+
+                    // spill a sorted run once the buffered tuples exceed the memory budget
+                    if config
+                        .sort_memory_budget
+                        .is_some_and(|budget| buffered_size > budget)
+                    {
+                        let tuples = mem::take(&mut tuples);
+
+                        runs.push(Box::new(throw!(spill_run(
+                            config,
+                            &schema,
+                            &sort_fields,
+                            tuples
+                        ))));
+                        offset = 0;
+                        buffered_size = 0;
+                    }
                 }
+                let mut limit = limit.unwrap_or(usize::MAX);
 
-                let sort_by = if tuples.len() > 256 {
-                    SortBy::Radix
-                } else {
-                    SortBy::Fast
-                };
-                let mut limit = limit.unwrap_or(tuples.len());
+                if runs.is_empty() {
+                    let sort_by = SortBy::with_len(tuples.len());
 
-                for tuple in throw!(sort_by.sorted_tuples(&schema, &sort_fields, tuples)) {
-                    if limit != 0 {
+                    for tuple in throw!(sort_by.sorted_tuples(&schema, &sort_fields, tuples)) {
+                        if limit == 0 {
+                            break;
+                        }
                         yield Ok(tuple);
                         limit -= 1;
                     }
+                    return;
+                }
+                if tuples.len() > 0 {
+                    let sort_by = SortBy::with_len(tuples.len());
+
+                    runs.push(Box::new(
+                        throw!(sort_by.sorted_tuples(&schema, &sort_fields, tuples)).map(Ok),
+                    ));
+                }
+                let merge = throw!(MergeIterator::new(schema, sort_fields, runs));
+
+                for tuple in merge {
+                    if limit == 0 {
+                        break;
+                    }
+                    yield tuple;
+                    limit -= 1;
                 }
             },
         )
@@ -273,11 +414,13 @@ mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::errors::DatabaseError;
     use crate::execution::dql::sort::{radix_sort, NullableVec, SortBy};
+    use crate::execution::dql::test::assert_same_results;
     use crate::expression::ScalarExpression;
     use crate::planner::operator::sort::SortField;
     use crate::types::tuple::Tuple;
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
+    use itertools::Itertools;
     use std::sync::Arc;
 
     #[test]
@@ -869,4 +1012,30 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_external_sort() -> Result<(), DatabaseError> {
+        let values = (0..1000)
+            .map(|i| {
+                if i % 7 == 0 {
+                    format!("({i}, null, 'k{i}')")
+                } else {
+                    format!("({i}, {}, 'k{i}')", (i * 7919) % 100)
+                }
+            })
+            .join(", ");
+
+        assert_same_results(
+            |builder| Ok(builder.sort_memory_budget(1024)),
+            &[
+                "create table t1 (a int primary key, b int, c varchar)".to_string(),
+                format!("insert into t1 values {values}"),
+            ],
+            &[
+                "select * from t1 order by b, a desc".to_string(),
+                "select * from t1 order by b desc nulls first, c".to_string(),
+                "select a, b from t1 order by b, a limit 10 offset 100".to_string(),
+            ],
+        )
+    }
 }
@@ -0,0 +1,173 @@
+use crate::execution::dql::sort::compare_sort_values;
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::top_n::TopNOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::ValueRef;
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Heap entry ordered by the evaluated sort fields, ties are broken by the input
+/// order so the output is the same as `Sort` + `Limit`.
+struct TopNItem {
+    sort_fields: Arc<Vec<SortField>>,
+    values: Vec<ValueRef>,
+    seq: usize,
+    tuple: Tuple,
+}
+
+impl PartialEq for TopNItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TopNItem {}
+
+impl PartialOrd for TopNItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopNItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_sort_values(&self.sort_fields, &self.values, &other.values)
+            .then_with(|| self.seq.cmp(&other.seq))
+    }
+}
+
+pub struct TopN {
+    sort_fields: Vec<SortField>,
+    limit: usize,
+    offset: Option<usize>,
+    input: LogicalPlan,
+}
+
+impl From<(TopNOperator, LogicalPlan)> for TopN {
+    fn from(
+        (
+            TopNOperator {
+                sort_fields,
+                limit,
+                offset,
+            },
+            input,
+        ): (TopNOperator, LogicalPlan),
+    ) -> Self {
+        TopN {
+            sort_fields,
+            limit,
+            offset,
+            input,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for TopN {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let TopN {
+                    sort_fields,
+                    limit,
+                    offset,
+                    mut input,
+                } = self;
+
+                if limit == 0 {
+                    return;
+                }
+                let offset = offset.unwrap_or(0);
+                let capacity = offset.saturating_add(limit);
+
+                let schema = input.output_schema().clone();
+                let sort_fields = Arc::new(sort_fields);
+                // max-heap: the top is the greatest of the tuples kept so far
+                let mut heap = BinaryHeap::new();
+                let mut seq = 0;
+
+                let mut coroutine = build_read(input, cache, transaction);
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let tuple = throw!(tuple);
+                    let values = throw!(sort_fields
+                        .iter()
+                        .map(|field| field.expr.eval(&tuple, &schema))
+                        .try_collect());
+                    let item = TopNItem {
+                        sort_fields: sort_fields.clone(),
+                        values,
+                        seq,
+                        tuple,
+                    };
+                    seq += 1;
+
+                    if heap.len() < capacity {
+                        heap.push(item);
+                    } else if heap.peek().is_some_and(|top| &item < top) {
+                        heap.pop();
+                        heap.push(item);
+                    }
+                }
+
+                for TopNItem { tuple, .. } in heap.into_sorted_vec().into_iter().skip(offset) {
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::execution::dql::test::assert_same_results;
+    use crate::optimizer::OptimizerPipeline;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_top_n() -> Result<(), DatabaseError> {
+        let values = (0..1000)
+            .map(|i| {
+                if i % 7 == 0 {
+                    format!("({i}, null, 'k{i}')")
+                } else {
+                    format!("({i}, {}, 'k{i}')", (i * 7919) % 100)
+                }
+            })
+            .join(", ");
+
+        // compared with the sort and the limit it fuses
+        assert_same_results(
+            |builder| {
+                Ok(builder.optimizer_pipeline(
+                    OptimizerPipeline::default().disable_rule("CombineSortLimit")?,
+                ))
+            },
+            &[
+                "create table t1 (a int primary key, b int, c varchar)".to_string(),
+                format!("insert into t1 values {values}"),
+            ],
+            &[
+                "select * from t1 order by b, a desc limit 10".to_string(),
+                "select * from t1 order by b desc nulls first, c limit 25 offset 100".to_string(),
+                "select * from t1 order by c limit 20 offset 990".to_string(),
+                "select * from t1 order by b nulls last, a limit 0".to_string(),
+            ],
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use std::ops::Coroutine;
@@ -22,7 +22,7 @@ impl From<(LogicalPlan, LogicalPlan)> for Union {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Union {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
@@ -1,4 +1,4 @@
-use crate::execution::{Executor, ReadExecutor};
+use crate::execution::{ExecutionConfig, Executor, ReadExecutor};
 use crate::planner::operator::values::ValuesOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::types::tuple::Tuple;
@@ -14,7 +14,11 @@ impl From<ValuesOperator> for Values {
 }
 
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Values {
-    fn execute(self, _: (&'a TableCache, &'a StatisticsMetaCache), _: &T) -> Executor<'a> {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+        _: &T,
+    ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
@@ -2,6 +2,8 @@ pub(crate) mod ddl;
 pub(crate) mod dml;
 pub(crate) mod dql;
 pub(crate) mod marco;
+pub(crate) mod profiler;
+pub(crate) mod spill;
 
 use self::ddl::add_column::AddColumn;
 use self::dql::join::nested_loop_join::NestedLoopJoin;
@@ -17,21 +19,28 @@ use crate::execution::dml::delete::Delete;
 use crate::execution::dml::insert::Insert;
 use crate::execution::dml::update::Update;
 use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
+use crate::execution::dql::aggregate::row_count::RowCountExecutor;
 use crate::execution::dql::aggregate::simple_agg::SimpleAggExecutor;
 use crate::execution::dql::describe::Describe;
 use crate::execution::dql::dummy::Dummy;
-use crate::execution::dql::explain::Explain;
+use crate::execution::dql::explain::{Explain, ExplainAnalyze};
 use crate::execution::dql::filter::Filter;
 use crate::execution::dql::function_scan::FunctionScan;
+use crate::execution::dql::index_merge_scan::IndexMergeScan;
 use crate::execution::dql::index_scan::IndexScan;
 use crate::execution::dql::join::hash_join::HashJoin;
+use crate::execution::dql::join::index_nested_loop_join::IndexNestLoopJoin;
+use crate::execution::dql::join::sort_merge_join::SortMergeJoin;
 use crate::execution::dql::limit::Limit;
 use crate::execution::dql::projection::Projection;
 use crate::execution::dql::seq_scan::SeqScan;
 use crate::execution::dql::show_table::ShowTables;
 use crate::execution::dql::sort::Sort;
+use crate::execution::dql::top_n::TopN;
 use crate::execution::dql::union::Union;
 use crate::execution::dql::values::Values;
+use crate::execution::profiler::Profiler;
+use crate::planner::operator::explain::ExplainOperator;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::planner::LogicalPlan;
@@ -39,15 +48,47 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::types::index::IndexInfo;
 use crate::types::tuple::Tuple;
 use std::ops::{Coroutine, CoroutineState};
+use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 
 pub type Executor<'a> =
     Box<dyn Coroutine<Yield = Result<Tuple, DatabaseError>, Return = ()> + 'a + Unpin>;
 
+/// Resource limits shared by all executors of a [`Database`](crate::db::Database),
+/// configured through [`DataBaseBuilder`](crate::db::DataBaseBuilder).
+#[derive(Debug, Clone)]
+pub struct ExecutionConfig {
+    /// Bytes of tuples that `Sort` may buffer before spilling sorted runs to disk
+    pub(crate) sort_memory_budget: Option<usize>,
+    /// Bytes that `HashJoin` and `HashAggregate` may keep in their hashtables before
+    /// partitioning their inputs to disk
+    pub(crate) hash_memory_budget: Option<usize>,
+    /// Directory where temporary spill files are created
+    pub(crate) spill_path: PathBuf,
+    /// Measures every executor, only set by `EXPLAIN ANALYZE` for the plan it executes
+    pub(crate) profiler: Option<Arc<Profiler>>,
+    /// Records the changes of the statistics in the transaction, set when the tables are
+    /// analyzed automatically
+    pub(crate) track_statistics: bool,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        ExecutionConfig {
+            sort_memory_budget: None,
+            hash_memory_budget: None,
+            spill_path: std::env::temp_dir(),
+            profiler: None,
+            track_statistics: false,
+        }
+    }
+}
+
 pub trait ReadExecutor<'a, T: Transaction + 'a> {
     fn execute(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a T,
     ) -> Executor<'a>;
 }
@@ -55,14 +96,27 @@ pub trait ReadExecutor<'a, T: Transaction + 'a> {
 pub trait WriteExecutor<'a, T: Transaction + 'a> {
     fn execute_mut(
         self,
-        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
         transaction: &'a mut T,
     ) -> Executor<'a>;
 }
 
 pub fn build_read<'a, T: Transaction + 'a>(
     plan: LogicalPlan,
-    cache: (&'a TableCache, &'a StatisticsMetaCache),
+    cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+    transaction: &'a T,
+) -> Executor<'a> {
+    if let Some(profiler) = &cache.2.profiler {
+        return profiler.instrument(plan, transaction.keys_read(), move |plan| {
+            build_read_operator(plan, cache, transaction)
+        });
+    }
+    build_read_operator(plan, cache, transaction)
+}
+
+fn build_read_operator<'a, T: Transaction + 'a>(
+    plan: LogicalPlan,
+    cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
     transaction: &'a T,
 ) -> Executor<'a> {
     let LogicalPlan {
@@ -76,7 +130,9 @@ pub fn build_read<'a, T: Transaction + 'a>(
         Operator::Aggregate(op) => {
             let input = childrens.pop().unwrap();
 
-            if op.groupby_exprs.is_empty() {
+            if plan.physical_option == Some(PhysicalOption::RowCount) {
+                RowCountExecutor::from((op, input)).execute(cache, transaction)
+            } else if op.groupby_exprs.is_empty() {
                 SimpleAggExecutor::from((op, input)).execute(cache, transaction)
             } else {
                 HashAggExecutor::from((op, input)).execute(cache, transaction)
@@ -97,6 +153,26 @@ pub fn build_read<'a, T: Transaction + 'a>(
                 {
                     HashJoin::from((op, left_input, right_input)).execute(cache, transaction)
                 }
+                JoinCondition::On { on, .. }
+                    if !on.is_empty()
+                        && matches!(
+                            plan.physical_option,
+                            Some(PhysicalOption::IndexNestLoopJoin(_))
+                        ) =>
+                {
+                    let Some(PhysicalOption::IndexNestLoopJoin(index_meta)) = plan.physical_option
+                    else {
+                        unreachable!()
+                    };
+                    IndexNestLoopJoin::from((op, left_input, right_input, index_meta))
+                        .execute(cache, transaction)
+                }
+                JoinCondition::On { on, .. }
+                    if !on.is_empty()
+                        && plan.physical_option == Some(PhysicalOption::SortMergeJoin) =>
+                {
+                    SortMergeJoin::from((op, left_input, right_input)).execute(cache, transaction)
+                }
                 _ => {
                     NestedLoopJoin::from((op, left_input, right_input)).execute(cache, transaction)
                 }
@@ -107,23 +183,28 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
             Projection::from((op, input)).execute(cache, transaction)
         }
-        Operator::TableScan(op) => {
-            if let Some(PhysicalOption::IndexScan(IndexInfo {
+        Operator::TableScan(op) => match plan.physical_option {
+            Some(PhysicalOption::IndexScan(IndexInfo {
                 meta,
                 range: Some(range),
-            })) = plan.physical_option
-            {
-                IndexScan::from((op, meta, range)).execute(cache, transaction)
-            } else {
-                SeqScan::from(op).execute(cache, transaction)
+                covered,
+            })) => IndexScan::from((op, meta, range, covered)).execute(cache, transaction),
+            Some(PhysicalOption::IndexMerge(index_merge)) => {
+                IndexMergeScan::from((op, index_merge)).execute(cache, transaction)
             }
-        }
+            _ => SeqScan::from(op).execute(cache, transaction),
+        },
         Operator::FunctionScan(op) => FunctionScan::from(op).execute(cache, transaction),
         Operator::Sort(op) => {
             let input = childrens.pop().unwrap();
 
             Sort::from((op, input)).execute(cache, transaction)
         }
+        Operator::TopN(op) => {
+            let input = childrens.pop().unwrap();
+
+            TopN::from((op, input)).execute(cache, transaction)
+        }
         Operator::Limit(op) => {
             let input = childrens.pop().unwrap();
 
@@ -131,10 +212,13 @@ pub fn build_read<'a, T: Transaction + 'a>(
         }
         Operator::Values(op) => Values::from(op).execute(cache, transaction),
         Operator::Show => ShowTables.execute(cache, transaction),
-        Operator::Explain => {
+        Operator::Explain(ExplainOperator {
+            analyze: false,
+            format,
+        }) => {
             let input = childrens.pop().unwrap();
 
-            Explain::from(input).execute(cache, transaction)
+            Explain::from((input, format)).execute(cache, transaction)
         }
         Operator::Describe(op) => Describe::from(op).execute(cache, transaction),
         Operator::Union(_) => {
@@ -149,13 +233,27 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
 pub fn build_write<'a, T: Transaction + 'a>(
     plan: LogicalPlan,
-    cache: (&'a TableCache, &'a StatisticsMetaCache),
+    cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
+    transaction: &'a mut T,
+) -> Executor<'a> {
+    if let Some(profiler) = &cache.2.profiler {
+        return profiler.instrument(plan, transaction.keys_read(), move |plan| {
+            build_write_operator(plan, cache, transaction)
+        });
+    }
+    build_write_operator(plan, cache, transaction)
+}
+
+fn build_write_operator<'a, T: Transaction + 'a>(
+    plan: LogicalPlan,
+    cache: (&'a TableCache, &'a StatisticsMetaCache, &'a ExecutionConfig),
     transaction: &'a mut T,
 ) -> Executor<'a> {
     let LogicalPlan {
         operator,
         mut childrens,
         physical_option,
+        estimate,
         _output_schema_ref,
     } = plan;
 
@@ -202,11 +300,20 @@ pub fn build_write<'a, T: Transaction + 'a>(
 
             Analyze::from((op, input)).execute_mut(cache, transaction)
         }
-        operator => build_read(
+        Operator::Explain(ExplainOperator {
+            analyze: true,
+            format,
+        }) => {
+            let input = childrens.pop().unwrap();
+
+            ExplainAnalyze::from((input, format)).execute_mut(cache, transaction)
+        }
+        operator => build_read_operator(
             LogicalPlan {
                 operator,
                 childrens,
                 physical_option,
+                estimate,
                 _output_schema_ref,
             },
             cache,
@@ -0,0 +1,186 @@
+use crate::execution::dql::explain::ExplainNode;
+use crate::execution::Executor;
+use crate::planner::LogicalPlan;
+use parking_lot::Mutex;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{Coroutine, CoroutineState};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// What an operator actually did under `EXPLAIN ANALYZE`. The children are built and resumed
+/// while their parent is, so the time and the keys read include the ones of the children.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct OperatorProfile {
+    /// Times the operator was built, e.g. the inner side of a nested loop join is rebuilt
+    /// for every outer tuple, `0` when it was never executed
+    pub(crate) loops: usize,
+    pub(crate) rows: usize,
+    pub(crate) elapsed: Duration,
+    pub(crate) keys_read: usize,
+}
+
+impl fmt::Display for OperatorProfile {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.loops == 0 {
+            return write!(f, "(never executed)");
+        }
+        write!(
+            f,
+            "(actual rows: {}, loops: {}, time: {:.3}ms, keys read: {})",
+            self.rows,
+            self.loops,
+            self.elapsed.as_secs_f64() * 1000.0,
+            self.keys_read
+        )
+    }
+}
+
+#[derive(Debug)]
+struct ProfileNode {
+    /// The node without its children
+    explain: ExplainNode,
+    children: Vec<usize>,
+    profile: Option<OperatorProfile>,
+}
+
+#[derive(Debug, Default)]
+struct ProfilerInner {
+    /// The nodes of the plan in pre-order, the root is the first one
+    nodes: Vec<ProfileNode>,
+    /// The operators being built or resumed, the last one is the parent of an operator built
+    stack: Vec<usize>,
+}
+
+/// Measures the executors of a plan for `EXPLAIN ANALYZE`, it is passed down through
+/// [`ExecutionConfig`](crate::execution::ExecutionConfig) and wraps every executor built by
+/// `build_read` and `build_write`.
+///
+/// The executors don't know the nodes of the plan they execute, and may attach filters to the
+/// plans of their children before building them, so a built plan is matched by its line of
+/// `EXPLAIN` among the children of the operator that builds it. The nodes never built, like the
+/// inner side of an index nested loop join, are reported as never executed.
+#[derive(Debug)]
+pub(crate) struct Profiler {
+    inner: Mutex<ProfilerInner>,
+}
+
+impl Profiler {
+    pub(crate) fn new(plan: &LogicalPlan) -> Self {
+        fn push_node(nodes: &mut Vec<ProfileNode>, plan: &LogicalPlan) -> usize {
+            let id = nodes.len();
+
+            nodes.push(ProfileNode {
+                explain: ExplainNode::new(plan),
+                children: vec![],
+                profile: None,
+            });
+            for child in &plan.childrens {
+                let child_id = push_node(nodes, child);
+                nodes[id].children.push(child_id);
+            }
+            id
+        }
+        let mut inner = ProfilerInner::default();
+        push_node(&mut inner.nodes, plan);
+
+        Profiler {
+            inner: Mutex::new(inner),
+        }
+    }
+
+    /// Wraps the executor built by `build` to count its rows and to measure the time and the
+    /// keys read while it is built and resumed.
+    pub(crate) fn instrument<'a>(
+        self: &Arc<Self>,
+        plan: LogicalPlan,
+        keys_read: Arc<AtomicUsize>,
+        build: impl FnOnce(LogicalPlan) -> Executor<'a>,
+    ) -> Executor<'a> {
+        let Some(id) = self.register(&plan) else {
+            return build(plan);
+        };
+        let profiler = self.clone();
+        let mut executor = profiler.measure(id, &keys_read, || build(plan));
+
+        Box::new(
+            #[coroutine]
+            move || loop {
+                let state = profiler.measure(id, &keys_read, || Pin::new(&mut executor).resume(()));
+
+                match state {
+                    CoroutineState::Yielded(tuple) => {
+                        if tuple.is_ok() {
+                            profiler.add_row(id);
+                        }
+                        yield tuple;
+                    }
+                    CoroutineState::Complete(()) => return,
+                }
+            },
+        )
+    }
+
+    /// Matches the plan with a child of the operator building it, the one built the fewest
+    /// times when several children are alike.
+    fn register(&self, plan: &LogicalPlan) -> Option<usize> {
+        let line = plan.explain_node();
+        let mut inner = self.inner.lock();
+        let candidates = match inner.stack.last() {
+            Some(parent) => inner.nodes[*parent].children.clone(),
+            None => vec![0],
+        };
+        let id = candidates
+            .into_iter()
+            .filter(|id| inner.nodes[*id].explain.line == line)
+            .min_by_key(|id| inner.nodes[*id].profile.map_or(0, |profile| profile.loops))?;
+
+        inner.nodes[id]
+            .profile
+            .get_or_insert_with(Default::default)
+            .loops += 1;
+        Some(id)
+    }
+
+    fn measure<R>(&self, id: usize, keys_read: &AtomicUsize, f: impl FnOnce() -> R) -> R {
+        self.inner.lock().stack.push(id);
+        let start = Instant::now();
+        let start_keys = keys_read.load(Ordering::Relaxed);
+
+        let result = f();
+
+        let mut inner = self.inner.lock();
+        inner.stack.pop();
+        if let Some(profile) = inner.nodes[id].profile.as_mut() {
+            profile.elapsed += start.elapsed();
+            profile.keys_read += keys_read.load(Ordering::Relaxed) - start_keys;
+        }
+        result
+    }
+
+    fn add_row(&self, id: usize) {
+        if let Some(profile) = self.inner.lock().nodes[id].profile.as_mut() {
+            profile.rows += 1;
+        }
+    }
+
+    /// The plan with the actual execution of each operator.
+    pub(crate) fn explain(&self) -> ExplainNode {
+        fn build(nodes: &[ProfileNode], id: usize) -> ExplainNode {
+            let node = &nodes[id];
+
+            ExplainNode {
+                actual: Some(node.profile.unwrap_or_default()),
+                children: node
+                    .children
+                    .iter()
+                    .map(|child| build(nodes, *child))
+                    .collect(),
+                ..node.explain.clone()
+            }
+        }
+        build(&self.inner.lock().nodes, 0)
+    }
+}
@@ -0,0 +1,220 @@
+use crate::errors::DatabaseError;
+use crate::types::tuple::{types, SchemaRef, Tuple};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use ahash::RandomState;
+use integer_encoding::FixedInt;
+use itertools::Itertools;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::path::Path;
+
+/// Rough number of bytes a tuple occupies in memory, used by executors to
+/// decide when to spill.
+pub(crate) fn estimated_size(tuple: &Tuple) -> usize {
+    values_size(&tuple.values) + mem::size_of::<Tuple>()
+}
+
+pub(crate) fn values_size(values: &[ValueRef]) -> usize {
+    values
+        .iter()
+        .map(|value| {
+            let heap_len = match value.as_ref() {
+                DataValue::Utf8 {
+                    value: Some(string),
+                    ..
+                } => string.len(),
+                _ => 0,
+            };
+            mem::size_of::<DataValue>() + heap_len
+        })
+        .sum()
+}
+
+/// Anonymous temporary file holding tuples encoded by [`Tuple::serialize_to`].
+///
+/// e.g.: |len(u32)|tuple bytes|len(u32)|tuple bytes|...
+///
+/// The file is removed by the OS once it is dropped.
+pub(crate) struct SpillWriter {
+    writer: BufWriter<File>,
+    types: Vec<LogicalType>,
+}
+
+impl SpillWriter {
+    pub(crate) fn new(path: &Path, schema: &SchemaRef) -> Result<Self, DatabaseError> {
+        Ok(SpillWriter {
+            writer: BufWriter::new(tempfile::tempfile_in(path)?),
+            types: types(schema),
+        })
+    }
+
+    pub(crate) fn write(&mut self, tuple: &Tuple) -> Result<(), DatabaseError> {
+        let bytes = tuple.serialize_to(&self.types)?;
+
+        self.writer
+            .write_all(&(bytes.len() as u32).encode_fixed_vec())?;
+        self.writer.write_all(&bytes)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn finish(self, schema: SchemaRef) -> Result<SpillReader, DatabaseError> {
+        let mut file = self.writer.into_inner().map_err(|err| err.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+
+        Ok(SpillReader {
+            reader: BufReader::new(file),
+            projections: (0..self.types.len()).collect_vec(),
+            types: self.types,
+            schema,
+            buf: Vec::new(),
+        })
+    }
+}
+
+pub(crate) struct SpillReader {
+    reader: BufReader<File>,
+    types: Vec<LogicalType>,
+    projections: Vec<usize>,
+    schema: SchemaRef,
+    buf: Vec<u8>,
+}
+
+impl SpillReader {
+    pub(crate) fn next_tuple(&mut self) -> Result<Option<Tuple>, DatabaseError> {
+        let mut len_bytes = [0u8; 4];
+
+        if let Err(err) = self.reader.read_exact(&mut len_bytes) {
+            return if err.kind() == ErrorKind::UnexpectedEof {
+                Ok(None)
+            } else {
+                Err(err.into())
+            };
+        }
+        self.buf.resize(u32::decode_fixed(&len_bytes) as usize, 0);
+        self.reader.read_exact(&mut self.buf)?;
+
+        Ok(Some(Tuple::deserialize_from(
+            &self.types,
+            &self.projections,
+            &self.schema,
+            &self.buf,
+        )))
+    }
+}
+
+impl Iterator for SpillReader {
+    type Item = Result<Tuple, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_tuple().transpose()
+    }
+}
+
+/// Number of files a spilled input is hash partitioned into.
+pub(crate) const SPILL_PARTITIONS: usize = 16;
+/// Partitions are re-partitioned at most this many times; a partition that still
+/// does not fit (e.g. a single huge key) is then processed in memory.
+pub(crate) const MAX_SPILL_DEPTH: usize = 4;
+
+/// Hash partitions tuples by their keys into [`SPILL_PARTITIONS`] spill files.
+///
+/// Tuples with equal keys always land in the same partition, so hash joins and
+/// hash aggregations can process every partition independently. `depth` seeds the
+/// hash so that re-partitioning a partition spreads its keys again.
+pub(crate) struct SpillPartitions {
+    writers: Vec<SpillWriter>,
+    random_state: RandomState,
+}
+
+impl SpillPartitions {
+    pub(crate) fn new(
+        path: &Path,
+        schema: &SchemaRef,
+        depth: usize,
+    ) -> Result<Self, DatabaseError> {
+        let writers = (0..SPILL_PARTITIONS)
+            .map(|_| SpillWriter::new(path, schema))
+            .try_collect()?;
+        let seed = depth as u64;
+
+        Ok(SpillPartitions {
+            writers,
+            random_state: RandomState::with_seeds(seed, seed + 1, seed + 2, seed + 3),
+        })
+    }
+
+    pub(crate) fn write(&mut self, keys: &[ValueRef], tuple: &Tuple) -> Result<(), DatabaseError> {
+        let i = self.random_state.hash_one(keys) as usize % self.writers.len();
+
+        self.writers[i].write(tuple)
+    }
+
+    pub(crate) fn finish(self, schema: &SchemaRef) -> Result<Vec<SpillReader>, DatabaseError> {
+        self.writers
+            .into_iter()
+            .map(|writer| writer.finish(schema.clone()))
+            .try_collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::errors::DatabaseError;
+    use crate::execution::spill::SpillWriter;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::{DataValue, Utf8Type};
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+    use sqlparser::ast::CharLengthUnits;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_spill_write_and_read() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let schema = Arc::new(vec![
+            Arc::new(ColumnCatalog::new(
+                "c1".to_string(),
+                true,
+                ColumnDesc::new(LogicalType::Integer, false, false, None),
+            )),
+            Arc::new(ColumnCatalog::new(
+                "c2".to_string(),
+                true,
+                ColumnDesc::new(
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    false,
+                    false,
+                    None,
+                ),
+            )),
+        ]);
+        let tuples = (0..100)
+            .map(|i| Tuple {
+                id: None,
+                values: vec![
+                    Arc::new(DataValue::Int32((i % 3 != 0).then_some(i))),
+                    Arc::new(DataValue::Utf8 {
+                        value: Some(format!("K{i}")),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    }),
+                ],
+            })
+            .collect_vec();
+
+        let mut writer = SpillWriter::new(temp_dir.path(), &schema)?;
+        for tuple in tuples.iter() {
+            writer.write(tuple)?;
+        }
+        let reader = writer.finish(schema)?;
+
+        debug_assert_eq!(reader.collect::<Result<Vec<_>, _>>()?, tuples);
+
+        Ok(())
+    }
+}
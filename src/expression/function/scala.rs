@@ -24,7 +24,7 @@ pub struct ScalarFunction {
 
 impl PartialEq for ScalarFunction {
     fn eq(&self, other: &Self) -> bool {
-        self.summary() == other.summary()
+        self.summary() == other.summary() && self.args == other.args
     }
 }
 
@@ -45,9 +45,22 @@ pub trait ScalarFunctionImpl: Debug + Send + Sync {
         schema: &[ColumnRef],
     ) -> Result<DataValue, DatabaseError>;
 
-    // TODO: Exploiting monotonicity when optimizing `ScalarFunctionImpl::monotonicity()`
     fn monotonicity(&self) -> Option<FuncMonotonicity>;
 
+    /// Returns the least value of the argument at `index` that the function maps to `value`,
+    /// the other arguments are constants.
+    ///
+    /// With the monotonicity on the argument, predicates on the function such as
+    /// `f(c1) >= value` can be detached into index ranges on `c1`.
+    fn inverse(
+        &self,
+        _args: &[ScalarExpression],
+        _index: usize,
+        _value: &DataValue,
+    ) -> Result<Option<DataValue>, DatabaseError> {
+        Ok(None)
+    }
+
     fn return_type(&self) -> &LogicalType;
 
     fn summary(&self) -> &FunctionSummary;
@@ -471,6 +471,114 @@ impl ScalarExpression {
         Ok(())
     }
 
+    /// Visits every constant of the expression, e.g. to substitute the literals of a cached
+    /// plan by the ones of another statement.
+    pub(crate) fn visit_constants_mut<F: FnMut(&mut ValueRef)>(&mut self, f: &mut F) {
+        match self {
+            ScalarExpression::Constant(value) => f(value),
+            ScalarExpression::ColumnRef(_) | ScalarExpression::Empty => (),
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. }
+            | ScalarExpression::Reference { expr, .. } => expr.visit_constants_mut(f),
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::IfNull {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::NullIf {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::Position {
+                expr: left_expr,
+                in_expr: right_expr,
+            } => {
+                left_expr.visit_constants_mut(f);
+                right_expr.visit_constants_mut(f);
+            }
+            ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Tuple(args) => {
+                for arg in args {
+                    arg.visit_constants_mut(f);
+                }
+            }
+            ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
+            | ScalarExpression::TableFunction(TableFunction { args, .. }) => {
+                for arg in args {
+                    arg.visit_constants_mut(f);
+                }
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                expr.visit_constants_mut(f);
+                for arg in args {
+                    arg.visit_constants_mut(f);
+                }
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::If {
+                condition: expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                expr.visit_constants_mut(f);
+                left_expr.visit_constants_mut(f);
+                right_expr.visit_constants_mut(f);
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                expr.visit_constants_mut(f);
+                for expr in for_expr.iter_mut().chain(from_expr.iter_mut()) {
+                    expr.visit_constants_mut(f);
+                }
+            }
+            ScalarExpression::Trim {
+                expr,
+                trim_what_expr,
+                ..
+            } => {
+                expr.visit_constants_mut(f);
+                if let Some(trim_what_expr) = trim_what_expr {
+                    trim_what_expr.visit_constants_mut(f);
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                if let Some(expr) = operand_expr {
+                    expr.visit_constants_mut(f);
+                }
+                for (expr_1, expr_2) in expr_pairs {
+                    expr_1.visit_constants_mut(f);
+                    expr_2.visit_constants_mut(f);
+                }
+                if let Some(expr) = else_expr {
+                    expr.visit_constants_mut(f);
+                }
+            }
+        }
+    }
+
     pub fn has_count_star(&self) -> bool {
         match self {
             ScalarExpression::Alias { expr, .. } => expr.has_count_star(),
@@ -1,7 +1,8 @@
 use crate::catalog::ColumnRef;
+use crate::expression::function::scala::ScalarFunction;
 use crate::expression::{BinaryOperator, ScalarExpression};
 use crate::types::value::{DataValue, ValueRef, NULL_VALUE};
-use crate::types::ColumnId;
+use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::Bound;
@@ -12,6 +13,9 @@ use std::{fmt, mem};
 /// Used to represent binary relationships between fields and constants
 /// Tips: The NotEq case is ignored because it makes expression composition very complex
 /// - [`Range::Scope`]:
+///
+/// On composite indexes the values are tuples, and a bound tuple shorter than the index key is
+/// a prefix of it: e.g. `[(1, 5), (1)]` covers every key with `a = 1 AND b >= 5`
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Range {
     Scope {
@@ -122,6 +126,10 @@ impl Range {
                 match bound {
                     Bound::Included(v) => Bound::Included(merge_value(tuple, v)),
                     Bound::Excluded(v) => Bound::Excluded(merge_value(tuple, v)),
+                    // the unbounded side is limited to the keys prefixed by the equal values
+                    Bound::Unbounded if !tuple.is_empty() => Bound::Included(Arc::new(
+                        DataValue::Tuple(Some(tuple.iter().copied().cloned().collect())),
+                    )),
                     Bound::Unbounded => Bound::Unbounded,
                 }
             }
@@ -147,20 +155,6 @@ impl Range {
 
         node.enumeration(&mut Vec::new(), &mut combinations);
 
-        if let Some(combination) = match self {
-            Range::Scope {
-                min: Bound::Unbounded,
-                ..
-            } => combinations.last(),
-            Range::Scope {
-                max: Bound::Unbounded,
-                ..
-            } => combinations.first(),
-            _ => None,
-        } {
-            return Some(_to_tuple_range(combination, self.clone()));
-        }
-
         let mut ranges = Vec::new();
 
         for tuple in combinations {
@@ -173,16 +167,30 @@ impl Range {
     }
 }
 
+/// The key of the index that the ranges are detached for.
+enum DetachKey<'a> {
+    Column(&'a ColumnId),
+    /// The key of an expression index, e.g. `substring(c1, 1, 2)`, which is matched as a whole.
+    Expr(&'a ScalarExpression),
+}
+
 pub struct RangeDetacher<'a> {
     table_name: &'a str,
-    column_id: &'a ColumnId,
+    key: DetachKey<'a>,
 }
 
 impl<'a> RangeDetacher<'a> {
     pub(crate) fn new(table_name: &'a str, column_id: &'a ColumnId) -> Self {
         Self {
             table_name,
-            column_id,
+            key: DetachKey::Column(column_id),
+        }
+    }
+
+    pub(crate) fn with_expr(table_name: &'a str, expr: &'a ScalarExpression) -> Self {
+        Self {
+            table_name,
+            key: DetachKey::Expr(expr),
         }
     }
 
@@ -198,6 +206,15 @@ impl<'a> RangeDetacher<'a> {
                     Self::merge_binary(*op, left_binary, right_binary)
                 }
                 (None, None) => {
+                    if let (true, Some(val)) =
+                        (self.is_key_expr(left_expr), right_expr.unpack_val())
+                    {
+                        return self.new_expr_range(*op, val, false);
+                    } else if let (Some(val), true) =
+                        (left_expr.unpack_val(), self.is_key_expr(right_expr))
+                    {
+                        return self.new_expr_range(*op, val, true);
+                    }
                     if let (Some(col), Some(val)) =
                         (left_expr.unpack_col(false), right_expr.unpack_val())
                     {
@@ -207,6 +224,11 @@ impl<'a> RangeDetacher<'a> {
                     {
                         return self.new_range(*op, col, val, true);
                     }
+                    if let Some(val) = right_expr.unpack_val() {
+                        return self.detach_monotonic(Self::flip(*op, false), left_expr, val);
+                    } else if let Some(val) = left_expr.unpack_val() {
+                        return self.detach_monotonic(Self::flip(*op, true), right_expr, val);
+                    }
 
                     None
                 }
@@ -220,17 +242,22 @@ impl<'a> RangeDetacher<'a> {
             | ScalarExpression::SubString { expr, .. } => self.detach(expr),
             ScalarExpression::Position { expr, .. } => self.detach(expr),
             ScalarExpression::Trim { expr, .. } => self.detach(expr),
+            ScalarExpression::IsNull { expr, negated, .. } if self.is_key_expr(expr) => {
+                if *negated {
+                    None
+                } else {
+                    Some(Range::Eq(NULL_VALUE.clone()))
+                }
+            }
             ScalarExpression::IsNull { expr, negated, .. } => match expr.as_ref() {
                 ScalarExpression::ColumnRef(column) => {
-                    if let (Some(col_id), Some(col_table)) = (column.id(), column.table_name()) {
-                        if &col_id == self.column_id && col_table.as_str() == self.table_name {
-                            return if *negated {
-                                // Range::NotEq(NULL_VALUE.clone())
-                                None
-                            } else {
-                                Some(Range::Eq(NULL_VALUE.clone()))
-                            };
-                        }
+                    if self.is_key_column(column) {
+                        return if *negated {
+                            // Range::NotEq(NULL_VALUE.clone())
+                            None
+                        } else {
+                            Some(Range::Eq(NULL_VALUE.clone()))
+                        };
                     }
 
                     None
@@ -699,25 +726,69 @@ impl<'a> RangeDetacher<'a> {
         }
     }
 
+    fn flip(op: BinaryOperator, is_flip: bool) -> BinaryOperator {
+        if !is_flip {
+            return op;
+        }
+        match op {
+            BinaryOperator::Gt => BinaryOperator::Lt,
+            BinaryOperator::Lt => BinaryOperator::Gt,
+            BinaryOperator::GtEq => BinaryOperator::LtEq,
+            BinaryOperator::LtEq => BinaryOperator::GtEq,
+            source_op => source_op,
+        }
+    }
+
+    fn is_key_column(&self, col: &ColumnRef) -> bool {
+        match self.key {
+            DetachKey::Column(column_id) => {
+                Self::_is_belong(self.table_name, col) && col.id() == Some(*column_id)
+            }
+            DetachKey::Expr(_) => false,
+        }
+    }
+
+    fn is_key_expr(&self, expr: &ScalarExpression) -> bool {
+        match self.key {
+            DetachKey::Column(_) => false,
+            DetachKey::Expr(key) => key == expr.unpack_alias_ref(),
+        }
+    }
+
     fn new_range(
         &mut self,
-        mut op: BinaryOperator,
+        op: BinaryOperator,
         col: ColumnRef,
         val: ValueRef,
         is_flip: bool,
     ) -> Option<Range> {
-        if !Self::_is_belong(self.table_name, &col) || col.id() != Some(*self.column_id) {
+        if !self.is_key_column(&col) {
             return None;
         }
-        if is_flip {
-            op = match op {
-                BinaryOperator::Gt => BinaryOperator::Lt,
-                BinaryOperator::Lt => BinaryOperator::Gt,
-                BinaryOperator::GtEq => BinaryOperator::LtEq,
-                BinaryOperator::LtEq => BinaryOperator::GtEq,
-                source_op => source_op,
-            };
-        }
+        Self::build_range(Self::flip(op, is_flip), val)
+    }
+
+    /// `expr op val` where `expr` is the key of the expression index, the value is cast to the
+    /// type of the key as the entries of the index are encoded by it.
+    fn new_expr_range(
+        &mut self,
+        op: BinaryOperator,
+        val: ValueRef,
+        is_flip: bool,
+    ) -> Option<Range> {
+        let DetachKey::Expr(key) = self.key else {
+            return None;
+        };
+        let ty = key.return_type();
+        let val = if val.logical_type() != ty {
+            Arc::new(DataValue::clone(&val).cast(&ty).ok()?)
+        } else {
+            val
+        };
+        Self::build_range(Self::flip(op, is_flip), val)
+    }
+
+    fn build_range(op: BinaryOperator, val: ValueRef) -> Option<Range> {
         match op {
             BinaryOperator::Gt => Some(Range::Scope {
                 min: Bound::Excluded(val.clone()),
@@ -740,6 +811,109 @@ impl<'a> RangeDetacher<'a> {
         }
     }
 
+    /// Detaches `expr op val` where `expr` is the column wrapped in monotonic functions, e.g.
+    /// `f(g(c1)) >= 1`. The functions are peeled off from the outside by their inverse, the
+    /// least argument `x` that is mapped to `val`:
+    ///
+    /// - increasing: `f(c1) > val => c1 > x`, `f(c1) >= val => c1 >= x`, `f(c1) < val => c1 < x`
+    /// - decreasing: `f(c1) < val => c1 > x`, `f(c1) <= val => c1 >= x`, `f(c1) > val => c1 < x`
+    ///
+    /// `f(c1) = val` only tells `c1 >= x` as the function may map a span of arguments to `val`,
+    /// the range can be wider than the predicate, which is still evaluated by the filter.
+    ///
+    /// Integer `+`/`-` with a constant and integer casts are inverted exactly, and the bound is
+    /// given up when it can't be represented by the argument type.
+    fn detach_monotonic(
+        &mut self,
+        op: BinaryOperator,
+        expr: &ScalarExpression,
+        val: ValueRef,
+    ) -> Option<Range> {
+        match expr {
+            ScalarExpression::ColumnRef(col) => self.new_range(op, col.clone(), val, false),
+            ScalarExpression::Alias { expr, .. } => self.detach_monotonic(op, expr, val),
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) => {
+                if val.is_null() {
+                    return None;
+                }
+                let (index, arg) = args
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, arg)| arg.unpack_val().is_none())
+                    .exactly_one()
+                    .ok()?;
+                let is_increasing = inner.monotonicity()?.get(index).cloned()??;
+                let op = match (is_increasing, op) {
+                    (true, BinaryOperator::Gt)
+                    | (true, BinaryOperator::GtEq)
+                    | (true, BinaryOperator::Lt) => op,
+                    (false, BinaryOperator::Lt) => BinaryOperator::Gt,
+                    (false, BinaryOperator::LtEq) => BinaryOperator::GtEq,
+                    (false, BinaryOperator::Gt) => BinaryOperator::Lt,
+                    (_, BinaryOperator::Eq) => BinaryOperator::GtEq,
+                    _ => return None,
+                };
+                let arg_val = inner.inverse(args, index, &val).ok()??;
+
+                self.detach_monotonic(
+                    op,
+                    arg,
+                    Arc::new(Self::exact_cast(&arg_val, &arg.return_type())?),
+                )
+            }
+            ScalarExpression::Binary {
+                op: arithmetic_op @ (BinaryOperator::Plus | BinaryOperator::Minus),
+                left_expr,
+                right_expr,
+                ty,
+                ..
+            } => {
+                if val.is_null() || !(ty.is_signed_numeric() || ty.is_unsigned_numeric()) {
+                    return None;
+                }
+                // `c1 + c`, `c + c1` and `c1 - c` increase with `c1`, `c - c1` decreases
+                let (arg, constant, is_increasing) =
+                    match (left_expr.unpack_val(), right_expr.unpack_val()) {
+                        (None, Some(constant)) => (left_expr, constant, true),
+                        (Some(constant), None) => {
+                            (right_expr, constant, arithmetic_op == &BinaryOperator::Plus)
+                        }
+                        _ => return None,
+                    };
+                let value = Self::exact_cast(&val, &LogicalType::Bigint)?.i64()?;
+                let constant = Self::exact_cast(&constant, &LogicalType::Bigint)?.i64()?;
+                let arg_val = match (arithmetic_op, is_increasing) {
+                    (BinaryOperator::Plus, _) => value.checked_sub(constant)?,
+                    (_, true) => value.checked_add(constant)?,
+                    (_, false) => constant.checked_sub(value)?,
+                };
+                let arg_val =
+                    Self::exact_cast(&DataValue::Int64(Some(arg_val)), &arg.return_type())?;
+
+                self.detach_monotonic(Self::flip(op, !is_increasing), arg, Arc::new(arg_val))
+            }
+            ScalarExpression::TypeCast { expr, ty } => {
+                let expr_ty = expr.return_type();
+                let is_integer =
+                    |ty: &LogicalType| ty.is_signed_numeric() || ty.is_unsigned_numeric();
+
+                if val.is_null() || !is_integer(ty) || !is_integer(&expr_ty) {
+                    return None;
+                }
+                self.detach_monotonic(op, expr, Arc::new(Self::exact_cast(&val, &expr_ty)?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Casts `val` into `ty` only when the cast is lossless, a rounded bound would narrow the
+    /// range and miss tuples that satisfy the predicate.
+    fn exact_cast(val: &DataValue, ty: &LogicalType) -> Option<DataValue> {
+        let cast_val = val.clone().cast(ty).ok()?;
+
+        (cast_val.clone().cast(&val.logical_type()).ok()? == *val).then_some(cast_val)
+    }
+
     /// check if: `c1 > c2 or c1 > 1` or `c2 > 1 or c1 > 1`
     /// this case it makes no sense to just extract c1 > 1
     fn check_or(&mut self, op: &BinaryOperator, binary: Range) -> Option<Range> {
@@ -784,8 +958,13 @@ impl fmt::Display for Range {
 #[cfg(test)]
 mod test {
     use crate::binder::test::select_sql_run;
+    use crate::catalog::ColumnRef;
+    use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
+    use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+    use crate::expression::function::FunctionSummary;
     use crate::expression::range_detacher::{Range, RangeDetacher};
+    use crate::expression::ScalarExpression;
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::optimizer::HepOptimizer;
     use crate::optimizer::rule::normalization::NormalizationRuleImpl;
@@ -793,9 +972,105 @@ mod test {
     use crate::planner::operator::Operator;
     use crate::planner::LogicalPlan;
     use crate::storage::rocksdb::RocksTransaction;
+    use crate::types::tuple::Tuple;
     use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use chrono::{Datelike, Duration, NaiveDate};
+    use itertools::Itertools;
+    use serde::{Deserialize, Serialize};
     use std::ops::Bound;
     use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// `floor_ten(x) = x / 10 * 10`, maps `[20, 29]` to `20`
+    #[derive(Debug, Serialize, Deserialize)]
+    struct FloorTen {
+        summary: FunctionSummary,
+    }
+
+    /// `negate(x) = -x`
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Negate {
+        summary: FunctionSummary,
+    }
+
+    #[typetag::serde]
+    impl ScalarFunctionImpl for FloorTen {
+        fn eval(
+            &self,
+            args: &[ScalarExpression],
+            tuple: &Tuple,
+            schema: &[ColumnRef],
+        ) -> Result<DataValue, DatabaseError> {
+            Ok(match args[0].eval(tuple, schema)?.as_ref() {
+                DataValue::Int32(value) => DataValue::Int32(value.map(|v| v.div_euclid(10) * 10)),
+                _ => DataValue::Int32(None),
+            })
+        }
+
+        fn monotonicity(&self) -> Option<FuncMonotonicity> {
+            Some(vec![Some(true)])
+        }
+
+        fn inverse(
+            &self,
+            _: &[ScalarExpression],
+            _: usize,
+            value: &DataValue,
+        ) -> Result<Option<DataValue>, DatabaseError> {
+            Ok(match value {
+                DataValue::Int32(Some(v)) if v % 10 == 0 => Some(value.clone()),
+                _ => None,
+            })
+        }
+
+        fn return_type(&self) -> &LogicalType {
+            &LogicalType::Integer
+        }
+
+        fn summary(&self) -> &FunctionSummary {
+            &self.summary
+        }
+    }
+
+    #[typetag::serde]
+    impl ScalarFunctionImpl for Negate {
+        fn eval(
+            &self,
+            args: &[ScalarExpression],
+            tuple: &Tuple,
+            schema: &[ColumnRef],
+        ) -> Result<DataValue, DatabaseError> {
+            Ok(match args[0].eval(tuple, schema)?.as_ref() {
+                DataValue::Int32(value) => DataValue::Int32(value.map(|v| -v)),
+                _ => DataValue::Int32(None),
+            })
+        }
+
+        fn monotonicity(&self) -> Option<FuncMonotonicity> {
+            Some(vec![Some(false)])
+        }
+
+        fn inverse(
+            &self,
+            _: &[ScalarExpression],
+            _: usize,
+            value: &DataValue,
+        ) -> Result<Option<DataValue>, DatabaseError> {
+            Ok(match value {
+                DataValue::Int32(Some(v)) => Some(DataValue::Int32(Some(-v))),
+                _ => None,
+            })
+        }
+
+        fn return_type(&self) -> &LogicalType {
+            &LogicalType::Integer
+        }
+
+        fn summary(&self) -> &FunctionSummary {
+            &self.summary
+        }
+    }
 
     fn plan_filter(plan: LogicalPlan) -> Result<Option<FilterOperator>, DatabaseError> {
         let best_plan = HepOptimizer::new(plan.clone())
@@ -1440,6 +1715,24 @@ mod test {
             ]),
         ];
 
+        let tuple = |values: &[Option<i32>]| {
+            Arc::new(DataValue::Tuple(Some(
+                values
+                    .iter()
+                    .map(|value| Arc::new(DataValue::Int32(*value)))
+                    .collect(),
+            )))
+        };
+        let prefixes = [
+            [Some(1), None, Some(1)],
+            [Some(1), None, Some(2)],
+            [Some(1), Some(1), Some(1)],
+            [Some(1), Some(1), Some(2)],
+            [Some(1), Some(2), Some(1)],
+            [Some(1), Some(2), Some(2)],
+        ];
+
+        // the unbounded side is limited to the keys prefixed by each combination of the eqs
         let range = Range::Scope {
             min: Bound::Included(Arc::new(DataValue::Int32(Some(1)))),
             max: Bound::Unbounded,
@@ -1448,15 +1741,15 @@ mod test {
 
         debug_assert_eq!(
             range,
-            Some(Range::Scope {
-                min: Bound::Included(Arc::new(DataValue::Tuple(Some(vec![
-                    Arc::new(DataValue::Int32(Some(1))),
-                    Arc::new(DataValue::Int32(None)),
-                    Arc::new(DataValue::Int32(Some(1))),
-                    Arc::new(DataValue::Int32(Some(1))),
-                ])))),
-                max: Bound::Unbounded,
-            })
+            Some(Range::SortedRanges(
+                prefixes
+                    .iter()
+                    .map(|prefix| Range::Scope {
+                        min: Bound::Included(tuple(&[&prefix[..], &[Some(1)]].concat())),
+                        max: Bound::Included(tuple(prefix)),
+                    })
+                    .collect()
+            ))
         );
 
         let range = Range::Scope {
@@ -1465,16 +1758,31 @@ mod test {
         }
         .combining_eqs(&eqs_ranges);
 
+        debug_assert_eq!(
+            range,
+            Some(Range::SortedRanges(
+                prefixes
+                    .iter()
+                    .map(|prefix| Range::Scope {
+                        min: Bound::Included(tuple(prefix)),
+                        max: Bound::Included(tuple(&[&prefix[..], &[Some(1)]].concat())),
+                    })
+                    .collect()
+            ))
+        );
+
+        // without eqs, the range is just wrapped into tuples
+        let range = Range::Scope {
+            min: Bound::Excluded(Arc::new(DataValue::Int32(Some(1)))),
+            max: Bound::Unbounded,
+        }
+        .combining_eqs(&[]);
+
         debug_assert_eq!(
             range,
             Some(Range::Scope {
-                min: Bound::Unbounded,
-                max: Bound::Included(Arc::new(DataValue::Tuple(Some(vec![
-                    Arc::new(DataValue::Int32(Some(1))),
-                    Arc::new(DataValue::Int32(Some(2))),
-                    Arc::new(DataValue::Int32(Some(2))),
-                    Arc::new(DataValue::Int32(Some(1))),
-                ])))),
+                min: Bound::Excluded(tuple(&[Some(1)])),
+                max: Bound::Unbounded,
             })
         );
 
@@ -1609,4 +1917,124 @@ mod test {
         debug_assert_eq!(range_1, None);
         debug_assert_eq!(range_2, None);
     }
+
+    #[test]
+    fn test_detach_monotonic_functions() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path())
+            .register_scala_function(Arc::new(FloorTen {
+                summary: FunctionSummary {
+                    name: "floor_ten".to_string(),
+                    arg_types: vec![LogicalType::Integer],
+                },
+            }))
+            .register_scala_function(Arc::new(Negate {
+                summary: FunctionSummary {
+                    name: "negate".to_string(),
+                    arg_types: vec![LogicalType::Integer],
+                },
+            }))
+            .build()?;
+        let _ = database.run("create table t1 (c1 int primary key, c2 int)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            (0..200).map(|i| format!("({i}, {i})")).join(", ")
+        ))?;
+        let _ = database.run("analyze table t1")?;
+
+        for (predicate, range, expected) in [
+            ("floor_ten(c1) >= 20", Some("[20, +inf)"), 20..200),
+            ("floor_ten(c1) > 20", Some("(20, +inf)"), 30..200),
+            ("floor_ten(c1) < 20", Some("(-inf, 20)"), 0..20),
+            ("floor_ten(c1) = 20", Some("[20, +inf)"), 20..30),
+            ("floor_ten(c1) <= 20", None, 0..30),
+            ("floor_ten(c1) >= 25", None, 30..200),
+            ("negate(c1) > -20", Some("(-inf, 20)"), 0..20),
+            ("-30 >= negate(floor_ten(c1))", Some("[30, +inf)"), 30..200),
+            ("c1 + 1 > 10", Some("(9, +inf)"), 10..200),
+            ("floor_ten(c1 - 5) >= 20", Some("[25, +inf)"), 25..200),
+            ("negate(c1 + 1) > -20", Some("(-inf, 19)"), 0..19),
+            ("negate(100 - c1) < -70", Some("(-inf, 30)"), 0..30),
+        ] {
+            let (_, plan) = database.run(format!("explain select * from t1 where {predicate}"))?;
+            let plan = format!("{:?}", plan);
+
+            match range {
+                Some(range) => debug_assert!(
+                    plan.contains(&format!("IndexScan By pk_c1 => {range}")),
+                    "{predicate}: {plan}"
+                ),
+                None => debug_assert!(plan.contains("SeqScan"), "{predicate}: {plan}"),
+            }
+            let (_, tuples) =
+                database.run(format!("select c1 from t1 where {predicate} order by c1"))?;
+
+            debug_assert_eq!(
+                tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+                expected
+                    .map(|i| vec![Arc::new(DataValue::Int32(Some(i)))])
+                    .collect_vec(),
+                "{predicate}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detach_year() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let start = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        let dates = (0..400).map(|i| start + Duration::days(i)).collect_vec();
+        let _ = database.run("create table t1 (c1 date primary key)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            dates.iter().map(|date| format!("('{date}')")).join(", ")
+        ))?;
+        let _ = database.run("analyze table t1")?;
+
+        // 2023-07-01 ~ 2023-12-31 are the first 184 days
+        for (predicate, range, expected) in [
+            ("year(c1) >= 2024", "[2024-01-01, +inf)", 184..400),
+            ("year(c1) < 2024", "(-inf, 2024-01-01)", 0..184),
+        ] {
+            let (_, plan) = database.run(format!("explain select * from t1 where {predicate}"))?;
+            let plan = format!("{:?}", plan);
+
+            debug_assert!(
+                plan.contains(&format!("IndexScan By pk_c1 => {range}")),
+                "{predicate}: {plan}"
+            );
+            let (_, tuples) =
+                database.run(format!("select c1 from t1 where {predicate} order by c1"))?;
+
+            debug_assert_eq!(
+                tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+                dates[expected]
+                    .iter()
+                    .map(|date| vec![Arc::new(DataValue::Date32(Some(date.num_days_from_ce())))])
+                    .collect_vec(),
+                "{predicate}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_cast() {
+        debug_assert_eq!(
+            RangeDetacher::exact_cast(&DataValue::Int64(Some(9)), &LogicalType::Integer),
+            Some(DataValue::Int32(Some(9)))
+        );
+        debug_assert_eq!(
+            RangeDetacher::exact_cast(&DataValue::Float64(Some(9.5)), &LogicalType::Integer),
+            None
+        );
+        debug_assert_eq!(
+            RangeDetacher::exact_cast(&DataValue::Int64(Some(i64::MAX)), &LogicalType::Integer),
+            None
+        );
+    }
 }
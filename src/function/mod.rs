@@ -1,2 +1,3 @@
 pub(crate) mod current_date;
 pub(crate) mod numbers;
+pub(crate) mod year;
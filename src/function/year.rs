@@ -0,0 +1,72 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `year(date)`, non-decreasing on the date: predicates such as `year(c1) >= 2024` are
+/// detached into index ranges on `c1`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Year {
+    summary: FunctionSummary,
+}
+
+impl Year {
+    pub(crate) fn new() -> Arc<Self> {
+        let function_name = "year".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types: vec![LogicalType::Date],
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Year {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let value = args[0].eval(tuple, schema)?;
+
+        Ok(DataValue::Int32(value.date().map(|date| date.year())))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        Some(vec![Some(true)])
+    }
+
+    /// The first day of the year, the least date that `year` maps to `value`
+    fn inverse(
+        &self,
+        _: &[ScalarExpression],
+        _: usize,
+        value: &DataValue,
+    ) -> Result<Option<DataValue>, DatabaseError> {
+        Ok(value
+            .i32()
+            .and_then(|year| NaiveDate::from_ymd_opt(year, 1, 1))
+            .map(|date| DataValue::Date32(Some(date.num_days_from_ce()))))
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Integer
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -105,7 +105,7 @@ pub mod expression;
 mod function;
 #[cfg(feature = "marcos")]
 pub mod macros;
-mod optimizer;
+pub mod optimizer;
 pub mod parser;
 pub mod planner;
 pub mod storage;
@@ -110,7 +110,7 @@ macro_rules! scala_function {
             }
 
             fn monotonicity(&self) -> Option<::fnck_sql::expression::function::scala::FuncMonotonicity> {
-                todo!()
+                None
             }
 
             fn return_type(&self) -> &::fnck_sql::types::LogicalType {
@@ -81,6 +81,36 @@ impl<K: Hash> CountMinSketch<K> {
         self.add(key, 1)
     }
 
+    /// Takes the value off the lowest counters of the key, the ones `add` increments. The
+    /// other keys sharing these counters may be underestimated afterwards.
+    pub fn sub<Q: ?Sized + Hash>(&mut self, key: &Q, value: usize)
+    where
+        K: Borrow<Q>,
+    {
+        let mut hashes = [0u64, 0u64];
+        let lowest = (0..self.k_num)
+            .map(|k_i| {
+                let offset = self.offset(&mut hashes, key, k_i);
+                self.offsets[k_i] = offset;
+                self.counters[k_i][offset]
+            })
+            .min()
+            .unwrap();
+        for k_i in 0..self.k_num {
+            let offset = self.offsets[k_i];
+            if self.counters[k_i][offset] == lowest {
+                self.counters[k_i][offset] = self.counters[k_i][offset].saturating_sub(value);
+            }
+        }
+    }
+
+    pub fn decrement<Q: ?Sized + Hash>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+    {
+        self.sub(key, 1)
+    }
+
     pub fn estimate<Q: ?Sized + Hash>(&self, key: &Q) -> usize
     where
         K: Borrow<Q>,
@@ -174,6 +204,16 @@ mod tests {
         debug_assert_eq!(cms.estimate("key"), 300);
     }
 
+    #[test]
+    fn test_decrement() {
+        let mut cms = CountMinSketch::<&str>::new(100, 0.95, 10.0);
+        cms.add("key", 3);
+        cms.decrement("key");
+        debug_assert_eq!(cms.estimate("key"), 2);
+        cms.sub("key", 5);
+        debug_assert_eq!(cms.estimate("key"), 0);
+    }
+
     #[test]
     fn test_increment_multi() {
         let mut cms = CountMinSketch::<u64>::new(100, 0.99, 2.0);
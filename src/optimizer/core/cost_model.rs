@@ -0,0 +1,156 @@
+//! The cost model of the physical plans.
+//!
+//! Every implementation rule estimates the output rows of its operator from the rows of its
+//! children, and the cost of producing them. The cost is split into the CPU cost of processing
+//! tuples and the I/O cost of reading them from the storage, both measured in the cost of
+//! processing one tuple in memory, so that they can be added up and compared.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::Add;
+
+/// Processing one tuple in memory, e.g. evaluating a predicate on it.
+pub(crate) const CPU_TUPLE_COST: usize = 1;
+/// Inserting one tuple into a hashtable, which is more expensive than probing it.
+pub(crate) const HASH_BUILD_COST: usize = 2;
+/// Reading one tuple while iterating the storage.
+pub(crate) const SEQ_READ_COST: usize = 2;
+/// Seeking the storage for one tuple, e.g. looking up the table by a secondary index.
+pub(crate) const RANDOM_READ_COST: usize = 4;
+/// Selectivity of the predicates that the statistics can't tell.
+pub(crate) const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cost {
+    pub(crate) cpu: usize,
+    pub(crate) io: usize,
+}
+
+impl Cost {
+    pub fn new(cpu: usize, io: usize) -> Self {
+        Cost { cpu, io }
+    }
+
+    pub(crate) fn cpu(tuples: usize) -> Self {
+        Cost::new(tuples.saturating_mul(CPU_TUPLE_COST), 0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.cpu.saturating_add(self.io)
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Cost::new(
+            self.cpu.saturating_add(rhs.cpu),
+            self.io.saturating_add(rhs.io),
+        )
+    }
+}
+
+/// Estimated output rows of a plan node and the cost of the whole subtree, shown by EXPLAIN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Estimate {
+    pub rows: Option<usize>,
+    pub cost: Option<Cost>,
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let rows = self.rows.map(|rows| format!("rows: {}", rows));
+        let cost = self
+            .cost
+            .map(|cost| format!("cost: {} = cpu {} + io {}", cost.total(), cost.cpu, cost.io));
+
+        write!(
+            f,
+            "({})",
+            rows.into_iter().chain(cost).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
+
+/// Estimated rows that satisfy a predicate the statistics can't tell.
+pub(crate) fn filtered_rows(rows: usize) -> usize {
+    ((rows as f64 * DEFAULT_SELECTIVITY).ceil() as usize).max(1)
+}
+
+/// Comparisons of sorting the rows through a heap of `heap_size`, a full sort keeps all rows in
+/// the heap.
+pub(crate) fn sort_cost(rows: usize, heap_size: usize) -> Cost {
+    let depth = heap_size.max(2).ilog2() as usize;
+
+    Cost::cpu(rows.saturating_mul(depth))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::core::cost_model::{filtered_rows, sort_cost, Cost, Estimate};
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cost() {
+        let cost = Cost::new(10, 20) + Cost::cpu(5);
+
+        debug_assert_eq!(cost, Cost::new(15, 20));
+        debug_assert_eq!(cost.total(), 35);
+        debug_assert_eq!(filtered_rows(0), 1);
+        debug_assert_eq!(filtered_rows(10), 4);
+        debug_assert_eq!(sort_cost(1024, 1024), Cost::cpu(10240));
+        debug_assert_eq!(sort_cost(1000, 1), Cost::cpu(1000));
+
+        debug_assert_eq!(
+            Estimate {
+                rows: Some(3),
+                cost: Some(cost),
+            }
+            .to_string(),
+            "(rows: 3, cost: 35 = cpu 15 + io 20)"
+        );
+        debug_assert_eq!(
+            Estimate {
+                rows: Some(3),
+                cost: None,
+            }
+            .to_string(),
+            "(rows: 3)"
+        );
+    }
+
+    #[test]
+    fn test_explain_estimate() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run(format!(
+            "insert into t1 values {}",
+            (0..1000).map(|i| format!("({i}, {})", i % 10)).join(", ")
+        ))?;
+        let sql = "explain select * from t1 where a < 100 order by b limit 5";
+
+        let (_, plan) = database.run(sql)?;
+        debug_assert!(!format!("{:?}", plan).contains("cost: "), "{:?}", plan);
+
+        let _ = database.run("analyze table t1")?;
+        let (_, plan) = database.run(sql)?;
+        let plan = format!("{:?}", plan);
+        // the index scan reads 100 tuples, and each costs a tuple read and a cpu tuple
+        debug_assert!(
+            plan.contains(
+                "[IndexScan By pk_a => (-inf, 100)] (rows: 100, cost: 300 = cpu 100 + io 200)"
+            ),
+            "{}",
+            plan
+        );
+        debug_assert!(plan.contains("[TopN] (rows: 5, cost: "), "{}", plan);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+//! Cost-based eager aggregation.
+//!
+//! An aggregate over an inner join whose aggregate functions only read the columns of one side
+//! is split into a partial aggregate of that side, grouped by all its columns used above it, and
+//! a final aggregate of the partial results above the join, e.g.
+//! `SELECT d.name, sum(f.v) FROM fact f JOIN dim d ON f.k = d.k GROUP BY d.name` joins the sums
+//! of `fact` per `f.k` instead of all its tuples. A partial group joins the same tuples of the
+//! other side as each of its tuples, so summing the partial sums and counts, and taking the
+//! min and max of the partial ones, gives the results of the original aggregate.
+//!
+//! The split is done when the statistics estimate that the partial aggregate reduces the joined
+//! tuples enough to pay for itself.
+
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{AliasType, ScalarExpression};
+use crate::optimizer::core::cost_model::{CPU_TUPLE_COST, HASH_BUILD_COST};
+use crate::optimizer::core::join_order::Leaf;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::optimizer::heuristic::batch::HepMatchOrder;
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::Operator;
+use crate::storage::Transaction;
+use itertools::Itertools;
+
+struct EagerAggregation {
+    aggregate_id: HepNodeId,
+    join_id: HepNodeId,
+    // the input of the join aggregated in advance
+    child_id: HepNodeId,
+    groupby_columns: Vec<ColumnRef>,
+    cost: f64,
+}
+
+/// Splits every aggregate over an inner join whose split is estimated to be cheaper.
+pub(crate) fn push_aggregates<T: Transaction>(
+    graph: &mut HepGraph,
+    loader: &StatisticMetaLoader<'_, T>,
+) -> Result<(), DatabaseError> {
+    let aggregate_ids = graph
+        .nodes_iter(HepMatchOrder::TopDown, None)
+        .filter(|node_id| matches!(graph.operator(*node_id), Operator::Aggregate(_)))
+        .collect_vec();
+
+    for aggregate_id in aggregate_ids {
+        let Some(join_id) = graph.eldest_child_at(aggregate_id) else {
+            continue;
+        };
+        let Some(lazy_cost) = lazy_cost(graph, aggregate_id, join_id, loader)? else {
+            continue;
+        };
+        let mut best = None;
+
+        for child_id in graph.children_at(join_id).collect_vec() {
+            let Some(eager) =
+                EagerAggregation::build(graph, aggregate_id, join_id, child_id, loader)?
+            else {
+                continue;
+            };
+            if eager.cost < lazy_cost
+                && best
+                    .as_ref()
+                    .map_or(true, |best: &EagerAggregation| eager.cost < best.cost)
+            {
+                best = Some(eager);
+            }
+        }
+        if let Some(eager) = best {
+            eager.rewrite(graph);
+        }
+    }
+
+    Ok(())
+}
+
+/// The aggregate functions that can be computed from the partial results of the same function.
+fn is_splittable(agg_op: &AggregateOperator) -> bool {
+    !agg_op.groupby_exprs.is_empty()
+        && !agg_op.agg_calls.is_empty()
+        && agg_op.agg_calls.iter().all(|expr| {
+            matches!(
+                expr,
+                ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Sum | AggKind::Count | AggKind::Min | AggKind::Max,
+                    ..
+                }
+            )
+        })
+}
+
+/// The estimated rows of both inputs of the join and of its output.
+fn join_rows<T: Transaction>(
+    graph: &HepGraph,
+    join_id: HepNodeId,
+    loader: &StatisticMetaLoader<'_, T>,
+) -> Result<Option<(Vec<Leaf>, f64)>, DatabaseError> {
+    let Operator::Join(JoinOperator {
+        join_type: JoinType::Inner,
+        on: JoinCondition::On { on, .. },
+    }) = graph.operator(join_id)
+    else {
+        return Ok(None);
+    };
+    let mut leaves = Vec::with_capacity(2);
+
+    for child_id in graph.children_at(join_id) {
+        let Some(leaf) = Leaf::estimate(graph, child_id, loader)? else {
+            return Ok(None);
+        };
+        leaves.push(leaf);
+    }
+    let Some((left_expr, right_expr)) = on.first() else {
+        return Ok(None);
+    };
+    // the selectivity of the first join key, as the join reorder does
+    let distinct_values = [left_expr, right_expr]
+        .into_iter()
+        .zip(leaves.iter())
+        .filter_map(|(expr, leaf)| match expr.unpack_alias_ref() {
+            ScalarExpression::ColumnRef(column) => Some(leaf.distinct_values(column)),
+            _ => None,
+        })
+        .fold(1.0, f64::max);
+    let rows = leaves[0].rows * leaves[1].rows / distinct_values;
+
+    Ok(Some((leaves, rows.max(1.0))))
+}
+
+fn hash_join_cost(left_rows: f64, right_rows: f64) -> f64 {
+    HASH_BUILD_COST as f64 * left_rows.min(right_rows)
+        + CPU_TUPLE_COST as f64 * left_rows.max(right_rows)
+}
+
+fn hash_aggregate_cost(rows: f64) -> f64 {
+    HASH_BUILD_COST as f64 * rows
+}
+
+fn lazy_cost<T: Transaction>(
+    graph: &HepGraph,
+    aggregate_id: HepNodeId,
+    join_id: HepNodeId,
+    loader: &StatisticMetaLoader<'_, T>,
+) -> Result<Option<f64>, DatabaseError> {
+    let Operator::Aggregate(agg_op) = graph.operator(aggregate_id) else {
+        return Ok(None);
+    };
+    if !is_splittable(agg_op) {
+        return Ok(None);
+    }
+    let Some((leaves, rows)) = join_rows(graph, join_id, loader)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        hash_join_cost(leaves[0].rows, leaves[1].rows) + hash_aggregate_cost(rows),
+    ))
+}
+
+impl EagerAggregation {
+    fn build<T: Transaction>(
+        graph: &HepGraph,
+        aggregate_id: HepNodeId,
+        join_id: HepNodeId,
+        child_id: HepNodeId,
+        loader: &StatisticMetaLoader<'_, T>,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let (Operator::Aggregate(agg_op), Operator::Join(join_op)) =
+            (graph.operator(aggregate_id), graph.operator(join_id))
+        else {
+            return Ok(None);
+        };
+        let JoinCondition::On { on, filter } = &join_op.on else {
+            return Ok(None);
+        };
+        let Some((leaves, join_rows)) = join_rows(graph, join_id, loader)? else {
+            return Ok(None);
+        };
+        let Some(position) = graph.children_at(join_id).position(|id| id == child_id) else {
+            return Ok(None);
+        };
+        let (leaf, other_leaf) = (&leaves[position], &leaves[1 - position]);
+        let fn_contains = |leaf: &Leaf, column: &ColumnRef| {
+            leaf.columns
+                .iter()
+                .any(|leaf_column| leaf_column.summary() == column.summary())
+        };
+
+        // the aggregate functions can't read the other input, the columns of the inputs renamed
+        // by table aliases are only found among all the columns the expressions are made of
+        if agg_op
+            .agg_calls
+            .iter()
+            .flat_map(|expr| expr.referenced_columns(false))
+            .any(|column| fn_contains(other_leaf, &column))
+        {
+            return Ok(None);
+        }
+        let mut groupby_columns: Vec<ColumnRef> = Vec::new();
+        let used_exprs = on
+            .iter()
+            .map(
+                |(left_expr, right_expr)| {
+                    if position == 0 {
+                        left_expr
+                    } else {
+                        right_expr
+                    }
+                },
+            )
+            .chain(filter.iter())
+            .chain(agg_op.groupby_exprs.iter());
+
+        for column in used_exprs.flat_map(|expr| expr.referenced_columns(false)) {
+            if fn_contains(leaf, &column)
+                && !groupby_columns
+                    .iter()
+                    .any(|groupby_column| groupby_column.summary() == column.summary())
+            {
+                groupby_columns.push(column);
+            }
+        }
+        let groups = groupby_columns
+            .iter()
+            .map(|column| leaf.distinct_values(column))
+            .product::<f64>()
+            .min(leaf.rows);
+        let ratio = groups / leaf.rows;
+        let cost = hash_aggregate_cost(leaf.rows)
+            + hash_join_cost(groups, other_leaf.rows)
+            + hash_aggregate_cost((join_rows * ratio).max(1.0));
+
+        Ok(Some(EagerAggregation {
+            aggregate_id,
+            join_id,
+            child_id,
+            groupby_columns,
+            cost,
+        }))
+    }
+
+    fn rewrite(self, graph: &mut HepGraph) {
+        let Operator::Aggregate(agg_op) = graph.operator(self.aggregate_id).clone() else {
+            return;
+        };
+        let final_calls = agg_op
+            .agg_calls
+            .iter()
+            .map(|expr| match expr {
+                ScalarExpression::AggCall { kind, ty, .. } => ScalarExpression::AggCall {
+                    distinct: false,
+                    // the counts of the partial groups are summed
+                    kind: if *kind == AggKind::Count {
+                        AggKind::Sum
+                    } else {
+                        *kind
+                    },
+                    args: vec![expr.clone()],
+                    ty: *ty,
+                },
+                expr => expr.clone(),
+            })
+            .collect_vec();
+        // the final aggregate functions are renamed to the original ones for the operators above
+        let project_exprs = final_calls
+            .iter()
+            .zip(agg_op.agg_calls.iter())
+            .map(|(final_call, agg_call)| ScalarExpression::Alias {
+                expr: Box::new(final_call.clone()),
+                alias: AliasType::Expr(Box::new(agg_call.clone())),
+            })
+            .chain(agg_op.groupby_exprs.iter().cloned())
+            .collect_vec();
+
+        graph.add_node(
+            self.join_id,
+            Some(self.child_id),
+            Operator::Aggregate(AggregateOperator {
+                groupby_exprs: self
+                    .groupby_columns
+                    .into_iter()
+                    .map(ScalarExpression::ColumnRef)
+                    .collect_vec(),
+                agg_calls: agg_op.agg_calls.clone(),
+                is_distinct: false,
+            }),
+        );
+        graph.replace_node(
+            self.aggregate_id,
+            Operator::Aggregate(AggregateOperator {
+                agg_calls: final_calls,
+                ..agg_op
+            }),
+        );
+        let project = Operator::Project(ProjectOperator {
+            exprs: project_exprs,
+        });
+        match graph.parent_id(self.aggregate_id) {
+            Some(parent_id) => graph.add_node(parent_id, Some(self.aggregate_id), project),
+            None => graph.add_root(project),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{DataBaseBuilder, Database};
+    use crate::errors::DatabaseError;
+    use crate::storage::rocksdb::RocksStorage;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    fn build_tables(database: &Database<RocksStorage>) -> Result<(), DatabaseError> {
+        let _ = database.run("create table fact (id int primary key, k int, v int)")?;
+        let _ = database.run("create table dim (k int primary key, name varchar)")?;
+        let _ = database.run("create index fact_k_index on fact (k)")?;
+        let _ = database.run(format!(
+            "insert into fact values {}",
+            (0..2000)
+                .map(|i| format!("({i}, {}, {})", i % 100, i % 7))
+                .join(", ")
+        ))?;
+        let _ = database.run(format!(
+            "insert into dim values {}",
+            (0..100).map(|i| format!("({i}, 'n{}')", i % 10)).join(", ")
+        ))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_aggregates() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        build_tables(&database)?;
+
+        let sql = "select d.name, sum(f.v), count(*), min(f.v), max(f.id) from fact f \
+            join dim d on f.k = d.k group by d.name";
+        let (_, expected) = database.run(format!("{sql} order by d.name"))?;
+
+        let _ = database.run("analyze table fact")?;
+        let _ = database.run("analyze table dim")?;
+        // `fact` has 20 rows per join key, so it is aggregated by `f.k` before the join
+        let (_, plan) = database.run(format!("explain {sql}"))?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("Group By [f.k]"), "{}", plan);
+        debug_assert!(plan.contains("Sum(Count(*))"), "{}", plan);
+        let (_, tuples) = database.run(format!("{sql} order by d.name"))?;
+
+        debug_assert_eq!(
+            tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+            expected.into_iter().map(|tuple| tuple.values).collect_vec()
+        );
+
+        // the aggregate reads both inputs
+        let (_, plan) = database.run(
+            "explain select d.name, sum(f.v + d.k) from fact f join dim d on f.k = d.k group by d.name",
+        )?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(!plan.contains("Group By [f.k]"), "{}", plan);
+
+        Ok(())
+    }
+}
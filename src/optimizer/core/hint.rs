@@ -0,0 +1,395 @@
+//! Optimizer hints, which override the choices of the optimizer when the statistics mislead it.
+//!
+//! - `USE_INDEX(t idx ..)`: scans `t` by one of the indexes if they can be used, without an index
+//!   otherwise.
+//! - `NO_INDEX(t [idx ..])`: never scans or looks up `t` by the indexes, all of them if none is
+//!   given.
+//! - `HASH_JOIN[(t ..)]` and `NL_JOIN[(t ..)]`: joins by a hashtable or by a nested loop, only
+//!   the joins of the given tables if any.
+//! - `LEADING(t ..)`: joins the tables first, in the given order.
+//!
+//! The tables are named by their names or their aliases. A hint that can't be followed leaves the
+//! choice to the optimizer, and an unknown one is ignored with a warning.
+
+use crate::catalog::TableName;
+use crate::expression::{AliasType, ScalarExpression};
+use crate::optimizer::heuristic::batch::HepMatchOrder;
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::parser::Hint;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::types::index::IndexMetaRef;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JoinMethod {
+    Hash,
+    NestLoop,
+}
+
+#[derive(Debug, Clone)]
+struct TableIndexes {
+    table: String,
+    indexes: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OptimizerHints {
+    use_indexes: Vec<TableIndexes>,
+    no_indexes: Vec<TableIndexes>,
+    // the joins of the tables, or all joins if there is no table
+    join_methods: Vec<(JoinMethod, Vec<String>)>,
+    leading: Vec<String>,
+}
+
+impl OptimizerHints {
+    pub fn new(hints: &[Hint]) -> Self {
+        let mut optimizer_hints = OptimizerHints::default();
+
+        for Hint { name, args } in hints {
+            let args = args.iter().map(|arg| arg.to_lowercase()).collect_vec();
+            let table_indexes = || {
+                args.split_first().map(|(table, indexes)| TableIndexes {
+                    table: table.clone(),
+                    indexes: indexes.to_vec(),
+                })
+            };
+
+            match name.as_str() {
+                "USE_INDEX" if args.len() > 1 => {
+                    optimizer_hints.use_indexes.extend(table_indexes())
+                }
+                "NO_INDEX" if !args.is_empty() => {
+                    optimizer_hints.no_indexes.extend(table_indexes())
+                }
+                "HASH_JOIN" => optimizer_hints.join_methods.push((JoinMethod::Hash, args)),
+                "NL_JOIN" => optimizer_hints
+                    .join_methods
+                    .push((JoinMethod::NestLoop, args)),
+                "LEADING" if !args.is_empty() => optimizer_hints.leading = args,
+                // reported by `OptimizerHints::warnings`
+                _ => (),
+            }
+        }
+        optimizer_hints
+    }
+
+    /// The hints ignored by [`OptimizerHints::new`]. They are reported before the plan cache
+    /// is looked up, so every run of the statement warns about them, not only the one planning
+    /// it.
+    pub(crate) fn warnings(hints: &[Hint]) -> Vec<String> {
+        hints
+            .iter()
+            .filter_map(|Hint { name, args }| match (name.as_str(), args.len()) {
+                ("USE_INDEX", 0 | 1) | ("NO_INDEX" | "LEADING", 0) => Some(format!(
+                    "ignored the hint `{}` without enough arguments",
+                    name
+                )),
+                ("USE_INDEX" | "NO_INDEX" | "HASH_JOIN" | "NL_JOIN" | "LEADING", _) => None,
+                _ => Some(format!("ignored the unknown hint `{}`", name)),
+            })
+            .collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.use_indexes.is_empty()
+            && self.no_indexes.is_empty()
+            && self.join_methods.is_empty()
+            && self.leading.is_empty()
+    }
+
+    /// Replaces the aliases of the tables in `graph` by the names of the tables.
+    pub(crate) fn resolve_aliases(&mut self, graph: &HepGraph) {
+        if self.is_empty() {
+            return;
+        }
+        let mut aliases = HashMap::new();
+
+        for node_id in graph.nodes_iter(HepMatchOrder::TopDown, None) {
+            let Operator::Project(project_op) = graph.operator(node_id) else {
+                continue;
+            };
+            for expr in project_op.exprs.iter() {
+                if let ScalarExpression::Alias {
+                    expr,
+                    alias: AliasType::Expr(alias_expr),
+                } = expr
+                {
+                    if let (
+                        ScalarExpression::ColumnRef(column),
+                        ScalarExpression::ColumnRef(alias_column),
+                    ) = (expr.as_ref(), alias_expr.as_ref())
+                    {
+                        if let (Some(table_name), Some(alias)) =
+                            (column.table_name(), alias_column.table_name())
+                        {
+                            aliases.insert(alias.to_string(), table_name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        let resolve = |table: &mut String| {
+            if let Some(table_name) = aliases.get(table.as_str()) {
+                table.clone_from(table_name);
+            }
+        };
+
+        for table_indexes in self
+            .use_indexes
+            .iter_mut()
+            .chain(self.no_indexes.iter_mut())
+        {
+            resolve(&mut table_indexes.table);
+        }
+        for table in self
+            .join_methods
+            .iter_mut()
+            .flat_map(|(_, tables)| tables.iter_mut())
+            .chain(self.leading.iter_mut())
+        {
+            resolve(table);
+        }
+    }
+
+    /// The tables to join first, in order.
+    pub(crate) fn leading(&self) -> &[String] {
+        &self.leading
+    }
+
+    /// Whether the join at `node_id` is hinted to be a nested loop join.
+    pub(crate) fn is_nest_loop_join(&self, graph: &HepGraph, node_id: HepNodeId) -> bool {
+        self.join_method(graph, node_id) == Some(JoinMethod::NestLoop)
+    }
+
+    /// Ranks a physical option of the operator at `node_id`, the options of the lowest rank
+    /// are the only ones the cheapest is chosen from.
+    pub(crate) fn rank(
+        &self,
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        option: &PhysicalOption,
+    ) -> usize {
+        let mut rank = 0;
+
+        match option {
+            PhysicalOption::IndexScan(index_info) => {
+                rank += self.index_rank(&[&index_info.meta], true);
+            }
+            PhysicalOption::IndexMerge(index_merge_info) => {
+                let index_metas = index_merge_info
+                    .index_infos
+                    .iter()
+                    .map(|index_info| &index_info.meta)
+                    .collect_vec();
+                rank += self.index_rank(&index_metas, true);
+            }
+            PhysicalOption::IndexSeek(index_meta) => {
+                rank += self.index_rank(&[index_meta], true);
+            }
+            // the index of the inner table is looked up instead of scanning it
+            PhysicalOption::IndexNestLoopJoin(index_meta) => {
+                rank += self.index_rank(&[index_meta], false);
+            }
+            PhysicalOption::SeqScan => {
+                if let Operator::TableScan(scan_op) = graph.operator(node_id) {
+                    rank += self
+                        .use_indexes
+                        .iter()
+                        .any(|table_indexes| table_indexes.table == *scan_op.table_name)
+                        as usize;
+                }
+            }
+            _ => (),
+        }
+        if let Some(join_method) = self.join_method(graph, node_id) {
+            rank += match (join_method, option) {
+                (JoinMethod::Hash, PhysicalOption::HashJoin)
+                | (
+                    JoinMethod::NestLoop,
+                    PhysicalOption::NestLoopJoin | PhysicalOption::IndexNestLoopJoin(_),
+                ) => 0,
+                _ => 1,
+            };
+        }
+        rank
+    }
+
+    /// Ranks the options reading the indexes of a table, `USE_INDEX` prefers the hinted
+    /// indexes to no index, and no index to the others.
+    fn index_rank(&self, index_metas: &[&IndexMetaRef], is_scan: bool) -> usize {
+        let index_names = index_metas
+            .iter()
+            .map(|index_meta| index_meta.name.to_lowercase())
+            .collect_vec();
+        let is_excluded = index_metas
+            .iter()
+            .zip(index_names.iter())
+            .any(|(index_meta, name)| {
+                self.no_indexes.iter().any(|table_indexes| {
+                    table_indexes.table == *index_meta.table_name
+                        && (table_indexes.indexes.is_empty()
+                            || table_indexes.indexes.contains(name))
+                })
+            });
+        let is_unhinted = is_scan
+            && index_metas
+                .iter()
+                .zip(index_names.iter())
+                .any(|(index_meta, name)| {
+                    self.use_indexes.iter().any(|table_indexes| {
+                        table_indexes.table == *index_meta.table_name
+                            && !table_indexes.indexes.contains(name)
+                    })
+                });
+
+        if is_excluded || is_unhinted {
+            2
+        } else {
+            0
+        }
+    }
+
+    fn join_method(&self, graph: &HepGraph, node_id: HepNodeId) -> Option<JoinMethod> {
+        if !matches!(graph.operator(node_id), Operator::Join(_)) {
+            return None;
+        }
+        let mut tables = None;
+
+        self.join_methods
+            .iter()
+            .rev()
+            .find(|(_, hint_tables)| {
+                hint_tables.is_empty() || {
+                    let tables = tables.get_or_insert_with(|| Self::tables(graph, node_id));
+                    hint_tables
+                        .iter()
+                        .any(|hint_table| tables.iter().any(|table| **table == *hint_table))
+                }
+            })
+            .map(|(join_method, _)| *join_method)
+    }
+
+    /// The tables scanned below `node_id`.
+    fn tables(graph: &HepGraph, node_id: HepNodeId) -> Vec<TableName> {
+        graph
+            .nodes_iter(HepMatchOrder::TopDown, Some(node_id))
+            .filter_map(|node_id| match graph.operator(node_id) {
+                Operator::TableScan(scan_op) => Some(scan_op.table_name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{DataBaseBuilder, Database};
+    use crate::errors::DatabaseError;
+    use crate::optimizer::core::hint::OptimizerHints;
+    use crate::parser::parse_sql_with_extensions;
+    use crate::storage::rocksdb::RocksStorage;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    fn build_tables(database: &Database<RocksStorage>) -> Result<(), DatabaseError> {
+        let _ = database.run("create table fact (id int primary key, k int, v int)")?;
+        let _ = database.run("create table dim (k int primary key, name varchar)")?;
+        let _ = database.run("create index i_k on fact (k)")?;
+        let _ = database.run("create index i_v on fact (v)")?;
+        let _ = database.run(format!(
+            "insert into fact values {}",
+            (0..2000)
+                .map(|i| format!("({i}, {}, {})", i % 100, i % 7))
+                .join(", ")
+        ))?;
+        let _ = database.run(format!(
+            "insert into dim values {}",
+            (0..100).map(|i| format!("({i}, 'n{}')", i % 10)).join(", ")
+        ))?;
+        let _ = database.run("analyze table fact")?;
+        let _ = database.run("analyze table dim")?;
+        Ok(())
+    }
+
+    fn explain(database: &Database<RocksStorage>, sql: &str) -> Result<String, DatabaseError> {
+        let (_, plan) = database.run(format!("explain {sql}"))?;
+        Ok(format!("{:?}", plan))
+    }
+
+    #[test]
+    fn test_index_hints() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        build_tables(&database)?;
+
+        let plan = explain(&database, "select * from fact where k = 3 and v = 2")?;
+        debug_assert!(plan.contains("IndexScan By i_k"), "{}", plan);
+        let plan = explain(
+            &database,
+            "select /*+ USE_INDEX(fact i_v) */ * from fact where k = 3 and v = 2",
+        )?;
+        debug_assert!(plan.contains("IndexScan By i_v"), "{}", plan);
+        let plan = explain(
+            &database,
+            "select /*+ NO_INDEX(f) */ * from fact f where k = 3 and v = 2",
+        )?;
+        debug_assert!(plan.contains("SeqScan"), "{}", plan);
+        debug_assert!(!plan.contains("IndexScan"), "{}", plan);
+        // the unknown hint and the hint without an index are ignored
+        let sql = "select /*+ FOO(x) USE_INDEX(fact) */ * from fact where k = 3";
+        let plan = explain(&database, sql)?;
+        debug_assert!(plan.contains("IndexScan By i_k"), "{}", plan);
+        debug_assert_eq!(
+            OptimizerHints::warnings(&parse_sql_with_extensions(sql)?[0].1.hints),
+            vec![
+                "ignored the unknown hint `FOO`".to_string(),
+                "ignored the hint `USE_INDEX` without enough arguments".to_string(),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_hints() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        build_tables(&database)?;
+        let _ = database.run("create table t3 (a int primary key, k int)")?;
+        let _ = database.run("insert into t3 values (1, 1), (2, 2)")?;
+
+        let sql = "select * from fact join dim on fact.k = dim.k";
+        let plan = explain(&database, sql)?;
+        debug_assert!(plan.contains("[HashJoin]"), "{}", plan);
+        let plan = explain(&database, &sql.replace("select", "select /*+ NL_JOIN */"))?;
+        debug_assert!(plan.contains("NestLoopJoin"), "{}", plan);
+        debug_assert!(!plan.contains("[HashJoin]"), "{}", plan);
+        let plan = explain(
+            &database,
+            "select /*+ HASH_JOIN(t3) NL_JOIN(d) */ * from fact join dim d on fact.k = d.k",
+        )?;
+        debug_assert!(plan.contains("NestLoopJoin"), "{}", plan);
+
+        // `t3` is not estimated, but the leading tables are joined first anyway
+        let sql = "select count(*) from fact join dim on fact.k = dim.k join t3 on t3.k = dim.k";
+        let (_, expected) = database.run(sql)?;
+        let hinted = sql.replace("select", "select /*+ LEADING(fact t3) */");
+        let plan = explain(&database, &hinted)?;
+        let fact = plan.find("TableScan fact").unwrap();
+        debug_assert!(fact < plan.find("TableScan t3").unwrap(), "{}", plan);
+        debug_assert!(
+            plan.find("TableScan t3") < plan.find("TableScan dim"),
+            "{}",
+            plan
+        );
+        let (_, tuples) = database.run(hinted)?;
+
+        debug_assert_eq!(
+            tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+            expected.into_iter().map(|tuple| tuple.values).collect_vec()
+        );
+
+        Ok(())
+    }
+}
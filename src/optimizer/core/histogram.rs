@@ -7,6 +7,7 @@ use crate::types::evaluator::EvaluatorFactory;
 use crate::types::index::{IndexId, IndexMeta};
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
+use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 use std::collections::Bound;
@@ -65,8 +66,8 @@ impl HistogramBuilder {
             let mut bytes = Vec::new();
 
             value.memcomparable_encode(&mut bytes)?;
+            self.sort_keys.push((self.values.len(), bytes));
             self.values.put((self.value_index, value.clone()));
-            self.sort_keys.push((self.value_index, bytes))
         }
 
         self.value_index += 1;
@@ -74,6 +75,11 @@ impl HistogramBuilder {
         Ok(())
     }
 
+    /// Number of the values appended besides the nulls.
+    pub fn values_len(&self) -> usize {
+        self.values.len()
+    }
+
     pub fn build(
         self,
         number_of_buckets: usize,
@@ -95,6 +101,22 @@ impl HistogramBuilder {
         } = self;
         let mut buckets = Vec::with_capacity(number_of_buckets);
         let values_len = values.len();
+
+        if number_of_buckets == 0 {
+            sketch.add(&DataValue::Null, null_count);
+
+            return Ok((
+                Histogram {
+                    index_id,
+                    number_of_distinct_value: 0,
+                    null_count,
+                    values_len,
+                    buckets,
+                    correlation: 0.0,
+                },
+                sketch,
+            ));
+        }
         let bucket_len = if values_len % number_of_buckets == 0 {
             values_len / number_of_buckets
         } else {
@@ -162,6 +184,19 @@ impl HistogramBuilder {
     }
 }
 
+/// Truncates the tuple `value` to the length of the prefix tuple `target`, so that a bound on the
+/// leading columns of a composite index covers every key it prefixes.
+fn prefix_of(value: &ValueRef, target: &ValueRef) -> ValueRef {
+    match (value.as_ref(), target.as_ref()) {
+        (DataValue::Tuple(Some(values)), DataValue::Tuple(Some(targets)))
+            if values.len() > targets.len() =>
+        {
+            Arc::new(DataValue::Tuple(Some(values[..targets.len()].to_vec())))
+        }
+        _ => value.clone(),
+    }
+}
+
 fn is_under(
     value: &ValueRef,
     target: &Bound<ValueRef>,
@@ -176,7 +211,7 @@ fn is_under(
                 BinaryOperator::LtEq
             },
         )?;
-        let value = evaluator.0.binary_eval(value, target);
+        let value = evaluator.0.binary_eval(&prefix_of(value, target), target);
         Ok::<bool, DatabaseError>(matches!(value, DataValue::Boolean(Some(true))))
     };
 
@@ -201,7 +236,7 @@ fn is_above(
                 BinaryOperator::Gt
             },
         )?;
-        let value = evaluator.0.binary_eval(value, target);
+        let value = evaluator.0.binary_eval(&prefix_of(value, target), target);
         Ok::<bool, DatabaseError>(matches!(value, DataValue::Boolean(Some(true))))
     };
     Ok(match target {
@@ -220,6 +255,70 @@ impl Histogram {
         self.values_len
     }
 
+    pub fn number_of_distinct_value(&self) -> usize {
+        self.number_of_distinct_value
+    }
+
+    /// Counts a value added to the index into the bucket covering it, the first or the last
+    /// bucket is widened for the values out of the histogram, so the buckets are no longer of
+    /// equal depth until the next `ANALYZE`.
+    pub fn insert(&mut self, value: &ValueRef, is_distinct: bool) {
+        if value.is_null() {
+            self.null_count += 1;
+            return;
+        }
+        self.values_len += 1;
+        if is_distinct {
+            self.number_of_distinct_value += 1;
+        }
+        if self.buckets.is_empty() {
+            self.buckets.push(Bucket {
+                lower: value.clone(),
+                upper: value.clone(),
+                count: 1,
+            });
+            return;
+        }
+        let bucket_i = self.bucket_of(value);
+        let bucket = &mut self.buckets[bucket_i];
+
+        if value < &bucket.lower {
+            bucket.lower = value.clone();
+        }
+        if value > &bucket.upper {
+            bucket.upper = value.clone();
+        }
+        bucket.count += 1;
+    }
+
+    /// Takes a value removed from the index off the bucket covering it, the bounds of the
+    /// buckets are kept.
+    pub fn delete(&mut self, value: &ValueRef, is_last: bool) {
+        if value.is_null() {
+            self.null_count = self.null_count.saturating_sub(1);
+            return;
+        }
+        if self.buckets.is_empty() {
+            return;
+        }
+        self.values_len = self.values_len.saturating_sub(1);
+        if is_last {
+            // the estimations divide by it
+            self.number_of_distinct_value = self.number_of_distinct_value.saturating_sub(1).max(1);
+        }
+        let bucket_i = self.bucket_of(value);
+        let bucket = &mut self.buckets[bucket_i];
+
+        bucket.count = bucket.count.saturating_sub(1);
+    }
+
+    /// The first bucket whose upper bound is not below the value, or the last bucket.
+    fn bucket_of(&self, value: &ValueRef) -> usize {
+        self.buckets
+            .partition_point(|bucket| &bucket.upper < value)
+            .min(self.buckets.len() - 1)
+    }
+
     pub fn collect_count(
         &self,
         ranges: &[Range],
@@ -228,6 +327,19 @@ impl Histogram {
         if self.buckets.is_empty() || ranges.is_empty() {
             return Ok(0);
         }
+        // the sketch only counts whole keys, so an equal prefix of a composite index is
+        // estimated as the scope of the keys it prefixes
+        let ranges = ranges
+            .iter()
+            .map(|range| match range {
+                Range::Eq(value) if self.is_prefix(value) => Range::Scope {
+                    min: Bound::Included(value.clone()),
+                    max: Bound::Included(value.clone()),
+                },
+                range => range.clone(),
+            })
+            .collect_vec();
+        let ranges = ranges.as_slice();
 
         let mut count = 0;
         let mut binary_i = 0;
@@ -255,6 +367,15 @@ impl Histogram {
             + count)
     }
 
+    fn is_prefix(&self, value: &DataValue) -> bool {
+        match (value, self.buckets[0].lower.as_ref()) {
+            (DataValue::Tuple(Some(values)), DataValue::Tuple(Some(keys))) => {
+                values.len() < keys.len()
+            }
+            _ => false,
+        }
+    }
+
     fn _collect_count(
         &self,
         ranges: &[Range],
@@ -338,6 +459,24 @@ impl Histogram {
             Ok::<f64, DatabaseError>(value)
         };
         let calc_fraction = |start: &DataValue, end: &DataValue, value: &DataValue| {
+            let (start, end, value) = match (start, end, value) {
+                // the leading columns before the first different one are the same in the bucket,
+                // so the fraction only depends on that column
+                (
+                    DataValue::Tuple(Some(starts)),
+                    DataValue::Tuple(Some(ends)),
+                    DataValue::Tuple(Some(values)),
+                ) => match starts
+                    .iter()
+                    .zip(ends.iter())
+                    .position(|(start, end)| start != end)
+                    .and_then(|i| Some((&starts[i], &ends[i], values.get(i)?)))
+                {
+                    Some((start, end, value)) => (start.as_ref(), end.as_ref(), value.as_ref()),
+                    None => (start, end, value),
+                },
+                _ => (start, end, value),
+            };
             let prefix_len = start.common_prefix_length(end).unwrap_or(0);
             Ok::<f64, DatabaseError>(
                 (float_value(value, prefix_len)? - float_value(start, prefix_len)?)
@@ -353,7 +492,7 @@ impl Histogram {
                 let mut temp_count = 0;
 
                 let is_eq = |value: &ValueRef, target: &Bound<ValueRef>| match target {
-                    Bound::Included(target) => target.eq(value),
+                    Bound::Included(target) => target.eq(&prefix_of(value, target)),
                     _ => false,
                 };
 
@@ -472,6 +611,9 @@ mod tests {
         IndexMeta {
             id: 0,
             column_ids: vec![0],
+            exprs: None,
+            include_column_ids: vec![],
+            predicate: None,
             table_name: Arc::new("t1".to_string()),
             pk_ty: LogicalType::Integer,
             name: "pk_c1".to_string(),
@@ -810,4 +952,111 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_collect_count_on_composite_index() -> Result<(), DatabaseError> {
+        let tuple = |values: &[i32]| {
+            Arc::new(DataValue::Tuple(Some(
+                values
+                    .iter()
+                    .map(|value| Arc::new(DataValue::Int32(Some(*value))))
+                    .collect(),
+            )))
+        };
+        let mut builder = HistogramBuilder::new(&index_meta(), Some(100))?;
+
+        for a in 0..4 {
+            for b in 0..25 {
+                builder.append(&tuple(&[a, b]))?;
+            }
+        }
+        let (histogram, sketch) = builder.build(10)?;
+
+        // a = 1
+        let count_1 = histogram.collect_count(&[Range::Eq(tuple(&[1]))], &sketch)?;
+
+        debug_assert_eq!(count_1, 20);
+
+        // a = 1 AND b BETWEEN 5 AND 14
+        let count_2 = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(tuple(&[1, 5])),
+                max: Bound::Included(tuple(&[1, 14])),
+            }],
+            &sketch,
+        )?;
+
+        debug_assert_eq!(count_2, 10);
+
+        // a = 1 AND b >= 10
+        let count_3 = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(tuple(&[1, 10])),
+                max: Bound::Included(tuple(&[1])),
+            }],
+            &sketch,
+        )?;
+
+        debug_assert_eq!(count_3, 15);
+
+        // a = 1 AND b >= 21
+        let count_4 = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(tuple(&[1, 21])),
+                max: Bound::Included(tuple(&[1])),
+            }],
+            &sketch,
+        )?;
+
+        debug_assert_eq!(count_4, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_incremental_histogram() -> Result<(), DatabaseError> {
+        let value = |i: i32| Arc::new(DataValue::Int32(Some(i)));
+        let mut builder = HistogramBuilder::new(&index_meta(), Some(10))?;
+
+        for i in 0..10 {
+            builder.append(&value(i))?;
+        }
+        let (mut histogram, _) = builder.build(2)?;
+
+        histogram.insert(&value(20), true);
+        histogram.insert(&value(-1), true);
+        histogram.insert(&value(3), false);
+        histogram.insert(&Arc::new(DataValue::Int32(None)), false);
+
+        debug_assert_eq!(histogram.values_len, 13);
+        debug_assert_eq!(histogram.null_count, 1);
+        debug_assert_eq!(histogram.number_of_distinct_value, 12);
+        debug_assert_eq!(histogram.buckets[0].lower, value(-1));
+        debug_assert_eq!(histogram.buckets[0].count, 7);
+        debug_assert_eq!(histogram.buckets[1].upper, value(20));
+        debug_assert_eq!(histogram.buckets[1].count, 6);
+
+        histogram.delete(&value(7), true);
+        histogram.delete(&Arc::new(DataValue::Int32(None)), false);
+
+        debug_assert_eq!(histogram.values_len, 12);
+        debug_assert_eq!(histogram.null_count, 0);
+        debug_assert_eq!(histogram.number_of_distinct_value, 11);
+        debug_assert_eq!(histogram.buckets[1].count, 5);
+
+        // a table without values is analyzed into an empty histogram
+        let mut builder = HistogramBuilder::new(&index_meta(), None)?;
+        builder.append(&Arc::new(DataValue::Int32(None)))?;
+        let (mut histogram, sketch) = builder.build(0)?;
+
+        debug_assert!(histogram.buckets.is_empty());
+        debug_assert_eq!(histogram.null_count, 1);
+        debug_assert_eq!(sketch.estimate(&DataValue::Null), 1);
+
+        histogram.insert(&value(1), true);
+        debug_assert_eq!(histogram.buckets.len(), 1);
+        debug_assert_eq!(histogram.buckets[0].count, 1);
+
+        Ok(())
+    }
 }
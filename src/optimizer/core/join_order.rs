@@ -0,0 +1,833 @@
+//! Cost-based reordering of inner joins.
+//!
+//! A join region is a tree of inner joins whose leaves are table scans, possibly filtered or
+//! renamed by a table alias.
+//! The cardinality of every leaf and the selectivity of every join condition are estimated
+//! with the `StatisticsMeta` of the tables, then the order with the least intermediate rows is
+//! searched with dynamic programming for small regions and greedily for large ones.
+//! The smaller input of every join is placed on the left, which is the build side of `HashJoin`.
+
+use crate::catalog::{ColumnRef, TableName};
+use crate::errors::DatabaseError;
+use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
+use crate::optimizer::core::cost_model::DEFAULT_SELECTIVITY;
+use crate::optimizer::core::hint::OptimizerHints;
+use crate::optimizer::core::memo::PhysicalProperties;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::optimizer::heuristic::batch::HepMatchOrder;
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::pushdown_predicates::split_conjunctive_predicates;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::storage::Transaction;
+use crate::types::index::IndexType;
+use crate::types::{ColumnId, LogicalType};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Regions with more leaves than this are ordered greedily.
+const DP_LEAVES_LIMIT: usize = 10;
+
+/// Set of leaves, one bit per leaf.
+type LeafSet = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum JoinTree {
+    Leaf(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+impl JoinTree {
+    fn leaves(&self) -> LeafSet {
+        match self {
+            JoinTree::Leaf(i) => 1 << i,
+            JoinTree::Join(left, right) => left.leaves() | right.leaves(),
+        }
+    }
+}
+
+/// A table scan, possibly filtered or renamed by a table alias, estimated by the statistics of
+/// the table.
+pub(crate) struct Leaf {
+    node_id: HepNodeId,
+    table_name: TableName,
+    pub(crate) columns: Vec<ColumnRef>,
+    pub(crate) rows: f64,
+    distinct_values: HashMap<ColumnId, f64>,
+}
+
+impl Leaf {
+    /// Only table scans and the filters on them can be estimated.
+    pub(crate) fn estimate<T: Transaction>(
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        loader: &StatisticMetaLoader<'_, T>,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let Some((scan_op, is_filtered, columns)) = Self::scan(graph, node_id) else {
+            return Ok(None);
+        };
+        let mut table_rows = None;
+        let mut filtered_rows: Option<usize> = None;
+        let mut distinct_values = HashMap::new();
+
+        for index_info in scan_op.index_infos.iter() {
+            let Some(statistics_meta) = loader.load(&scan_op.table_name, index_info.meta.id)?
+            else {
+                continue;
+            };
+            let histogram = statistics_meta.histogram();
+
+            if index_info.meta.ty == IndexType::PrimaryKey {
+                table_rows = Some(histogram.values_len());
+            }
+            if let ([column_id], None) =
+                (&index_info.meta.column_ids[..], &index_info.meta.predicate)
+            {
+                distinct_values.insert(*column_id, histogram.number_of_distinct_value() as f64);
+            }
+            // the ranges are detached from the filters on the scan
+            if let Some(range) = &index_info.range {
+                let rows = statistics_meta.collect_count(range)?;
+
+                filtered_rows = Some(filtered_rows.map_or(rows, |filtered| filtered.min(rows)));
+            }
+        }
+        let Some(table_rows) = table_rows else {
+            return Ok(None);
+        };
+        let rows = match (is_filtered, filtered_rows) {
+            (false, _) => table_rows as f64,
+            (true, Some(rows)) => rows as f64,
+            (true, None) => table_rows as f64 * DEFAULT_SELECTIVITY,
+        };
+
+        Ok(Some(Leaf {
+            node_id,
+            table_name: scan_op.table_name.clone(),
+            columns,
+            rows: rows.max(1.0),
+            distinct_values,
+        }))
+    }
+
+    /// A leaf of a table without statistics, which can only be ordered by the hints.
+    fn unestimated(graph: &HepGraph, node_id: HepNodeId) -> Option<Self> {
+        let (scan_op, _, columns) = Self::scan(graph, node_id)?;
+
+        Some(Leaf {
+            node_id,
+            table_name: scan_op.table_name.clone(),
+            columns,
+            rows: 1.0,
+            distinct_values: HashMap::new(),
+        })
+    }
+
+    /// Returns the table scan below `node_id`, whether it is filtered, and the columns output
+    /// by `node_id`.
+    fn scan(
+        graph: &HepGraph,
+        node_id: HepNodeId,
+    ) -> Option<(&TableScanOperator, bool, Vec<ColumnRef>)> {
+        let mut scan_id = node_id;
+        let mut is_filtered = false;
+        // the columns output by the topmost renaming projection
+        let mut alias_columns = None;
+
+        let scan_op = loop {
+            match graph.operator(scan_id) {
+                Operator::Filter(_) => is_filtered = true,
+                Operator::Project(project_op) => {
+                    let columns = project_op
+                        .exprs
+                        .iter()
+                        .map(Self::renamed_column)
+                        .collect::<Option<Vec<_>>>()?;
+                    alias_columns.get_or_insert(columns);
+                }
+                Operator::TableScan(scan_op) => break scan_op,
+                _ => return None,
+            }
+            scan_id = graph.eldest_child_at(scan_id)?;
+        };
+        let columns = alias_columns.unwrap_or_else(|| {
+            scan_op
+                .columns
+                .iter()
+                .map(|(_, column)| column.clone())
+                .collect()
+        });
+
+        Some((scan_op, is_filtered, columns))
+    }
+
+    /// The output column of a column or a column renamed by a table alias, which keeps the id
+    /// of the column in the table.
+    fn renamed_column(expr: &ScalarExpression) -> Option<ColumnRef> {
+        match expr {
+            ScalarExpression::ColumnRef(column) => Some(column.clone()),
+            ScalarExpression::Alias {
+                expr,
+                alias: AliasType::Expr(alias_expr),
+            } if matches!(
+                (expr.as_ref(), alias_expr.as_ref()),
+                (
+                    ScalarExpression::ColumnRef(_),
+                    ScalarExpression::ColumnRef(_)
+                )
+            ) =>
+            {
+                Some(alias_expr.output_column())
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn distinct_values(&self, column: &ColumnRef) -> f64 {
+        column
+            .id()
+            .and_then(|column_id| self.distinct_values.get(&column_id))
+            .map_or(self.rows, |distinct_values| distinct_values.min(self.rows))
+            .max(1.0)
+    }
+}
+
+struct EquiCondition {
+    left_expr: ScalarExpression,
+    right_expr: ScalarExpression,
+    left_leaves: LeafSet,
+    right_leaves: LeafSet,
+    selectivity: f64,
+}
+
+struct FilterCondition {
+    expr: ScalarExpression,
+    leaves: LeafSet,
+}
+
+struct JoinRegion {
+    root: HepNodeId,
+    joins: Vec<HepNodeId>,
+    leaves: Vec<Leaf>,
+    on: Vec<EquiCondition>,
+    filters: Vec<FilterCondition>,
+    tree: JoinTree,
+    // whether all the leaves have statistics
+    is_estimated: bool,
+}
+
+/// Reorders every region of inner joins in the graph whose tables all have statistics, or
+/// whose tables are hinted to be joined first by `LEADING`.
+pub(crate) fn reorder_joins<T: Transaction>(
+    graph: &mut HepGraph,
+    loader: &StatisticMetaLoader<'_, T>,
+    hints: &OptimizerHints,
+) -> Result<(), DatabaseError> {
+    let roots = graph
+        .nodes_iter(HepMatchOrder::TopDown, None)
+        .filter(|node_id| {
+            is_inner_join(graph, *node_id)
+                && !graph
+                    .parent_id(*node_id)
+                    .is_some_and(|parent_id| is_inner_join(graph, parent_id))
+        })
+        .collect_vec();
+
+    for root in roots {
+        if let Some(region) = JoinRegion::build(graph, root, loader, hints.leading())? {
+            // the hinted order is followed regardless of the cost
+            if let Some(leading_tree) = region.leading_tree(hints.leading()) {
+                region.rebuild(graph, &leading_tree);
+                continue;
+            }
+            if !region.is_estimated {
+                continue;
+            }
+            let best_tree = region.best_tree();
+
+            if region.cost(&best_tree).0 < region.cost(&region.tree).0 {
+                region.rebuild(graph, &best_tree);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn is_inner_join(graph: &HepGraph, node_id: HepNodeId) -> bool {
+    matches!(
+        graph.operator(node_id),
+        Operator::Join(JoinOperator {
+            join_type: JoinType::Inner,
+            on: JoinCondition::On { .. },
+        })
+    )
+}
+
+impl JoinRegion {
+    fn build<T: Transaction>(
+        graph: &HepGraph,
+        root: HepNodeId,
+        loader: &StatisticMetaLoader<'_, T>,
+        leading: &[String],
+    ) -> Result<Option<Self>, DatabaseError> {
+        let mut joins = Vec::new();
+        let mut leaf_ids = Vec::new();
+        let mut on = Vec::new();
+        let mut filters = Vec::new();
+        let tree = Self::collect(
+            graph,
+            root,
+            &mut joins,
+            &mut leaf_ids,
+            &mut on,
+            &mut filters,
+        );
+
+        if leaf_ids.len() > LeafSet::BITS as usize {
+            return Ok(None);
+        }
+        let mut leaves = Vec::with_capacity(leaf_ids.len());
+        let mut is_estimated = true;
+        for node_id in leaf_ids {
+            let leaf = match Leaf::estimate(graph, node_id, loader)? {
+                Some(leaf) => leaf,
+                None if !leading.is_empty() => {
+                    is_estimated = false;
+                    let Some(leaf) = Leaf::unestimated(graph, node_id) else {
+                        return Ok(None);
+                    };
+                    leaf
+                }
+                None => return Ok(None),
+            };
+            leaves.push(leaf);
+        }
+        let leaves_of = |expr: &ScalarExpression| -> Option<LeafSet> {
+            expr.referenced_columns(true)
+                .iter()
+                .try_fold(0, |leaf_set, column| {
+                    leaves
+                        .iter()
+                        .positions(|leaf| {
+                            leaf.columns
+                                .iter()
+                                .any(|leaf_column| leaf_column.summary() == column.summary())
+                        })
+                        .exactly_one()
+                        .ok()
+                        .map(|i| leaf_set | (1 << i))
+                })
+        };
+
+        let mut equi_conditions = Vec::with_capacity(on.len());
+        for (left_expr, right_expr) in on {
+            let (Some(left_leaves), Some(right_leaves)) =
+                (leaves_of(&left_expr), leaves_of(&right_expr))
+            else {
+                return Ok(None);
+            };
+            let selectivity = match (
+                PhysicalProperties::column(&left_expr),
+                PhysicalProperties::column(&right_expr),
+            ) {
+                (Some(left_column), Some(right_column)) => {
+                    let left_leaf = &leaves[left_leaves.trailing_zeros() as usize];
+                    let right_leaf = &leaves[right_leaves.trailing_zeros() as usize];
+
+                    1.0 / left_leaf
+                        .distinct_values(left_column)
+                        .max(right_leaf.distinct_values(right_column))
+                }
+                _ => DEFAULT_SELECTIVITY,
+            };
+            equi_conditions.push(EquiCondition {
+                left_expr,
+                right_expr,
+                left_leaves,
+                right_leaves,
+                selectivity,
+            });
+        }
+        let mut filter_conditions = Vec::with_capacity(filters.len());
+        for expr in filters {
+            let Some(leaves) = leaves_of(&expr) else {
+                return Ok(None);
+            };
+            filter_conditions.push(FilterCondition { expr, leaves });
+        }
+
+        Ok(Some(JoinRegion {
+            root,
+            joins,
+            leaves,
+            on: equi_conditions,
+            filters: filter_conditions,
+            tree,
+            is_estimated,
+        }))
+    }
+
+    fn collect(
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        joins: &mut Vec<HepNodeId>,
+        leaves: &mut Vec<HepNodeId>,
+        on: &mut Vec<(ScalarExpression, ScalarExpression)>,
+        filters: &mut Vec<ScalarExpression>,
+    ) -> JoinTree {
+        if let Operator::Join(JoinOperator {
+            on:
+                JoinCondition::On {
+                    on: join_on,
+                    filter,
+                },
+            join_type: JoinType::Inner,
+        }) = graph.operator(node_id)
+        {
+            joins.push(node_id);
+            on.extend(join_on.iter().cloned());
+            if let Some(filter) = filter {
+                filters.extend(split_conjunctive_predicates(filter));
+            }
+            let children = graph.children_at(node_id).collect_vec();
+            let left = Self::collect(graph, children[0], joins, leaves, on, filters);
+            let right = Self::collect(graph, children[1], joins, leaves, on, filters);
+
+            JoinTree::Join(Box::new(left), Box::new(right))
+        } else {
+            leaves.push(node_id);
+            JoinTree::Leaf(leaves.len() - 1)
+        }
+    }
+
+    /// Estimated rows of joining the leaves with all the conditions among them.
+    fn rows(&self, leaves: LeafSet) -> f64 {
+        let mut rows = self
+            .leaves
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| leaves & (1 << i) != 0)
+            .map(|(_, leaf)| leaf.rows)
+            .product::<f64>();
+
+        for condition in self.on.iter() {
+            if (condition.left_leaves | condition.right_leaves) & !leaves == 0 {
+                rows *= condition.selectivity;
+            }
+        }
+        for condition in self.filters.iter() {
+            if condition.leaves & !leaves == 0 {
+                rows *= DEFAULT_SELECTIVITY;
+            }
+        }
+        rows.max(1.0)
+    }
+
+    /// Returns the cost and the output rows of the tree, the cost of a join is the rows that it
+    /// reads and outputs.
+    fn cost(&self, tree: &JoinTree) -> (f64, f64) {
+        match tree {
+            JoinTree::Leaf(i) => (0.0, self.leaves[*i].rows),
+            JoinTree::Join(left, right) => {
+                let (left_cost, left_rows) = self.cost(left);
+                let (right_cost, right_rows) = self.cost(right);
+                let rows = self.rows(tree.leaves());
+
+                (left_cost + right_cost + left_rows + right_rows + rows, rows)
+            }
+        }
+    }
+
+    fn best_tree(&self) -> JoinTree {
+        if self.leaves.len() <= DP_LEAVES_LIMIT {
+            self.dynamic_programming()
+        } else {
+            self.greedy()
+        }
+    }
+
+    /// Builds the join with the smaller input on the left.
+    fn join(&self, left: (JoinTree, f64), right: (JoinTree, f64)) -> JoinTree {
+        let ((left, left_rows), (right, right_rows)) = (left, right);
+
+        if right_rows < left_rows {
+            JoinTree::Join(Box::new(right), Box::new(left))
+        } else {
+            JoinTree::Join(Box::new(left), Box::new(right))
+        }
+    }
+
+    /// Finds the cheapest bushy tree of every set of leaves, from the smaller sets to the
+    /// larger ones.
+    fn dynamic_programming(&self) -> JoinTree {
+        let leaves_len = self.leaves.len();
+        let full_set: LeafSet = (1 << leaves_len) - 1;
+        let mut best: Vec<Option<(f64, JoinTree)>> = vec![None; 1 << leaves_len];
+
+        for i in 0..leaves_len {
+            best[1 << i] = Some((0.0, JoinTree::Leaf(i)));
+        }
+        for leaves in (1..=full_set).sorted_by_key(|leaves: &LeafSet| leaves.count_ones()) {
+            if leaves.count_ones() < 2 {
+                continue;
+            }
+            let rows = self.rows(leaves);
+            let mut best_join: Option<(f64, JoinTree)> = None;
+            let mut left_leaves = (leaves - 1) & leaves;
+
+            while left_leaves > 0 {
+                let right_leaves = leaves ^ left_leaves;
+
+                if left_leaves < right_leaves {
+                    if let (Some((left_cost, left_tree)), Some((right_cost, right_tree))) =
+                        (&best[left_leaves as usize], &best[right_leaves as usize])
+                    {
+                        let left_rows = self.rows(left_leaves);
+                        let right_rows = self.rows(right_leaves);
+                        let cost = left_cost + right_cost + left_rows + right_rows + rows;
+
+                        if best_join
+                            .as_ref()
+                            .map_or(true, |(best_cost, _)| cost < *best_cost)
+                        {
+                            let tree = self.join(
+                                (left_tree.clone(), left_rows),
+                                (right_tree.clone(), right_rows),
+                            );
+                            best_join = Some((cost, tree));
+                        }
+                    }
+                }
+                left_leaves = (left_leaves - 1) & leaves;
+            }
+            best[leaves as usize] = best_join;
+        }
+
+        best[full_set as usize]
+            .take()
+            .map(|(_, tree)| tree)
+            .unwrap_or_else(|| self.tree.clone())
+    }
+
+    /// Repeatedly joins the two trees with the least output rows, preferring the trees that
+    /// are connected by a condition.
+    fn greedy(&self) -> JoinTree {
+        let mut trees = (0..self.leaves.len())
+            .map(|i| (1 << i, JoinTree::Leaf(i)))
+            .collect_vec();
+
+        while trees.len() > 1 {
+            let Some((_, i, j)) = trees
+                .iter()
+                .enumerate()
+                .tuple_combinations()
+                .map(|((i, (left_leaves, _)), (j, (right_leaves, _)))| {
+                    let is_connected = self.is_connected(*left_leaves, *right_leaves);
+
+                    ((!is_connected, self.rows(left_leaves | right_leaves)), i, j)
+                })
+                .min_by(|(key_1, ..), (key_2, ..)| key_1.partial_cmp(key_2).unwrap())
+            else {
+                break;
+            };
+            let (right_leaves, right_tree) = trees.remove(j);
+            let (left_leaves, left_tree) = trees.remove(i);
+            let tree = self.join(
+                (left_tree, self.rows(left_leaves)),
+                (right_tree, self.rows(right_leaves)),
+            );
+
+            trees.push((left_leaves | right_leaves, tree));
+        }
+
+        trees
+            .pop()
+            .map(|(_, tree)| tree)
+            .unwrap_or_else(|| self.tree.clone())
+    }
+
+    /// Joins the leaves of the tables in `leading` first in order, then the other leaves one by
+    /// one as the greedy order does. Returns `None` if any of the tables is not in the region.
+    fn leading_tree(&self, leading: &[String]) -> Option<JoinTree> {
+        let mut joined: LeafSet = 0;
+        let mut tree: Option<JoinTree> = None;
+        let mut join_leaf = |joined: &mut LeafSet, i: usize| {
+            *joined |= 1 << i;
+            tree = Some(match tree.take() {
+                Some(tree) => JoinTree::Join(Box::new(tree), Box::new(JoinTree::Leaf(i))),
+                None => JoinTree::Leaf(i),
+            });
+        };
+
+        for table in leading {
+            let i = self.leaves.iter().enumerate().position(|(i, leaf)| {
+                joined & (1 << i) == 0 && leaf.table_name.as_str() == table
+            })?;
+            join_leaf(&mut joined, i);
+        }
+        loop {
+            let key = |i: &usize| {
+                (
+                    !self.is_connected(joined, 1 << i),
+                    self.rows(joined | (1 << i)),
+                )
+            };
+            let Some(i) = (0..self.leaves.len())
+                .filter(|i| joined & (1 << i) == 0)
+                .min_by(|i, j| key(i).partial_cmp(&key(j)).unwrap())
+            else {
+                break;
+            };
+            join_leaf(&mut joined, i);
+        }
+        tree
+    }
+
+    fn is_connected(&self, left_leaves: LeafSet, right_leaves: LeafSet) -> bool {
+        let leaves = left_leaves | right_leaves;
+        let is_across = |condition_leaves: LeafSet| {
+            condition_leaves & !leaves == 0
+                && condition_leaves & left_leaves != 0
+                && condition_leaves & right_leaves != 0
+        };
+
+        self.on
+            .iter()
+            .any(|condition| is_across(condition.left_leaves | condition.right_leaves))
+            || self
+                .filters
+                .iter()
+                .any(|condition| is_across(condition.leaves))
+    }
+
+    /// Rewrites the joins of the region into `tree`, every condition is placed on the lowest
+    /// join that has all the columns it references.
+    fn rebuild(&self, graph: &mut HepGraph, tree: &JoinTree) {
+        let mut join_ids = self
+            .joins
+            .iter()
+            .filter(|join_id| **join_id != self.root)
+            .cloned()
+            .collect_vec();
+        let mut is_placed = vec![false; self.on.len()];
+        let mut is_filter_placed = vec![false; self.filters.len()];
+
+        self.rebuild_node(
+            graph,
+            tree,
+            Some(self.root),
+            &mut join_ids,
+            &mut is_placed,
+            &mut is_filter_placed,
+        );
+    }
+
+    fn rebuild_node(
+        &self,
+        graph: &mut HepGraph,
+        tree: &JoinTree,
+        node_id: Option<HepNodeId>,
+        join_ids: &mut Vec<HepNodeId>,
+        is_placed: &mut [bool],
+        is_filter_placed: &mut [bool],
+    ) -> HepNodeId {
+        let JoinTree::Join(left, right) = tree else {
+            let JoinTree::Leaf(i) = tree else {
+                unreachable!()
+            };
+            return self.leaves[*i].node_id;
+        };
+        let node_id = node_id.unwrap_or_else(|| join_ids.pop().unwrap());
+        let left_id = self.rebuild_node(graph, left, None, join_ids, is_placed, is_filter_placed);
+        let right_id = self.rebuild_node(graph, right, None, join_ids, is_placed, is_filter_placed);
+        let (left_leaves, right_leaves) = (left.leaves(), right.leaves());
+        let leaves = left_leaves | right_leaves;
+
+        let mut on = Vec::new();
+        let mut filters = Vec::new();
+        for (condition, is_placed) in self.on.iter().zip(is_placed.iter_mut()) {
+            if *is_placed || (condition.left_leaves | condition.right_leaves) & !leaves != 0 {
+                continue;
+            }
+            *is_placed = true;
+
+            if condition.left_leaves & !left_leaves == 0
+                && condition.right_leaves & !right_leaves == 0
+            {
+                on.push((condition.left_expr.clone(), condition.right_expr.clone()));
+            } else if condition.left_leaves & !right_leaves == 0
+                && condition.right_leaves & !left_leaves == 0
+            {
+                on.push((condition.right_expr.clone(), condition.left_expr.clone()));
+            } else {
+                filters.push(ScalarExpression::Binary {
+                    op: BinaryOperator::Eq,
+                    left_expr: Box::new(condition.left_expr.clone()),
+                    right_expr: Box::new(condition.right_expr.clone()),
+                    evaluator: None,
+                    ty: LogicalType::Boolean,
+                });
+            }
+        }
+        for (condition, is_placed) in self.filters.iter().zip(is_filter_placed.iter_mut()) {
+            if *is_placed || condition.leaves & !leaves != 0 {
+                continue;
+            }
+            *is_placed = true;
+            filters.push(condition.expr.clone());
+        }
+        let filter = filters
+            .into_iter()
+            .reduce(|left_expr, right_expr| ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr: Box::new(left_expr),
+                right_expr: Box::new(right_expr),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            });
+
+        graph.replace_node(
+            node_id,
+            Operator::Join(JoinOperator {
+                on: JoinCondition::On { on, filter },
+                join_type: JoinType::Inner,
+            }),
+        );
+        graph.replace_children(node_id, &[left_id, right_id]);
+
+        node_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::{Binder, BinderContext};
+    use crate::db::{DataBaseBuilder, Database};
+    use crate::errors::DatabaseError;
+    use crate::optimizer::core::join_order::{JoinRegion, JoinTree};
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::graph::HepGraph;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::storage::rocksdb::{RocksStorage, RocksTransaction};
+    use crate::storage::{Storage, Transaction};
+    use itertools::Itertools;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const SQL: &str =
+        "select * from t1 join t2 on t1.b = t2.d join t3 on t2.c = t3.e where t3.f < 10";
+
+    fn build_tables(database: &Database<RocksStorage>) -> Result<(), DatabaseError> {
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("create table t2 (c int primary key, d int)")?;
+        let _ = database.run("create table t3 (e int primary key, f int)")?;
+        let _ = database.run("create index t1_b_index on t1 (b)")?;
+        let _ = database.run("create index t2_d_index on t2 (d)")?;
+        let _ = database.run("create index t3_f_index on t3 (f)")?;
+
+        for table in ["t1", "t2", "t3"] {
+            let _ = database.run(format!(
+                "insert into {table} values {}",
+                (0..300).map(|i| format!("({i}, {})", i % 30)).join(", ")
+            ))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reorder_joins() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        build_tables(&database)?;
+
+        // without statistics, the joins are kept in the written order
+        let (_, plan) = database.run(format!("explain {SQL}"))?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.find("t2.c = t3.e").unwrap() < plan.find("t1.b = t2.d").unwrap());
+        let (_, expected) = database.run(format!("{SQL} order by a, c, e"))?;
+
+        for table in ["t1", "t2", "t3"] {
+            let _ = database.run(format!("analyze table {table}"))?;
+        }
+        // `t1.b = t2.d` matches 10 rows per row, so `t2` is joined with the filtered `t3` first,
+        // and the smaller `t3` becomes the build side
+        let (_, plan) = database.run(format!("explain {SQL}"))?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(
+            plan.find("t2.d = t1.b").unwrap() < plan.find("t3.e = t2.c").unwrap(),
+            "{}",
+            plan
+        );
+        let (_, tuples) = database.run(format!("{SQL} order by a, c, e"))?;
+
+        debug_assert_eq!(
+            tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+            expected.into_iter().map(|tuple| tuple.values).collect_vec()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_greedy_join_order() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        build_tables(&database)?;
+        for table in ["t1", "t2", "t3"] {
+            let _ = database.run(format!("analyze table {table}"))?;
+        }
+
+        let transaction = database.storage.transaction()?;
+        let scala_functions = Default::default();
+        let table_functions = Default::default();
+        let mut binder = Binder::new(
+            BinderContext::new(
+                &database.table_cache,
+                &transaction,
+                &scala_functions,
+                &table_functions,
+                Arc::new(AtomicUsize::new(0)),
+            ),
+            None,
+        );
+        let stmt = crate::parser::parse_sql(SQL)?;
+        let plan = HepOptimizer::new(binder.bind(&stmt[0])?)
+            .batch(
+                "Predicate Pushdown".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::PushPredicateThroughJoin,
+                    NormalizationRuleImpl::PushPredicateIntoScan,
+                ],
+            )
+            .find_best::<RocksTransaction>(None)?;
+        let graph = HepGraph::new(plan);
+        let loader = transaction.meta_loader(&database.meta_cache);
+        let root = graph
+            .nodes_iter(
+                crate::optimizer::heuristic::batch::HepMatchOrder::TopDown,
+                None,
+            )
+            .find(|node_id| super::is_inner_join(&graph, *node_id))
+            .unwrap();
+        let region = JoinRegion::build(&graph, root, &loader, &[])?.unwrap();
+
+        // leaves: t1, t2, t3
+        let expected = JoinTree::Join(
+            Box::new(JoinTree::Join(
+                Box::new(JoinTree::Leaf(2)),
+                Box::new(JoinTree::Leaf(1)),
+            )),
+            Box::new(JoinTree::Leaf(0)),
+        );
+        debug_assert_eq!(region.dynamic_programming(), expected);
+        debug_assert_eq!(region.greedy(), expected);
+        debug_assert!(region.cost(&expected).0 < region.cost(&region.tree).0);
+
+        Ok(())
+    }
+}
@@ -1,32 +1,273 @@
+use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::cost_model::{Cost, Estimate};
+use crate::optimizer::core::hint::OptimizerHints;
 use crate::optimizer::core::pattern::PatternMatcher;
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::optimizer::heuristic::batch::HepMatchOrder;
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::optimizer::heuristic::matcher::HepMatcher;
+use crate::optimizer::rule::implementation::dql::join::nest_loop_join;
 use crate::optimizer::rule::implementation::ImplementationRuleImpl;
-use crate::planner::operator::PhysicalOption;
+use crate::planner::operator::join::{JoinOperator, JoinType};
+use crate::planner::operator::sort::{SortField, SortOperator};
+use crate::planner::operator::top_n::TopNOperator;
+use crate::planner::operator::{Operator, PhysicalOption};
 use crate::storage::Transaction;
+use crate::types::index::IndexMetaRef;
+use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct Expression {
     pub(crate) op: PhysicalOption,
-    pub(crate) cost: Option<usize>,
-    // TODO: output rows
+    /// Cost of the operator itself, without its children.
+    pub(crate) cost: Option<Cost>,
+    /// Estimated number of output rows.
+    pub(crate) rows: Option<usize>,
+}
+
+impl Expression {
+    pub fn new(op: PhysicalOption, cost: Option<Cost>, rows: Option<usize>) -> Self {
+        Expression { op, cost, rows }
+    }
+}
+
+/// Properties of the tuples produced by a plan node that its parent can rely on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhysicalProperties {
+    /// Columns the output is sorted on in ascending order, the most significant one first.
+    pub(crate) sort_columns: Vec<ColumnRef>,
+    /// Whether the order can be reversed by scanning the table backward, the operators between
+    /// must keep the tuples they read in place.
+    pub(crate) is_reversible: bool,
+    /// Estimated number of output rows.
+    pub(crate) rows: Option<usize>,
+    /// Whether the rows are narrowed by the ranges of an index scan or an index merge, which are
+    /// detached from the filter above it.
+    pub(crate) is_ranged: bool,
+    /// Accumulated cost of the plan node and its children.
+    pub(crate) cost: Option<Cost>,
+    /// Indexes that tuples can be looked up by, only a plain table scan has them.
+    pub(crate) lookup_indexes: Vec<IndexMetaRef>,
+    /// Whether the output is every tuple of the table, read by a table scan without a limit.
+    pub(crate) is_full_scan: bool,
+}
+
+impl PhysicalProperties {
+    /// Estimated number of output rows.
+    pub fn rows(&self) -> Option<usize> {
+        self.rows
+    }
+
+    fn derive(
+        op: &Operator,
+        expr: Option<&Expression>,
+        cost: Option<Cost>,
+        children: &[PhysicalProperties],
+    ) -> Self {
+        let sort_columns = match (op, expr.map(|expr| &expr.op)) {
+            (Operator::TableScan(scan_op), Some(physical_option)) => {
+                let column_ids = match physical_option {
+                    // the merged tuples are read in the order of the primary key
+                    PhysicalOption::SeqScan | PhysicalOption::IndexMerge(_) => {
+                        vec![scan_op.primary_key]
+                    }
+                    PhysicalOption::IndexScan(index_info) => index_info.meta.column_ids.clone(),
+                    _ => vec![],
+                };
+                column_ids
+                    .into_iter()
+                    .map_while(|column_id| {
+                        scan_op
+                            .columns
+                            .iter()
+                            .find(|(_, column)| column.id() == Some(column_id))
+                            .map(|(_, column)| column.clone())
+                    })
+                    .collect()
+            }
+            (Operator::Filter(_) | Operator::Limit(_), _) => children[0].sort_columns.clone(),
+            (Operator::Project(op), _) => children[0]
+                .sort_columns
+                .iter()
+                .take_while(|sort_column| {
+                    op.exprs.iter().any(|expr| {
+                        Self::column(expr)
+                            .is_some_and(|column| column.summary() == sort_column.summary())
+                    })
+                })
+                .cloned()
+                .collect(),
+            (
+                Operator::Sort(SortOperator { sort_fields, .. })
+                | Operator::TopN(TopNOperator { sort_fields, .. }),
+                _,
+            ) => sort_fields
+                .iter()
+                .map_while(|sort_field| {
+                    sort_field
+                        .asc
+                        .then(|| Self::column(&sort_field.expr).cloned())
+                        .flatten()
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let is_reversible = match op {
+            // reversing the scan changes the tuples kept by the limit
+            Operator::TableScan(scan_op) => {
+                scan_op.limit == (None, None)
+                    && matches!(
+                        expr.map(|expr| &expr.op),
+                        Some(
+                            PhysicalOption::SeqScan
+                                | PhysicalOption::IndexScan(_)
+                                | PhysicalOption::IndexMerge(_)
+                        )
+                    )
+            }
+            Operator::Filter(_) | Operator::Project(_) => children[0].is_reversible,
+            _ => false,
+        };
+        let is_ranged = matches!(
+            expr.map(|expr| &expr.op),
+            Some(PhysicalOption::IndexScan(_) | PhysicalOption::IndexMerge(_))
+        );
+        let is_full_scan =
+            matches!(op, Operator::TableScan(scan_op) if scan_op.limit == (None, None));
+        let lookup_indexes = match op {
+            // the partial indexes miss the tuples out of their predicates
+            Operator::TableScan(scan_op) if scan_op.limit == (None, None) => scan_op
+                .index_infos
+                .iter()
+                .filter(|index_info| index_info.meta.predicate.is_none())
+                .map(|index_info| index_info.meta.clone())
+                .collect(),
+            _ => vec![],
+        };
+
+        PhysicalProperties {
+            sort_columns,
+            is_reversible,
+            rows: expr.and_then(|expr| expr.rows),
+            is_ranged,
+            cost,
+            lookup_indexes,
+            is_full_scan,
+        }
+    }
+
+    /// Whether the output is already in the order of the sort fields, so that sorting it again is
+    /// redundant. The fields must be in the same direction, a descending one is read by
+    /// reversing the scan, and the nulls of a nullable column must be where the index keeps
+    /// them: in front of the ascending order.
+    pub(crate) fn is_sorted_by(&self, sort_fields: &[SortField]) -> bool {
+        let Some(asc) = sort_fields.first().map(|sort_field| sort_field.asc) else {
+            return false;
+        };
+
+        (asc || self.is_reversible)
+            && sort_fields.len() <= self.sort_columns.len()
+            && sort_fields
+                .iter()
+                .zip(self.sort_columns.iter())
+                .all(|(sort_field, sort_column)| {
+                    sort_field.asc == asc
+                        && (!sort_column.nullable || sort_field.nulls_first == asc)
+                        && Self::column(&sort_field.expr)
+                            .is_some_and(|column| column.summary() == sort_column.summary())
+                })
+    }
+
+    /// Returns the column if the expression outputs it without any computation.
+    pub(crate) fn column(expr: &ScalarExpression) -> Option<&ColumnRef> {
+        match expr {
+            ScalarExpression::ColumnRef(column) => Some(column),
+            ScalarExpression::Reference { expr, .. } => Self::column(expr),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct GroupExpression {
     exprs: Vec<Expression>,
+    /// Properties of the children in the order of `HepGraph::children_at`.
+    children_properties: Vec<PhysicalProperties>,
+    properties: PhysicalProperties,
 }
 
 impl GroupExpression {
-    pub(crate) fn append_expr(&mut self, expr: Expression) {
+    pub fn append_expr(&mut self, expr: Expression) {
         self.exprs.push(expr);
     }
+
+    pub fn children_properties(&self) -> &[PhysicalProperties] {
+        &self.children_properties
+    }
+
+    /// The cost of the expression and the children it reads, an index nested loop join probes
+    /// the inner table instead of reading it, and an index seek or a row count reads nothing of
+    /// the table scan below.
+    fn accumulated_cost(&self, expr: &Expression) -> Option<Cost> {
+        let children_properties = match expr.op {
+            PhysicalOption::IndexNestLoopJoin(_) => &self.children_properties[..1],
+            PhysicalOption::IndexSeek(_) | PhysicalOption::RowCount => &[],
+            _ => &self.children_properties[..],
+        };
+
+        children_properties
+            .iter()
+            .try_fold(expr.cost?, |cost, properties| Some(cost + properties.cost?))
+    }
+
+    /// Keeps the expressions ranked first by the hints. The nested loop join is only listed for
+    /// the joins without equivalent conditions, so it is added for the hint.
+    fn follow_hints(
+        &mut self,
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        op: &Operator,
+        hints: &OptimizerHints,
+    ) {
+        if let Operator::Join(JoinOperator { on, join_type }) = op {
+            if *join_type != JoinType::Full
+                && hints.is_nest_loop_join(graph, node_id)
+                && !self
+                    .exprs
+                    .iter()
+                    .any(|expr| expr.op == PhysicalOption::NestLoopJoin)
+            {
+                self.exprs
+                    .push(nest_loop_join(on, join_type, &self.children_properties));
+            }
+        }
+        let ranks = self
+            .exprs
+            .iter()
+            .map(|expr| hints.rank(graph, node_id, &expr.op))
+            .collect_vec();
+
+        if let Some(min_rank) = ranks.iter().min() {
+            let mut ranks = ranks.iter();
+            self.exprs.retain(|_| ranks.next() == Some(min_rank));
+        }
+    }
+
+    fn cheapest(&self) -> Option<&Expression> {
+        self.exprs.iter().min_by(|expr_1, expr_2| {
+            match (self.accumulated_cost(expr_1), self.accumulated_cost(expr_2)) {
+                (Some(cost_1), Some(cost_2)) => cost_1.total().cmp(&cost_2.total()),
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (None, None) => Ordering::Equal,
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -39,6 +280,7 @@ impl Memo {
         graph: &HepGraph,
         loader: &StatisticMetaLoader<'_, T>,
         implementations: &[ImplementationRuleImpl],
+        hints: &OptimizerHints,
     ) -> Result<Self, DatabaseError> {
         let node_count = graph.node_count();
         let mut groups = HashMap::new();
@@ -48,34 +290,61 @@ impl Memo {
         }
 
         for node_id in graph.nodes_iter(HepMatchOrder::BottomUp, None) {
+            let op = graph.operator(node_id);
+            let children_properties = graph
+                .children_at(node_id)
+                .map(|child_id| {
+                    groups
+                        .get(&child_id)
+                        .map(|group_expr: &GroupExpression| group_expr.properties.clone())
+                        .unwrap_or_default()
+                })
+                .collect_vec();
+
             for rule in implementations {
                 if HepMatcher::new(rule.pattern(), node_id, graph).match_opt_expr() {
-                    let op = graph.operator(node_id);
-                    let group_expr = groups
-                        .entry(node_id)
-                        .or_insert_with(|| GroupExpression { exprs: vec![] });
+                    let group_expr = groups.entry(node_id).or_insert_with(|| GroupExpression {
+                        exprs: vec![],
+                        children_properties: children_properties.clone(),
+                        properties: PhysicalProperties::default(),
+                    });
 
                     rule.to_expression(op, loader, group_expr)?;
                 }
             }
+            if let Some(group_expr) = groups.get_mut(&node_id) {
+                if !hints.is_empty() {
+                    group_expr.follow_hints(graph, node_id, op, hints);
+                }
+                let cheapest = group_expr.cheapest();
+
+                group_expr.properties = PhysicalProperties::derive(
+                    op,
+                    cheapest,
+                    cheapest.and_then(|expr| group_expr.accumulated_cost(expr)),
+                    &group_expr.children_properties,
+                );
+            }
         }
 
         Ok(Memo { groups })
     }
 
     pub(crate) fn cheapest_physical_option(&self, node_id: &HepNodeId) -> Option<PhysicalOption> {
-        self.groups.get(node_id).and_then(|exprs| {
-            exprs
-                .exprs
-                .iter()
-                .min_by(|expr_1, expr_2| match (expr_1.cost, expr_2.cost) {
-                    (Some(cost_1), Some(cost_2)) => cost_1.cmp(&cost_2),
-                    (None, Some(_)) => Ordering::Greater,
-                    (Some(_), None) => Ordering::Less,
-                    (None, None) => Ordering::Equal,
-                })
-                .map(|expr| expr.op.clone())
-        })
+        self.groups
+            .get(node_id)
+            .and_then(|group_expr| group_expr.cheapest())
+            .map(|expr| expr.op.clone())
+    }
+
+    pub(crate) fn estimate(&self, node_id: &HepNodeId) -> Option<Estimate> {
+        self.groups
+            .get(node_id)
+            .map(|group_expr| Estimate {
+                rows: group_expr.properties.rows,
+                cost: group_expr.properties.cost,
+            })
+            .filter(|estimate| estimate.rows.is_some() || estimate.cost.is_some())
     }
 }
 
@@ -85,6 +354,8 @@ mod tests {
     use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
     use crate::expression::range_detacher::Range;
+    use crate::optimizer::core::cost_model::{Cost, Estimate};
+    use crate::optimizer::core::hint::OptimizerHints;
     use crate::optimizer::core::memo::Memo;
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::graph::HepGraph;
@@ -97,6 +368,7 @@ mod tests {
     use crate::types::index::{IndexInfo, IndexMeta, IndexType};
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
+    use itertools::Itertools;
     use petgraph::stable_graph::NodeIndex;
     use std::ops::Bound;
     use std::sync::atomic::AtomicUsize;
@@ -161,21 +433,38 @@ mod tests {
             &graph,
             &transaction.meta_loader(&database.meta_cache),
             &rules,
+            &OptimizerHints::default(),
         )?;
         let best_plan = graph.into_plan(Some(&memo));
         let exprs = &memo.groups.get(&NodeIndex::new(3)).unwrap();
 
         debug_assert_eq!(exprs.exprs.len(), 2);
-        debug_assert_eq!(exprs.exprs[0].cost, Some(1000));
+        debug_assert_eq!(exprs.exprs[0].rows, Some(1000));
+        debug_assert_eq!(exprs.exprs[0].cost, Some(Cost::new(1000, 2000)));
         debug_assert_eq!(exprs.exprs[0].op, PhysicalOption::SeqScan);
-        debug_assert!(exprs.exprs[1].cost.unwrap() >= 960);
+        let index_rows = exprs.exprs[1].rows.unwrap();
+        debug_assert!(index_rows >= 960);
+        debug_assert_eq!(
+            exprs.exprs[1].cost,
+            Some(Cost::new(index_rows, index_rows * 2))
+        );
         debug_assert!(matches!(exprs.exprs[1].op, PhysicalOption::IndexScan(_)));
+        debug_assert_eq!(
+            best_plan.as_ref().unwrap().childrens[0].childrens[0].childrens[0].estimate,
+            Some(Estimate {
+                rows: Some(index_rows),
+                cost: Some(Cost::new(index_rows, index_rows * 2)),
+            })
+        );
         debug_assert_eq!(
             best_plan.as_ref().unwrap().childrens[0].childrens[0].childrens[0].physical_option,
             Some(PhysicalOption::IndexScan(IndexInfo {
                 meta: Arc::new(IndexMeta {
                     id: 0,
                     column_ids: vec![0],
+                    exprs: None,
+                    include_column_ids: vec![],
+                    predicate: None,
                     table_name: Arc::new("t1".to_string()),
                     pk_ty: LogicalType::Integer,
                     name: "pk_c1".to_string(),
@@ -188,9 +477,208 @@ mod tests {
                         max: Bound::Unbounded,
                     }
                 ])),
+                covered: false,
             }))
         );
 
         Ok(())
     }
+
+    #[test]
+    fn test_eliminate_sort() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        database.run("create table t1 (c1 int primary key, c2 int null)")?;
+        database.run(format!(
+            "insert into t1 values {}",
+            (0..200)
+                .map(|i| if i % 50 == 0 {
+                    format!("({i}, null)")
+                } else {
+                    format!("({i}, {i})")
+                })
+                .join(", ")
+        ))?;
+        database.run("create index i_c2 on t1 (c2)")?;
+        database.run("analyze table t1")?;
+
+        for (sql, is_eliminated, is_reverse, expected) in [
+            (
+                "select c1 from t1 order by c1 desc limit 3",
+                true,
+                true,
+                vec![199, 198, 197],
+            ),
+            (
+                "select c1 from t1 where c1 < 10 order by c1 limit 3 offset 1",
+                true,
+                false,
+                vec![1, 2, 3],
+            ),
+            (
+                "select c2 from t1 where c2 > 195 order by c2 desc",
+                true,
+                true,
+                vec![199, 198, 197, 196],
+            ),
+            // the nulls are in front of the ascending index
+            (
+                "select c2 from t1 where c2 > 195 order by c2 asc",
+                false,
+                false,
+                vec![196, 197, 198, 199],
+            ),
+            // the limit keeps the tuples of the forward scan
+            (
+                "select c1 from (select c1 from t1 limit 3) order by c1 desc",
+                false,
+                false,
+                vec![2, 1, 0],
+            ),
+        ] {
+            let (_, plan) = database.run(format!("explain {sql}"))?;
+            let plan = format!("{:?}", plan);
+            debug_assert_eq!(!plan.contains("Sort By"), is_eliminated, "{sql}: {plan}");
+            debug_assert_eq!(plan.contains("Reverse"), is_reverse, "{sql}: {plan}");
+
+            let (_, tuples) = database.run(sql)?;
+            debug_assert_eq!(
+                tuples
+                    .iter()
+                    .map(|tuple| tuple.values[0].clone())
+                    .collect_vec(),
+                expected
+                    .into_iter()
+                    .map(|i| Arc::new(DataValue::Int32(Some(i))))
+                    .collect_vec(),
+                "{sql}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_index_and_count_rows() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        database.run("create table t1 (c1 int primary key, c2 int null)")?;
+        database.run(format!(
+            "insert into t1 values {}",
+            (0..200)
+                .map(|i| if i % 50 == 0 {
+                    format!("({i}, null)")
+                } else {
+                    format!("({i}, {i})")
+                })
+                .join(", ")
+        ))?;
+        database.run("create index i_c2 on t1 (c2)")?;
+        database.run("analyze table t1")?;
+
+        for (sql, option, expected) in [
+            ("select min(c2) from t1", "IndexSeek By i_c2", 1),
+            ("select max(c2) from t1", "IndexSeek By i_c2", 199),
+            ("select min(c1) from t1", "IndexSeek By pk_c1", 0),
+            ("select max(c1) from t1", "IndexSeek By pk_c1", 199),
+            ("select count(*) from t1", "RowCount", 200),
+            // the filter is between the aggregate and the table scan
+            (
+                "select max(c2) from t1 where c1 < 100",
+                "SimpleAggregate",
+                99,
+            ),
+            (
+                "select count(*) from t1 where c1 < 100",
+                "SimpleAggregate",
+                100,
+            ),
+            ("select count(c2) from t1", "SimpleAggregate", 196),
+            // the limit reads a part of the table
+            (
+                "select count(*) from (select * from t1 limit 10)",
+                "SimpleAggregate",
+                10,
+            ),
+        ] {
+            let (_, plan) = database.run(format!("explain {sql}"))?;
+            let plan = format!("{:?}", plan);
+            debug_assert!(plan.contains(option), "{sql}: {plan}");
+
+            let (_, tuples) = database.run(sql)?;
+            debug_assert_eq!(
+                tuples[0].values[0],
+                Arc::new(DataValue::Int32(Some(expected))),
+                "{sql}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_merge() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        database.run("create table t1 (c1 int primary key, c2 int, c3 int)")?;
+        database.run(format!(
+            "insert into t1 values {}",
+            (0..1000)
+                .map(|i| format!("({i}, {}, {})", i % 10, (i / 10) % 10))
+                .join(", ")
+        ))?;
+        database.run("create index i_c2 on t1 (c2)")?;
+        database.run("create index i_c3 on t1 (c3)")?;
+        database.run("analyze table t1")?;
+
+        for (sql, option, expected) in [
+            (
+                "select c1 from t1 where c2 = 3 or c3 = 5",
+                "IndexMerge Union [i_c2 => 3, i_c3 => 5]",
+                (0..1000)
+                    .filter(|i| i % 10 == 3 || (i / 10) % 10 == 5)
+                    .collect_vec(),
+            ),
+            (
+                "select c1 from t1 where c2 = 3 or c1 < 5 or c2 = 4",
+                "IndexMerge Union [i_c2 => 3, 4, pk_c1 => (-inf, 5)]",
+                (0..1000)
+                    .filter(|i| i % 10 == 3 || i % 10 == 4 || *i < 5)
+                    .collect_vec(),
+            ),
+            (
+                "select c1 from t1 where c2 = 3 and c3 = 5",
+                "IndexMerge Intersection [i_c2 => 3, i_c3 => 5]",
+                (0..10).map(|i| i * 100 + 53).collect_vec(),
+            ),
+            // a single index has the range of the disjunction
+            (
+                "select c1 from t1 where c2 = 3 or c2 = 4",
+                "IndexScan By i_c2",
+                (0..1000)
+                    .filter(|i| i % 10 == 3 || i % 10 == 4)
+                    .collect_vec(),
+            ),
+        ] {
+            let (_, plan) = database.run(format!("explain {sql}"))?;
+            let plan = format!("{:?}", plan);
+            debug_assert!(plan.contains(option), "{sql}: {plan}");
+
+            let (_, tuples) = database.run(sql)?;
+            debug_assert_eq!(
+                tuples
+                    .iter()
+                    .map(|tuple| tuple.values[0].clone())
+                    .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+                    .collect_vec(),
+                expected
+                    .into_iter()
+                    .map(|i| Arc::new(DataValue::Int32(Some(i))))
+                    .collect_vec(),
+                "{sql}"
+            );
+        }
+
+        Ok(())
+    }
 }
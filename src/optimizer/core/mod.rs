@@ -1,7 +1,12 @@
 pub(crate) mod cm_sketch;
+pub(crate) mod cost_model;
+pub(crate) mod eager_aggregation;
+pub(crate) mod hint;
 pub(crate) mod histogram;
+pub(crate) mod join_order;
 pub(crate) mod memo;
 pub(crate) mod opt_expr;
 pub(crate) mod pattern;
 pub(crate) mod rule;
 pub(crate) mod statistics_meta;
+pub(crate) mod statistics_tracker;
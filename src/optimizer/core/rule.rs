@@ -1,10 +1,11 @@
 use crate::errors::DatabaseError;
 use crate::optimizer::core::memo::GroupExpression;
 use crate::optimizer::core::pattern::Pattern;
-use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::optimizer::core::statistics_meta::{StatisticMetaLoader, StatisticsLoader};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::Operator;
 use crate::storage::Transaction;
+use std::fmt;
 
 // TODO: Use indexing and other methods for matching optimization to avoid traversal
 pub trait MatchPattern {
@@ -23,3 +24,26 @@ pub trait ImplementationRule<T: Transaction>: MatchPattern {
         group_expr: &mut GroupExpression,
     ) -> Result<(), DatabaseError>;
 }
+
+/// An [`ImplementationRule`] for the transactions of any storage, which the embedders register on
+/// [`OptimizerPipeline`](crate::optimizer::heuristic::pipeline::OptimizerPipeline).
+pub trait DynImplementationRule: MatchPattern + Send + Sync {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &dyn StatisticsLoader,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError>;
+}
+
+impl fmt::Debug for dyn NormalizationRule + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NormalizationRule")
+    }
+}
+
+impl fmt::Debug for dyn DynImplementationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DynImplementationRule")
+    }
+}
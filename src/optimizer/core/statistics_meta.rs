@@ -5,7 +5,7 @@ use crate::optimizer::core::cm_sketch::CountMinSketch;
 use crate::optimizer::core::histogram::Histogram;
 use crate::storage::{StatisticsMetaCache, Transaction};
 use crate::types::index::IndexId;
-use crate::types::value::DataValue;
+use crate::types::value::{DataValue, ValueRef};
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -44,7 +44,26 @@ impl<'a, T: Transaction> StatisticMetaLoader<'a, T> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Loads the statistics of the indexes for the rules that don't know the type of the transaction.
+pub trait StatisticsLoader {
+    fn load(
+        &self,
+        table_name: &TableName,
+        index_id: IndexId,
+    ) -> Result<Option<&StatisticsMeta>, DatabaseError>;
+}
+
+impl<T: Transaction> StatisticsLoader for StatisticMetaLoader<'_, T> {
+    fn load(
+        &self,
+        table_name: &TableName,
+        index_id: IndexId,
+    ) -> Result<Option<&StatisticsMeta>, DatabaseError> {
+        StatisticMetaLoader::load(self, table_name, index_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatisticsMeta {
     index_id: IndexId,
     histogram: Histogram,
@@ -66,6 +85,23 @@ impl StatisticsMeta {
         &self.histogram
     }
 
+    /// Maintains the statistics for a value added to the index, which are rebuilt by the next
+    /// `ANALYZE`. The sketch tells whether the value is a distinct one.
+    pub fn insert(&mut self, value: &ValueRef) {
+        let is_distinct = self.cm_sketch.estimate(value.as_ref()) == 0;
+
+        self.histogram.insert(value, is_distinct);
+        self.cm_sketch.increment(value.as_ref());
+    }
+
+    /// Maintains the statistics for a value removed from the index.
+    pub fn delete(&mut self, value: &ValueRef) {
+        self.cm_sketch.decrement(value.as_ref());
+        let is_last = self.cm_sketch.estimate(value.as_ref()) == 0;
+
+        self.histogram.delete(value, is_last);
+    }
+
     pub fn collect_count(&self, range: &Range) -> Result<usize, DatabaseError> {
         let mut count = 0;
 
@@ -119,6 +155,9 @@ mod tests {
         let index = IndexMeta {
             id: 0,
             column_ids: vec![0],
+            exprs: None,
+            include_column_ids: vec![],
+            predicate: None,
             table_name: Arc::new("t1".to_string()),
             pk_ty: LogicalType::Integer,
             name: "pk_c1".to_string(),
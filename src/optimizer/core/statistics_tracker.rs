@@ -0,0 +1,189 @@
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::storage::StatisticsMetaCache;
+use crate::types::index::IndexId;
+use crate::types::value::{DataValue, ValueRef};
+use itertools::Itertools;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The wait before analyzing again a table failed to be analyzed automatically, doubled by each
+/// failure in a row up to [`MAX_ANALYZE_BACKOFF`].
+const ANALYZE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_ANALYZE_BACKOFF: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default)]
+struct TableChanges {
+    /// Rows inserted, updated or deleted
+    modified_rows: usize,
+    /// The table was analyzed by the transaction, so the changes before are in its statistics
+    is_analyzed: bool,
+    /// The values added to the indexes, or removed from them with `false`, in their order
+    index_values: Vec<(IndexId, ValueRef, bool)>,
+}
+
+/// The rows modified by the DML executors of a transaction and the values they changed in
+/// the indexes, handed to the [`StatisticsTracker`] once the transaction commits.
+#[derive(Debug, Default)]
+pub struct StatisticsChanges {
+    tables: HashMap<TableName, TableChanges>,
+}
+
+impl StatisticsChanges {
+    pub(crate) fn modify_rows(&mut self, table_name: &TableName, rows: usize) {
+        self.tables
+            .entry(table_name.clone())
+            .or_default()
+            .modified_rows += rows;
+    }
+
+    /// Records the values of an index added for a tuple, or removed without `is_insert`. The
+    /// values of a composite index are kept as the tuple `ANALYZE` builds its histogram of.
+    pub(crate) fn change_index(
+        &mut self,
+        table_name: &TableName,
+        index_id: IndexId,
+        values: &[ValueRef],
+        is_insert: bool,
+    ) {
+        let value = if values.len() == 1 {
+            values[0].clone()
+        } else {
+            Arc::new(DataValue::Tuple(Some(values.to_vec())))
+        };
+
+        self.tables
+            .entry(table_name.clone())
+            .or_default()
+            .index_values
+            .push((index_id, value, is_insert));
+    }
+
+    /// The statistics of the table are rebuilt from the rows the transaction sees, which
+    /// include the changes recorded before.
+    pub(crate) fn analyze(&mut self, table_name: &TableName) {
+        self.tables.insert(
+            table_name.clone(),
+            TableChanges {
+                is_analyzed: true,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+    /// Rows inserted, updated or deleted since the last `ANALYZE`
+    modified_rows: usize,
+    /// Automatic analyzes failed in a row
+    failures: u32,
+    /// The table is not analyzed automatically before, after a failure
+    retry_at: Option<Instant>,
+}
+
+/// Counts the rows modified in each table since it was last analyzed, the table is analyzed
+/// again once they reach `ratio` of its rows.
+///
+/// Meanwhile the values changed in the indexes are applied to the cached statistics, so the
+/// estimations follow the changes. These statistics are not saved, the ones evicted from the
+/// cache are loaded from the last `ANALYZE` until the table is analyzed again.
+pub(crate) struct StatisticsTracker {
+    ratio: f64,
+    tables: Mutex<HashMap<TableName, TableState>>,
+}
+
+impl StatisticsTracker {
+    pub(crate) fn new(ratio: f64) -> Self {
+        StatisticsTracker {
+            ratio,
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Applies the changes of a committed transaction to the cached statistics, which are not
+    /// written back to the storage. Returns whether any of the cached statistics is updated.
+    pub(crate) fn commit(
+        &self,
+        changes: StatisticsChanges,
+        meta_cache: &StatisticsMetaCache,
+    ) -> bool {
+        // also serializes the updates of the cached statistics
+        let mut tables = self.tables.lock();
+        let mut is_updated = false;
+
+        for (table_name, changes) in changes.tables {
+            let state = tables.entry(table_name.clone()).or_default();
+
+            if changes.is_analyzed {
+                *state = TableState::default();
+            }
+            state.modified_rows += changes.modified_rows;
+
+            let index_values = changes
+                .index_values
+                .into_iter()
+                .map(|(index_id, value, is_insert)| (index_id, (value, is_insert)))
+                .into_group_map();
+            for (index_id, values) in index_values {
+                let key = (table_name.clone(), index_id);
+                let Some(mut statistics_meta) = meta_cache.get(&key).cloned() else {
+                    continue;
+                };
+
+                for (value, is_insert) in values {
+                    if is_insert {
+                        statistics_meta.insert(&value);
+                    } else {
+                        statistics_meta.delete(&value);
+                    }
+                }
+                meta_cache.put(key, statistics_meta);
+                is_updated = true;
+            }
+        }
+        is_updated
+    }
+
+    /// The tables whose modified rows reached the ratio of the rows counted by `row_count`,
+    /// except the ones waiting after a failed analyze.
+    pub(crate) fn stale_tables(
+        &self,
+        row_count: impl Fn(&str) -> Result<Option<usize>, DatabaseError>,
+    ) -> Result<Vec<TableName>, DatabaseError> {
+        let tables = self.tables.lock();
+        let now = Instant::now();
+        let mut stale_tables = Vec::new();
+
+        for (table_name, state) in tables.iter() {
+            if state.modified_rows == 0 || state.retry_at.is_some_and(|retry_at| retry_at > now) {
+                continue;
+            }
+            let table_rows = row_count(table_name.as_str())?.unwrap_or(0);
+
+            if state.modified_rows as f64 >= table_rows as f64 * self.ratio {
+                stale_tables.push(table_name.clone());
+            }
+        }
+        Ok(stale_tables)
+    }
+
+    /// Backs off analyzing the table automatically, for longer after each failure in a row.
+    pub(crate) fn analyze_failed(&self, table_name: &TableName) {
+        let mut tables = self.tables.lock();
+        let state = tables.entry(table_name.clone()).or_default();
+        let backoff = ANALYZE_BACKOFF
+            .saturating_mul(2_u32.saturating_pow(state.failures))
+            .min(MAX_ANALYZE_BACKOFF);
+
+        state.failures += 1;
+        state.retry_at = Some(Instant::now() + backoff);
+    }
+
+    /// Stops counting the dropped table.
+    pub(crate) fn forget(&self, table_name: &TableName) {
+        self.tables.lock().remove(table_name);
+    }
+}
@@ -1,7 +1,10 @@
+use crate::expression::agg::AggKind;
+use crate::expression::ScalarExpression;
 use crate::optimizer::core::memo::Memo;
 use crate::optimizer::core::opt_expr::OptExprNodeId;
 use crate::optimizer::heuristic::batch::HepMatchOrder;
-use crate::planner::operator::Operator;
+use crate::planner::operator::limit::LimitOperator;
+use crate::planner::operator::{Operator, PhysicalOption};
 use crate::planner::LogicalPlan;
 use itertools::Itertools;
 use petgraph::stable_graph::{NodeIndex, StableDiGraph};
@@ -95,6 +98,29 @@ impl HepGraph {
         self.version += 1;
     }
 
+    /// Replaces the children of the node, the order of `children_ids` is the order of children.
+    pub fn replace_children(&mut self, source_id: HepNodeId, children_ids: &[HepNodeId]) {
+        let edge_ids = self
+            .graph
+            .edges(source_id)
+            .map(|edge| edge.id())
+            .collect_vec();
+
+        for edge_id in edge_ids {
+            let _ = self.graph.remove_edge(edge_id);
+        }
+        for (order, children_id) in children_ids.iter().enumerate() {
+            if let Some(edge_id) = self
+                .parent_id(*children_id)
+                .and_then(|parent_id| self.graph.find_edge(parent_id, *children_id))
+            {
+                let _ = self.graph.remove_edge(edge_id);
+            }
+            let _ = self.graph.add_edge(source_id, *children_id, order);
+        }
+        self.version += 1;
+    }
+
     pub fn swap_node(&mut self, a: HepNodeId, b: HepNodeId) {
         let tmp = self.graph[a].clone();
 
@@ -196,6 +222,7 @@ impl HepGraph {
     fn build_childrens(&mut self, start: HepNodeId, memo: Option<&Memo>) -> Option<LogicalPlan> {
         let mut childrens = Vec::with_capacity(2);
         let physical_option = memo.and_then(|memo| memo.cheapest_physical_option(&start));
+        let estimate = memo.and_then(|memo| memo.estimate(&start));
 
         for child_id in self.children_at(start).collect_vec() {
             if let Some(child_plan) = self.build_childrens(child_id, memo) {
@@ -203,10 +230,46 @@ impl HepGraph {
             }
         }
 
-        self.graph.remove_node(start).map(|operator| LogicalPlan {
+        let mut operator = self.graph.remove_node(start)?;
+
+        match (&operator, &physical_option) {
+            // the input is already in order, a descending one is read by reversing its scan
+            (Operator::Sort(op), Some(PhysicalOption::Eliminated)) => {
+                let mut child = childrens.pop()?;
+
+                if !op.sort_fields[0].asc {
+                    child.reverse_scan();
+                }
+                return Some(child);
+            }
+            (Operator::TopN(op), Some(PhysicalOption::Limit)) => {
+                if !op.sort_fields[0].asc {
+                    childrens[0].reverse_scan();
+                }
+                operator = Operator::Limit(LimitOperator {
+                    offset: op.offset,
+                    limit: Some(op.limit),
+                });
+            }
+            // the input reads only the minimum or the maximum from the index
+            (Operator::Aggregate(op), Some(PhysicalOption::IndexSeek(index_meta))) => {
+                let is_max = matches!(
+                    op.agg_calls[0],
+                    ScalarExpression::AggCall {
+                        kind: AggKind::Max,
+                        ..
+                    }
+                );
+                childrens[0].seek_index(index_meta.clone(), is_max);
+            }
+            _ => (),
+        }
+
+        Some(LogicalPlan {
             operator,
             childrens,
             physical_option,
+            estimate,
             _output_schema_ref: None,
         })
     }
@@ -219,6 +282,7 @@ mod tests {
     use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
     use crate::planner::operator::Operator;
     use crate::planner::LogicalPlan;
+    use itertools::Itertools;
     use petgraph::stable_graph::{EdgeIndex, NodeIndex};
 
     #[test]
@@ -283,6 +347,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_graph_replace_children() -> Result<(), DatabaseError> {
+        let plan = select_sql_run("select * from t1 left join t2 on c1 = c3")?;
+        let mut graph = HepGraph::new(plan);
+
+        graph.replace_children(HepNodeId::new(1), &[HepNodeId::new(3), HepNodeId::new(2)]);
+
+        debug_assert_eq!(graph.graph.edge_count(), 3);
+        debug_assert_eq!(
+            graph.children_at(HepNodeId::new(1)).collect_vec(),
+            vec![HepNodeId::new(3), HepNodeId::new(2)]
+        );
+
+        graph.replace_children(HepNodeId::new(0), &[HepNodeId::new(2)]);
+
+        debug_assert_eq!(graph.graph.edge_count(), 2);
+        debug_assert_eq!(graph.parent_id(HepNodeId::new(2)), Some(HepNodeId::new(0)));
+        debug_assert_eq!(
+            graph.children_at(HepNodeId::new(1)).collect_vec(),
+            vec![HepNodeId::new(3)]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_graph_remove_middle_node_by_single() -> Result<(), DatabaseError> {
         let plan = select_sql_run("select * from t1 left join t2 on c1 = c3")?;
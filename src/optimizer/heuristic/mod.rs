@@ -2,3 +2,4 @@ pub(crate) mod batch;
 pub(crate) mod graph;
 pub(crate) mod matcher;
 pub mod optimizer;
+pub(crate) mod pipeline;
@@ -1,4 +1,7 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::eager_aggregation::push_aggregates;
+use crate::optimizer::core::hint::OptimizerHints;
+use crate::optimizer::core::join_order::reorder_joins;
 use crate::optimizer::core::memo::Memo;
 use crate::optimizer::core::pattern::PatternMatcher;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
@@ -12,10 +15,20 @@ use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use std::ops::Not;
 
+/// Rewrites of the graph chosen by the statistics, which run between the batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CostBasedRewrite {
+    JoinReorder,
+    EagerAggregation,
+}
+
 pub struct HepOptimizer {
     batches: Vec<HepBatch>,
     pub graph: HepGraph,
     implementations: Vec<ImplementationRuleImpl>,
+    hints: OptimizerHints,
+    // the rewrites with the index of the batch they run before
+    rewrites: Vec<(usize, CostBasedRewrite)>,
 }
 
 impl HepOptimizer {
@@ -24,6 +37,8 @@ impl HepOptimizer {
             batches: vec![],
             graph: HepGraph::new(root),
             implementations: vec![],
+            hints: OptimizerHints::default(),
+            rewrites: vec![],
         }
     }
 
@@ -37,16 +52,42 @@ impl HepOptimizer {
         self
     }
 
+    /// Reorders the inner joins by the statistics after the batches added so far.
+    pub fn join_reorder(mut self) -> Self {
+        self.rewrites
+            .push((self.batches.len(), CostBasedRewrite::JoinReorder));
+        self
+    }
+
+    /// Splits the aggregates over inner joins into partial aggregates below the joins and final
+    /// ones above them by the statistics after the batches added so far.
+    pub fn eager_aggregation(mut self) -> Self {
+        self.rewrites
+            .push((self.batches.len(), CostBasedRewrite::EagerAggregation));
+        self
+    }
+
     pub fn implementations(mut self, implementations: Vec<ImplementationRuleImpl>) -> Self {
         self.implementations = implementations;
         self
     }
 
+    /// Constrains the join order and the physical options by the hints of the statement.
+    pub fn hints(mut self, hints: OptimizerHints) -> Self {
+        self.hints = hints;
+        self
+    }
+
     pub fn find_best<T: Transaction>(
         mut self,
         loader: Option<&StatisticMetaLoader<'_, T>>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        for ref batch in self.batches {
+        let batches_len = self.batches.len();
+        // the aliases are resolved before the batches remove the projections of them
+        self.hints.resolve_aliases(&self.graph);
+
+        for (i, ref batch) in self.batches.into_iter().enumerate() {
+            Self::rewrite_at(&mut self.graph, loader, &self.rewrites, &self.hints, i)?;
             let mut batch_over = false;
             let mut iteration = 1usize;
 
@@ -58,12 +99,19 @@ impl HepOptimizer {
                 }
             }
         }
+        Self::rewrite_at(
+            &mut self.graph,
+            loader,
+            &self.rewrites,
+            &self.hints,
+            batches_len,
+        )?;
         let memo = loader
             .and_then(|loader| {
                 self.implementations
                     .is_empty()
                     .not()
-                    .then(|| Memo::new(&self.graph, loader, &self.implementations))
+                    .then(|| Memo::new(&self.graph, loader, &self.implementations, &self.hints))
             })
             .transpose()?;
 
@@ -72,6 +120,25 @@ impl HepOptimizer {
             .ok_or(DatabaseError::EmptyPlan)
     }
 
+    fn rewrite_at<T: Transaction>(
+        graph: &mut HepGraph,
+        loader: Option<&StatisticMetaLoader<'_, T>>,
+        rewrites: &[(usize, CostBasedRewrite)],
+        hints: &OptimizerHints,
+        batch_index: usize,
+    ) -> Result<(), DatabaseError> {
+        let Some(loader) = loader else {
+            return Ok(());
+        };
+        for (_, rewrite) in rewrites.iter().filter(|(index, _)| *index == batch_index) {
+            match rewrite {
+                CostBasedRewrite::JoinReorder => reorder_joins(graph, loader, hints)?,
+                CostBasedRewrite::EagerAggregation => push_aggregates(graph, loader)?,
+            }
+        }
+        Ok(())
+    }
+
     fn apply_batch(
         graph: &mut HepGraph,
         HepBatch {
@@ -0,0 +1,631 @@
+use crate::errors::DatabaseError;
+use crate::optimizer::core::rule::{DynImplementationRule, NormalizationRule};
+use crate::optimizer::heuristic::batch::{HepBatch, HepBatchStrategy};
+use crate::optimizer::heuristic::optimizer::HepOptimizer;
+use crate::optimizer::rule::implementation::ImplementationRuleImpl;
+use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+use crate::planner::LogicalPlan;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+pub const JOIN_REORDER: &str = "Join Reorder";
+pub const EAGER_AGGREGATION: &str = "Eager Aggregation";
+
+#[derive(Clone)]
+enum PipelineStep {
+    Batch(HepBatch),
+    JoinReorder,
+    EagerAggregation,
+}
+
+impl PipelineStep {
+    fn name(&self) -> &str {
+        match self {
+            PipelineStep::Batch(batch) => &batch.name,
+            PipelineStep::JoinReorder => JOIN_REORDER,
+            PipelineStep::EagerAggregation => EAGER_AGGREGATION,
+        }
+    }
+}
+
+/// The steps a [`HepOptimizer`] runs in order, which are the batches of normalization rules and
+/// the cost based rewrites between them, and the implementation rules of the physical options.
+///
+/// The steps are named to be removed or moved, the cost based rewrites by [`JOIN_REORDER`] and
+/// [`EAGER_AGGREGATION`], and the rules by [`NormalizationRuleImpl::name`] and
+/// [`ImplementationRuleImpl::name`] to be disabled. Editing a step or a rule that doesn't exist
+/// fails with [`DatabaseError::NotFound`], and adding one named as a step or a rule of the
+/// pipeline or of the default one fails with [`DatabaseError::DuplicateName`]. The last batch
+/// binds the evaluators of the expressions that the executors need, so it is kept last.
+#[derive(Clone)]
+pub struct OptimizerPipeline {
+    steps: Vec<PipelineStep>,
+    implementations: Vec<ImplementationRuleImpl>,
+    disabled_rules: HashSet<String>,
+}
+
+impl OptimizerPipeline {
+    /// A pipeline without any step or implementation rule.
+    pub fn empty() -> Self {
+        OptimizerPipeline {
+            steps: vec![],
+            implementations: vec![],
+            disabled_rules: HashSet::new(),
+        }
+    }
+
+    pub fn batch(
+        self,
+        name: String,
+        strategy: HepBatchStrategy,
+        rules: Vec<NormalizationRuleImpl>,
+    ) -> Result<Self, DatabaseError> {
+        self.check_batch(&name, &rules)?;
+
+        Ok(self.builtin_batch(name, strategy, rules))
+    }
+
+    fn builtin_batch(
+        mut self,
+        name: String,
+        strategy: HepBatchStrategy,
+        rules: Vec<NormalizationRuleImpl>,
+    ) -> Self {
+        self.steps
+            .push(PipelineStep::Batch(HepBatch::new(name, strategy, rules)));
+        self
+    }
+
+    /// Reorders the inner joins by the statistics after the steps added so far.
+    pub fn join_reorder(mut self) -> Self {
+        self.steps.push(PipelineStep::JoinReorder);
+        self
+    }
+
+    /// Splits the aggregates over inner joins by the statistics after the steps added so far.
+    pub fn eager_aggregation(mut self) -> Self {
+        self.steps.push(PipelineStep::EagerAggregation);
+        self
+    }
+
+    pub fn implementations(mut self, implementations: Vec<ImplementationRuleImpl>) -> Self {
+        self.implementations = implementations;
+        self
+    }
+
+    /// Inserts a batch before the step named `before`.
+    pub fn insert_batch_before(
+        mut self,
+        before: &str,
+        name: String,
+        strategy: HepBatchStrategy,
+        rules: Vec<NormalizationRuleImpl>,
+    ) -> Result<Self, DatabaseError> {
+        let index = self.position(before)?;
+        self.check_batch(&name, &rules)?;
+
+        self.steps.insert(
+            index,
+            PipelineStep::Batch(HepBatch::new(name, strategy, rules)),
+        );
+        Ok(self)
+    }
+
+    pub fn remove_step(mut self, name: &str) -> Result<Self, DatabaseError> {
+        let index = self.position(name)?;
+
+        self.steps.remove(index);
+        Ok(self)
+    }
+
+    /// Moves the step named `name` before the step named `before`.
+    pub fn move_step_before(mut self, name: &str, before: &str) -> Result<Self, DatabaseError> {
+        let index = self.position(name)?;
+        let step = self.steps.remove(index);
+        let before_index = self.position(before)?;
+        self.steps.insert(before_index, step);
+        Ok(self)
+    }
+
+    /// Appends a rule registered by the embedder to the batch named `batch`.
+    pub fn register_normalization_rule(
+        mut self,
+        batch: &str,
+        name: String,
+        rule: Arc<dyn NormalizationRule + Send + Sync>,
+    ) -> Result<Self, DatabaseError> {
+        let index = self.position(batch)?;
+        self.check_rule_name(&name)?;
+        let PipelineStep::Batch(batch) = &mut self.steps[index] else {
+            return Err(DatabaseError::NotFound(
+                "optimizer batch",
+                batch.to_string(),
+            ));
+        };
+        batch.rules.push(NormalizationRuleImpl::Custom(name, rule));
+        Ok(self)
+    }
+
+    /// Appends an implementation rule registered by the embedder, its physical options compete
+    /// with the builtin ones by their costs.
+    pub fn register_implementation_rule(
+        mut self,
+        name: String,
+        rule: Arc<dyn DynImplementationRule>,
+    ) -> Result<Self, DatabaseError> {
+        self.check_rule_name(&name)?;
+
+        self.implementations
+            .push(ImplementationRuleImpl::Custom(name, rule));
+        Ok(self)
+    }
+
+    /// Skips the normalization or implementation rule named `name` until it is enabled again.
+    pub fn disable_rule(mut self, name: &str) -> Result<Self, DatabaseError> {
+        if !self.rule_names().any(|rule_name| rule_name == name) {
+            return Err(DatabaseError::NotFound("optimizer rule", name.to_string()));
+        }
+        self.disabled_rules.insert(name.to_string());
+        Ok(self)
+    }
+
+    pub fn enable_rule(mut self, name: &str) -> Result<Self, DatabaseError> {
+        if !self.disabled_rules.remove(name) {
+            return Err(DatabaseError::NotFound(
+                "disabled optimizer rule",
+                name.to_string(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// The names of the steps in order.
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps.iter().map(PipelineStep::name).collect()
+    }
+
+    pub(crate) fn optimizer(&self, root: LogicalPlan) -> HepOptimizer {
+        let is_enabled = |name: String| !self.disabled_rules.contains(&name);
+        let mut optimizer = HepOptimizer::new(root);
+
+        for step in self.steps.iter() {
+            optimizer = match step {
+                PipelineStep::Batch(HepBatch {
+                    name,
+                    strategy,
+                    rules,
+                }) => {
+                    let rules = if self.disabled_rules.is_empty() {
+                        rules.clone()
+                    } else {
+                        rules
+                            .iter()
+                            .filter(|rule| is_enabled(rule.name()))
+                            .cloned()
+                            .collect()
+                    };
+                    optimizer.batch(name.clone(), strategy.clone(), rules)
+                }
+                PipelineStep::JoinReorder => optimizer.join_reorder(),
+                PipelineStep::EagerAggregation => optimizer.eager_aggregation(),
+            };
+        }
+        optimizer.implementations(
+            self.implementations
+                .iter()
+                .filter(|rule| self.disabled_rules.is_empty() || is_enabled(rule.name()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn rule_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.steps
+            .iter()
+            .flat_map(|step| match step {
+                PipelineStep::Batch(batch) => batch.rules.as_slice(),
+                _ => &[],
+            })
+            .map(NormalizationRuleImpl::name)
+            .chain(
+                self.implementations
+                    .iter()
+                    .map(ImplementationRuleImpl::name),
+            )
+    }
+
+    fn check_batch(
+        &self,
+        name: &str,
+        rules: &[NormalizationRuleImpl],
+    ) -> Result<(), DatabaseError> {
+        let is_duplicated =
+            |pipeline: &OptimizerPipeline| pipeline.steps.iter().any(|step| step.name() == name);
+
+        if is_duplicated(self) || is_duplicated(&OptimizerPipeline::default()) {
+            return Err(DatabaseError::DuplicateName(
+                "optimizer step",
+                name.to_string(),
+            ));
+        }
+        for rule in rules {
+            if let NormalizationRuleImpl::Custom(name, _) = rule {
+                self.check_rule_name(name)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_rule_name(&self, name: &str) -> Result<(), DatabaseError> {
+        let is_duplicated =
+            |pipeline: &OptimizerPipeline| pipeline.rule_names().any(|rule_name| rule_name == name);
+
+        if is_duplicated(self) || is_duplicated(&OptimizerPipeline::default()) {
+            return Err(DatabaseError::DuplicateName(
+                "optimizer rule",
+                name.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn position(&self, name: &str) -> Result<usize, DatabaseError> {
+        self.steps
+            .iter()
+            .position(|step| step.name() == name)
+            .ok_or_else(|| DatabaseError::NotFound("optimizer step", name.to_string()))
+    }
+}
+
+impl Default for OptimizerPipeline {
+    fn default() -> Self {
+        OptimizerPipeline::empty()
+            .builtin_batch(
+                "Decorrelation".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::DecorrelateJoin],
+            )
+            .builtin_batch(
+                "Column Pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::ColumnPruning],
+            )
+            .builtin_batch(
+                "Simplify Filter".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::SimplifyFilter,
+                    NormalizationRuleImpl::ConstantCalculation,
+                ],
+            )
+            .builtin_batch(
+                "Predicate Pushdown".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::SimplifyOuterJoin,
+                    NormalizationRuleImpl::InferPredicatesThroughJoin,
+                    NormalizationRuleImpl::PushPredicateThroughJoin,
+                    NormalizationRuleImpl::PushPredicateIntoScan,
+                ],
+            )
+            .builtin_batch(
+                "Limit Pushdown".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::LimitProjectTranspose,
+                    NormalizationRuleImpl::PushLimitThroughJoin,
+                    NormalizationRuleImpl::PushLimitIntoTableScan,
+                ],
+            )
+            .join_reorder()
+            .eager_aggregation()
+            .builtin_batch(
+                "Combine Operators".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::CollapseProject,
+                    NormalizationRuleImpl::CollapseGroupByAgg,
+                    NormalizationRuleImpl::CombineFilter,
+                    NormalizationRuleImpl::CombineSortLimit,
+                ],
+            )
+            .builtin_batch(
+                "Common Subexpression Elimination".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::EliminateCommonSubexpression],
+            )
+            .builtin_batch(
+                "Expression Remapper".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![
+                    NormalizationRuleImpl::ExpressionRemapper,
+                    // TIPS: This rule is necessary
+                    NormalizationRuleImpl::EvaluatorBind,
+                ],
+            )
+            .implementations(vec![
+                // DQL
+                ImplementationRuleImpl::SimpleAggregate,
+                ImplementationRuleImpl::GroupByAggregate,
+                ImplementationRuleImpl::Dummy,
+                ImplementationRuleImpl::Filter,
+                ImplementationRuleImpl::HashJoin,
+                ImplementationRuleImpl::Limit,
+                ImplementationRuleImpl::Projection,
+                ImplementationRuleImpl::SeqScan,
+                ImplementationRuleImpl::IndexScan,
+                ImplementationRuleImpl::IndexMerge,
+                ImplementationRuleImpl::FunctionScan,
+                ImplementationRuleImpl::Sort,
+                ImplementationRuleImpl::TopN,
+                ImplementationRuleImpl::Values,
+                // DML
+                ImplementationRuleImpl::Analyze,
+                ImplementationRuleImpl::CopyFromFile,
+                ImplementationRuleImpl::CopyToFile,
+                ImplementationRuleImpl::Delete,
+                ImplementationRuleImpl::Insert,
+                ImplementationRuleImpl::Update,
+                // DLL
+                ImplementationRuleImpl::AddColumn,
+                ImplementationRuleImpl::CreateTable,
+                ImplementationRuleImpl::DropColumn,
+                ImplementationRuleImpl::DropTable,
+                ImplementationRuleImpl::Truncate,
+            ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::{
+        Cost, DynImplementationRule, Expression, GroupExpression, HepBatchStrategy, HepGraph,
+        HepNodeId, MatchPattern, NormalizationRule, NormalizationRuleImpl, OptimizerPipeline,
+        Pattern, PatternChildrenPredicate, StatisticsLoader, EAGER_AGGREGATION, JOIN_REORDER,
+    };
+    use crate::planner::operator::{Operator, PhysicalOption};
+    use itertools::Itertools;
+    use lazy_static::lazy_static;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    lazy_static! {
+        static ref LIMIT_PATTERN: Pattern = {
+            Pattern {
+                predicate: |op| matches!(op, Operator::Limit(_)),
+                children: PatternChildrenPredicate::None,
+            }
+        };
+        static ref TABLE_SCAN_PATTERN: Pattern = {
+            Pattern {
+                predicate: |op| matches!(op, Operator::TableScan(_)),
+                children: PatternChildrenPredicate::None,
+            }
+        };
+    }
+
+    struct IgnoreLimit;
+
+    impl MatchPattern for IgnoreLimit {
+        fn pattern(&self) -> &Pattern {
+            &LIMIT_PATTERN
+        }
+    }
+
+    impl NormalizationRule for IgnoreLimit {
+        fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+            graph.remove_node(node_id, false);
+            Ok(())
+        }
+    }
+
+    struct FreeSeqScan;
+
+    impl MatchPattern for FreeSeqScan {
+        fn pattern(&self) -> &Pattern {
+            &TABLE_SCAN_PATTERN
+        }
+    }
+
+    impl DynImplementationRule for FreeSeqScan {
+        fn to_expression(
+            &self,
+            _: &Operator,
+            _: &dyn StatisticsLoader,
+            group_expr: &mut GroupExpression,
+        ) -> Result<(), DatabaseError> {
+            group_expr.append_expr(Expression::new(
+                PhysicalOption::SeqScan,
+                Some(Cost::new(0, 0)),
+                None,
+            ));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_edit_steps() -> Result<(), DatabaseError> {
+        let pipeline = OptimizerPipeline::default()
+            .remove_step(JOIN_REORDER)?
+            .move_step_before(EAGER_AGGREGATION, "Decorrelation")?
+            .insert_batch_before(
+                "Limit Pushdown",
+                "Ignore Limit".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![],
+            )?;
+
+        debug_assert_eq!(
+            pipeline.step_names(),
+            vec![
+                EAGER_AGGREGATION,
+                "Decorrelation",
+                "Column Pruning",
+                "Simplify Filter",
+                "Predicate Pushdown",
+                "Ignore Limit",
+                "Limit Pushdown",
+                "Combine Operators",
+                "Common Subexpression Elimination",
+                "Expression Remapper",
+            ]
+        );
+        debug_assert!(matches!(
+            pipeline.clone().remove_step(JOIN_REORDER),
+            Err(DatabaseError::NotFound(..))
+        ));
+        debug_assert!(matches!(
+            pipeline.clone().disable_rule("PushNothing"),
+            Err(DatabaseError::NotFound(..))
+        ));
+        debug_assert!(matches!(
+            pipeline.clone().enable_rule("CombineFilter"),
+            Err(DatabaseError::NotFound(..))
+        ));
+        debug_assert!(matches!(
+            pipeline.register_normalization_rule(
+                JOIN_REORDER,
+                "IgnoreLimit".to_string(),
+                Arc::new(IgnoreLimit),
+            ),
+            Err(DatabaseError::NotFound(..))
+        ));
+        debug_assert_eq!(
+            NormalizationRuleImpl::PushPredicateIntoScan.name(),
+            "PushPredicateIntoScan"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let pipeline = OptimizerPipeline::default()
+            .remove_step(JOIN_REORDER)?
+            .disable_rule("CombineFilter")?;
+        let database = DataBaseBuilder::path(temp_dir.path())
+            .optimizer_pipeline(pipeline)
+            .build()?;
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("insert into t1 values (0, 0), (1, 1)")?;
+        let (_, tuples) = database.run("select * from t1 where a > 0 and b > 0")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_names() -> Result<(), DatabaseError> {
+        let pipeline = OptimizerPipeline::default().remove_step("Column Pruning")?;
+
+        // the names of the builtin steps and rules are kept even when they are removed
+        debug_assert!(matches!(
+            pipeline.clone().batch(
+                "Column Pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![],
+            ),
+            Err(DatabaseError::DuplicateName("optimizer step", _))
+        ));
+        debug_assert!(matches!(
+            OptimizerPipeline::empty().batch(
+                JOIN_REORDER.to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![],
+            ),
+            Err(DatabaseError::DuplicateName("optimizer step", _))
+        ));
+        debug_assert!(matches!(
+            pipeline.clone().insert_batch_before(
+                "Limit Pushdown",
+                "ColumnPruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::Custom(
+                    "ColumnPruning".to_string(),
+                    Arc::new(IgnoreLimit)
+                )],
+            ),
+            Err(DatabaseError::DuplicateName("optimizer rule", _))
+        ));
+        debug_assert!(matches!(
+            pipeline.clone().register_normalization_rule(
+                "Limit Pushdown",
+                "SeqScan".to_string(),
+                Arc::new(IgnoreLimit),
+            ),
+            Err(DatabaseError::DuplicateName("optimizer rule", _))
+        ));
+        debug_assert!(matches!(
+            pipeline
+                .clone()
+                .register_implementation_rule("FreeSeqScan".to_string(), Arc::new(FreeSeqScan))?
+                .register_implementation_rule("FreeSeqScan".to_string(), Arc::new(FreeSeqScan)),
+            Err(DatabaseError::DuplicateName("optimizer rule", _))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_rules() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let pipeline = OptimizerPipeline::default()
+            .insert_batch_before(
+                "Limit Pushdown",
+                "Ignore Limit".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![],
+            )?
+            .register_normalization_rule(
+                "Ignore Limit",
+                "IgnoreLimit".to_string(),
+                Arc::new(IgnoreLimit),
+            )?
+            .register_implementation_rule("FreeSeqScan".to_string(), Arc::new(FreeSeqScan))?;
+        let database = DataBaseBuilder::path(temp_dir.path())
+            .optimizer_pipeline(pipeline.clone())
+            .build()?;
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("insert into t1 values (0, 0), (1, 1), (2, 2)")?;
+        let (_, tuples) = database.run("select * from t1 limit 1")?;
+        debug_assert_eq!(tuples.len(), 3);
+        // the free sequential scan is cheaper than the scan of the primary key
+        let (_, plan) = database.run("explain select * from t1 where a = 1")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("[SeqScan]"), "{}", plan);
+
+        // the rules are turned off by their names
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path())
+            .optimizer_pipeline(
+                pipeline
+                    .disable_rule("IgnoreLimit")?
+                    .disable_rule("FreeSeqScan")?
+                    .disable_rule("PushPredicateIntoScan")?
+                    .disable_rule("CombineFilter")?
+                    .enable_rule("CombineFilter")?,
+            )
+            .build()?;
+
+        let _ = database.run("create table t1 (a int primary key, b int)")?;
+        let _ = database.run("insert into t1 values (0, 0), (1, 1), (2, 2)")?;
+        let (_, tuples) = database.run("select * from t1 limit 1")?;
+        debug_assert_eq!(tuples.len(), 1);
+        let (_, tuples) = database.run("select * from t1 where a = 1")?;
+        debug_assert_eq!(
+            tuples.into_iter().map(|tuple| tuple.values).collect_vec(),
+            vec![vec![Arc::new(1.into()), Arc::new(1.into())]]
+        );
+        // the filter is not pushed into the scan to be read by the index
+        let (_, plan) = database.run("explain select * from t1 where a = 1")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("[SeqScan]"), "{}", plan);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,17 @@
 /// The architecture and some components,
 /// such as (/core) are referenced from sqlrs
-pub mod core;
-pub mod heuristic;
-pub mod rule;
+pub(crate) mod core;
+pub(crate) mod heuristic;
+pub(crate) mod rule;
+
+// The pipeline of the optimizer and what the rules registered on it are built with
+pub use self::core::cost_model::Cost;
+pub use self::core::memo::{Expression, GroupExpression, PhysicalProperties};
+pub use self::core::pattern::{Pattern, PatternChildrenPredicate};
+pub use self::core::rule::{DynImplementationRule, MatchPattern, NormalizationRule};
+pub use self::core::statistics_meta::{StatisticsLoader, StatisticsMeta};
+pub use self::heuristic::batch::{HepBatchStrategy, HepMatchOrder};
+pub use self::heuristic::graph::{HepGraph, HepNodeId};
+pub use self::heuristic::pipeline::{OptimizerPipeline, EAGER_AGGREGATION, JOIN_REORDER};
+pub use self::rule::implementation::ImplementationRuleImpl;
+pub use self::rule::normalization::NormalizationRuleImpl;
@@ -1,8 +1,14 @@
 use crate::errors::DatabaseError;
-use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::expression::agg::AggKind;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::cost_model::{
+    filtered_rows, Cost, CPU_TUPLE_COST, HASH_BUILD_COST, RANDOM_READ_COST,
+};
+use crate::optimizer::core::memo::{Expression, GroupExpression, PhysicalProperties};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::single_mapping;
 use crate::storage::Transaction;
@@ -39,13 +45,77 @@ pub struct GroupByAggregateImplementation;
 single_mapping!(
     GroupByAggregateImplementation,
     GROUP_BY_AGGREGATE_PATTERN,
-    PhysicalOption::HashAggregate
+    PhysicalOption::HashAggregate,
+    |_, children| (
+        children[0].rows.map(filtered_rows),
+        children[0]
+            .rows
+            .map(|rows| Cost::new(rows.saturating_mul(HASH_BUILD_COST), 0))
+    )
 );
 
 pub struct SimpleAggregateImplementation;
 
-single_mapping!(
-    SimpleAggregateImplementation,
-    SIMPLE_AGGREGATE_PATTERN,
-    PhysicalOption::SimpleAggregate
-);
+impl MatchPattern for SimpleAggregateImplementation {
+    fn pattern(&self) -> &Pattern {
+        &SIMPLE_AGGREGATE_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for SimpleAggregateImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(AggregateOperator { agg_calls, .. }) = op else {
+            unreachable!("invalid operator!")
+        };
+        let children = group_expr.children_properties();
+        let input_rows = children[0].rows;
+        // the row count and the index seek read the table directly, so the input must be every
+        // tuple of the table, without a filter or a limit between
+        let shortcut = match &agg_calls[..] {
+            // `COUNT(*)` counts the constant, which is not null in any row
+            [ScalarExpression::AggCall {
+                kind: AggKind::Count,
+                distinct: false,
+                args,
+                ..
+            }] if matches!(&args[0], ScalarExpression::Constant(value) if !value.is_null())
+                && children[0].is_full_scan =>
+            {
+                Some(PhysicalOption::RowCount)
+            }
+            [ScalarExpression::AggCall {
+                kind: AggKind::Min | AggKind::Max,
+                args,
+                ..
+            }] => PhysicalProperties::column(&args[0])
+                .and_then(|column| column.id())
+                .and_then(|column_id| {
+                    children[0].lookup_indexes.iter().find(|index_meta| {
+                        index_meta.exprs.is_none() && index_meta.column_ids[0] == column_id
+                    })
+                })
+                .map(|index_meta| PhysicalOption::IndexSeek(index_meta.clone())),
+            _ => None,
+        };
+
+        if let Some(option) = shortcut {
+            group_expr.append_expr(Expression {
+                op: option,
+                cost: Some(Cost::new(CPU_TUPLE_COST, RANDOM_READ_COST)),
+                rows: Some(1),
+            });
+        }
+        group_expr.append_expr(Expression {
+            op: PhysicalOption::SimpleAggregate,
+            cost: input_rows.map(Cost::cpu),
+            rows: Some(1),
+        });
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::Cost;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
@@ -20,4 +21,9 @@ lazy_static! {
 #[derive(Clone)]
 pub struct DummyImplementation;
 
-single_mapping!(DummyImplementation, DUMMY_PATTERN, PhysicalOption::Dummy);
+single_mapping!(
+    DummyImplementation,
+    DUMMY_PATTERN,
+    PhysicalOption::Dummy,
+    |_, _| (Some(1), Some(Cost::cpu(1)))
+);
@@ -1,5 +1,6 @@
 use crate::errors::DatabaseError;
-use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::optimizer::core::cost_model::{filtered_rows, Cost};
+use crate::optimizer::core::memo::{Expression, GroupExpression, PhysicalProperties};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
@@ -20,4 +21,23 @@ lazy_static! {
 #[derive(Clone)]
 pub struct FilterImplementation;
 
-single_mapping!(FilterImplementation, FILTER_PATTERN, PhysicalOption::Filter);
+single_mapping!(
+    FilterImplementation,
+    FILTER_PATTERN,
+    PhysicalOption::Filter,
+    estimate
+);
+
+fn estimate(_: &Operator, children: &[PhysicalProperties]) -> (Option<usize>, Option<Cost>) {
+    let Some(rows) = children[0].rows else {
+        return (None, None);
+    };
+    // the index scan has already narrowed the rows by the ranges detached from the predicate
+    let filtered = if children[0].is_ranged {
+        rows
+    } else {
+        filtered_rows(rows)
+    };
+
+    (Some(filtered), Some(Cost::cpu(rows)))
+}
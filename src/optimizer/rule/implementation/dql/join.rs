@@ -1,11 +1,16 @@
 use crate::errors::DatabaseError;
-use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::cost_model::{
+    filtered_rows, Cost, CPU_TUPLE_COST, HASH_BUILD_COST, RANDOM_READ_COST, SEQ_READ_COST,
+};
+use crate::optimizer::core::memo::{Expression, GroupExpression, PhysicalProperties};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
-use crate::planner::operator::join::{JoinCondition, JoinOperator};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::storage::Transaction;
+use crate::types::index::{IndexMetaRef, IndexType};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -30,24 +35,235 @@ impl<T: Transaction> ImplementationRule<T> for JoinImplementation {
     fn to_expression(
         &self,
         op: &Operator,
-        _: &StatisticMetaLoader<'_, T>,
+        loader: &StatisticMetaLoader<'_, T>,
         group_expr: &mut GroupExpression,
     ) -> Result<(), DatabaseError> {
-        let mut physical_option = PhysicalOption::NestLoopJoin;
-
-        if let Operator::Join(JoinOperator {
-            on: JoinCondition::On { on, .. },
-            ..
-        }) = op
-        {
-            if !on.is_empty() {
-                physical_option = PhysicalOption::HashJoin;
+        let Operator::Join(JoinOperator { on, join_type }) = op else {
+            unreachable!("invalid operator!")
+        };
+        let children_properties = group_expr.children_properties();
+        let rows = estimate_rows(on, join_type, children_properties);
+        let join_cost = |cpu: fn(usize, usize) -> usize| join_cost(children_properties, rows, cpu);
+        let mut exprs = Vec::new();
+
+        match on {
+            JoinCondition::On { on, .. } if !on.is_empty() => {
+                for (index_meta, cost) in index_lookups(on, join_type, children_properties, loader)?
+                {
+                    exprs.push(Expression {
+                        op: PhysicalOption::IndexNestLoopJoin(index_meta),
+                        cost: rows.map(|rows| cost + Cost::cpu(rows)),
+                        rows,
+                    });
+                }
+                if is_sort_merge_joinable(on, join_type, children_properties) {
+                    exprs.push(Expression {
+                        op: PhysicalOption::SortMergeJoin,
+                        cost: join_cost(|left_rows, right_rows| {
+                            left_rows.saturating_add(right_rows)
+                        }),
+                        rows,
+                    });
+                }
+                // the left input is built into a hashtable, and probed by the right input
+                exprs.push(Expression {
+                    op: PhysicalOption::HashJoin,
+                    cost: join_cost(|left_rows, right_rows| {
+                        left_rows
+                            .saturating_mul(HASH_BUILD_COST)
+                            .saturating_add(right_rows)
+                    }),
+                    rows,
+                });
             }
+            _ => exprs.push(nest_loop_join(on, join_type, children_properties)),
+        }
+        for expr in exprs {
+            group_expr.append_expr(expr);
         }
-        group_expr.append_expr(Expression {
-            op: physical_option,
-            cost: None,
-        });
         Ok(())
     }
 }
+
+/// Compares every tuple of the left input with every tuple of the right input, which is the
+/// only way of the joins without equivalent conditions.
+pub(crate) fn nest_loop_join(
+    on: &JoinCondition,
+    join_type: &JoinType,
+    children_properties: &[PhysicalProperties],
+) -> Expression {
+    let rows = estimate_rows(on, join_type, children_properties);
+
+    Expression {
+        op: PhysicalOption::NestLoopJoin,
+        cost: join_cost(children_properties, rows, |left_rows, right_rows| {
+            left_rows.saturating_mul(right_rows)
+        }),
+        rows,
+    }
+}
+
+fn join_cost(
+    children_properties: &[PhysicalProperties],
+    rows: Option<usize>,
+    cpu: fn(usize, usize) -> usize,
+) -> Option<Cost> {
+    children_properties[0]
+        .rows
+        .zip(children_properties[1].rows)
+        .zip(rows)
+        .map(|((left_rows, right_rows), rows)| {
+            Cost::cpu(cpu(left_rows, right_rows).saturating_add(rows))
+        })
+}
+
+/// Estimates the output rows of the join, an equivalent condition is assumed to join on a
+/// key of the smaller input.
+fn estimate_rows(
+    on: &JoinCondition,
+    join_type: &JoinType,
+    children_properties: &[PhysicalProperties],
+) -> Option<usize> {
+    let [PhysicalProperties {
+        rows: Some(left_rows),
+        ..
+    }, PhysicalProperties {
+        rows: Some(right_rows),
+        ..
+    }] = children_properties
+    else {
+        return None;
+    };
+    let (left_rows, right_rows) = (*left_rows, *right_rows);
+    let (is_equi, has_filter) = match on {
+        JoinCondition::On { on, filter } => (!on.is_empty(), filter.is_some()),
+        JoinCondition::None => (false, false),
+    };
+    let mut rows = if is_equi {
+        left_rows.max(right_rows)
+    } else {
+        left_rows.saturating_mul(right_rows)
+    };
+    if has_filter {
+        rows = filtered_rows(rows);
+    }
+
+    Some(match join_type {
+        JoinType::Inner | JoinType::Cross => rows,
+        JoinType::LeftOuter => rows.max(left_rows),
+        JoinType::RightOuter => rows.max(right_rows),
+        JoinType::Full => rows.max(left_rows).max(right_rows),
+        JoinType::LeftSemi => rows.min(left_rows),
+        JoinType::LeftAnti => filtered_rows(left_rows),
+    })
+}
+
+/// Both inputs must already be sorted on the equivalent columns, in the order of `on`,
+/// so that the join can be done by merging them without building a hashtable.
+fn is_sort_merge_joinable(
+    on: &[(ScalarExpression, ScalarExpression)],
+    join_type: &JoinType,
+    children_properties: &[PhysicalProperties],
+) -> bool {
+    if !matches!(
+        join_type,
+        JoinType::Inner | JoinType::LeftOuter | JoinType::RightOuter | JoinType::Full
+    ) {
+        return false;
+    }
+    let [left_properties, right_properties] = children_properties else {
+        return false;
+    };
+    if on.len() > left_properties.sort_columns.len()
+        || on.len() > right_properties.sort_columns.len()
+    {
+        return false;
+    }
+
+    on.iter()
+        .zip(
+            left_properties
+                .sort_columns
+                .iter()
+                .zip(right_properties.sort_columns.iter()),
+        )
+        .all(|((left_expr, right_expr), (left_sorted, right_sorted))| {
+            match (
+                PhysicalProperties::column(left_expr),
+                PhysicalProperties::column(right_expr),
+            ) {
+                (Some(left_column), Some(right_column)) => {
+                    left_column.summary() == left_sorted.summary()
+                        && right_column.summary() == right_sorted.summary()
+                        && left_column.datatype() == right_column.datatype()
+                }
+                _ => false,
+            }
+        })
+}
+
+/// Lists the indexes of the inner(right) table that can be probed with every outer(left)
+/// tuple, and the cost of the lookups.
+///
+/// Only an unfiltered table scan is probed, and only through the indexes of a single column
+/// compared by the join keys. The prefixes of the composite indexes are not probed, and a
+/// filtered inner input has no lookup indexes, so these joins are left to the other options.
+fn index_lookups<T: Transaction>(
+    on: &[(ScalarExpression, ScalarExpression)],
+    join_type: &JoinType,
+    children_properties: &[PhysicalProperties],
+    loader: &StatisticMetaLoader<'_, T>,
+) -> Result<Vec<(IndexMetaRef, Cost)>, DatabaseError> {
+    if !matches!(join_type, JoinType::Inner | JoinType::LeftOuter) {
+        return Ok(vec![]);
+    }
+    let [PhysicalProperties {
+        rows: Some(outer_rows),
+        ..
+    }, inner_properties] = children_properties
+    else {
+        return Ok(vec![]);
+    };
+    let mut lookups = Vec::new();
+
+    for index_meta in inner_properties.lookup_indexes.iter() {
+        let [column_id] = index_meta.column_ids[..] else {
+            continue;
+        };
+        let is_probed = on.iter().any(|(left_expr, right_expr)| {
+            PhysicalProperties::column(right_expr).is_some_and(|column| {
+                column.id() == Some(column_id) && &left_expr.return_type() == column.datatype()
+            })
+        });
+        if !is_probed {
+            continue;
+        }
+        let Some(statistics_meta) = loader.load(&index_meta.table_name, index_meta.id)? else {
+            continue;
+        };
+        let histogram = statistics_meta.histogram();
+        let (matched_rows, read_cost) = match index_meta.ty {
+            IndexType::PrimaryKey => (1, SEQ_READ_COST),
+            IndexType::Unique => (1, SEQ_READ_COST + RANDOM_READ_COST),
+            // need to return table query(non-covering index)
+            IndexType::Normal | IndexType::Composite => (
+                histogram
+                    .values_len()
+                    .div_ceil(histogram.number_of_distinct_value().max(1)),
+                SEQ_READ_COST + RANDOM_READ_COST,
+            ),
+        };
+        // one seek per outer tuple besides reading the matched tuples
+        let probe_cost = RANDOM_READ_COST + matched_rows.saturating_mul(read_cost);
+
+        lookups.push((
+            index_meta.clone(),
+            Cost::new(
+                outer_rows.saturating_mul(CPU_TUPLE_COST),
+                outer_rows.saturating_mul(probe_cost),
+            ),
+        ));
+    }
+
+    Ok(lookups)
+}
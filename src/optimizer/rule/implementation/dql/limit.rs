@@ -1,8 +1,10 @@
 use crate::errors::DatabaseError;
-use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::optimizer::core::cost_model::Cost;
+use crate::optimizer::core::memo::{Expression, GroupExpression, PhysicalProperties};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::limit::LimitOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::single_mapping;
 use crate::storage::Transaction;
@@ -20,4 +22,25 @@ lazy_static! {
 #[derive(Clone)]
 pub struct LimitImplementation;
 
-single_mapping!(LimitImplementation, LIMIT_PATTERN, PhysicalOption::Limit);
+single_mapping!(
+    LimitImplementation,
+    LIMIT_PATTERN,
+    PhysicalOption::Limit,
+    estimate
+);
+
+fn estimate(op: &Operator, children: &[PhysicalProperties]) -> (Option<usize>, Option<Cost>) {
+    let Operator::Limit(LimitOperator { offset, limit }) = op else {
+        unreachable!("invalid operator!")
+    };
+    let offset = offset.unwrap_or(0);
+    let Some(rows) = children[0].rows else {
+        return (*limit, None);
+    };
+    let limit = limit.unwrap_or(usize::MAX);
+
+    (
+        Some(rows.saturating_sub(offset).min(limit)),
+        Some(Cost::cpu(rows.min(offset.saturating_add(limit)))),
+    )
+}
@@ -1,4 +1,5 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::Cost;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
@@ -23,5 +24,6 @@ pub struct ProjectionImplementation;
 single_mapping!(
     ProjectionImplementation,
     PROJECTION_PATTERN,
-    PhysicalOption::Project
+    PhysicalOption::Project,
+    |_, children| (children[0].rows, children[0].rows.map(Cost::cpu))
 );
@@ -1,10 +1,11 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::{sort_cost, Cost};
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::sort::SortOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
-use crate::single_mapping;
 use crate::storage::Transaction;
 use lazy_static::lazy_static;
 
@@ -20,4 +21,39 @@ lazy_static! {
 #[derive(Clone)]
 pub struct SortImplementation;
 
-single_mapping!(SortImplementation, SORT_PATTERN, PhysicalOption::Sort);
+impl MatchPattern for SortImplementation {
+    fn pattern(&self) -> &Pattern {
+        &SORT_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for SortImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        let Operator::Sort(SortOperator { sort_fields, limit }) = op else {
+            unreachable!("invalid operator!")
+        };
+        let children = group_expr.children_properties();
+        let rows = children[0].rows;
+
+        // appended first to win the tie when the costs are unknown
+        if limit.is_none() && children[0].is_sorted_by(sort_fields) {
+            group_expr.append_expr(Expression {
+                op: PhysicalOption::Eliminated,
+                cost: Some(Cost::default()),
+                rows,
+            });
+        }
+        group_expr.append_expr(Expression {
+            op: PhysicalOption::Sort,
+            cost: rows.map(|rows| sort_cost(rows, rows)),
+            rows,
+        });
+
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::{Cost, CPU_TUPLE_COST, RANDOM_READ_COST, SEQ_READ_COST};
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::planner::operator::{Operator, PhysicalOption};
-use crate::storage::Transaction;
-use crate::types::index::IndexType;
+use crate::storage::{Bounds, Transaction};
+use crate::types::index::{IndexInfo, IndexType};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -34,7 +35,7 @@ impl<T: Transaction> ImplementationRule<T> for SeqScanImplementation {
         group_expr: &mut GroupExpression,
     ) -> Result<(), DatabaseError> {
         if let Operator::TableScan(scan_op) = op {
-            let cost = scan_op
+            let table_rows = scan_op
                 .index_infos
                 .iter()
                 .find(|index_info| {
@@ -46,10 +47,14 @@ impl<T: Transaction> ImplementationRule<T> for SeqScanImplementation {
                 .transpose()?
                 .flatten()
                 .map(|statistics_meta| statistics_meta.histogram().values_len());
+            let (rows, cost) = table_rows
+                .map(|table_rows| estimate(table_rows, scan_op.limit, SEQ_READ_COST))
+                .unzip();
 
             group_expr.append_expr(Expression {
                 op: PhysicalOption::SeqScan,
                 cost,
+                rows,
             });
 
             Ok(())
@@ -80,24 +85,44 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
                     continue;
                 }
                 let mut cost = None;
+                let mut rows = None;
+                let covered = scan_op.columns.iter().all(|(_, column)| {
+                    column.id().is_some_and(|column_id| {
+                        index_info
+                            .meta
+                            .is_covering(&column_id, &scan_op.primary_key)
+                    })
+                });
 
                 if let Some(range) = &index_info.range {
                     if let Some(statistics_meta) =
                         loader.load(&scan_op.table_name, index_info.meta.id)?
                     {
-                        let mut row_count = statistics_meta.collect_count(range)?;
+                        let read_cost =
+                            if covered || matches!(index_info.meta.ty, IndexType::PrimaryKey) {
+                                SEQ_READ_COST
+                            } else {
+                                // need to return table query(non-covering index)
+                                SEQ_READ_COST + RANDOM_READ_COST
+                            };
+                        let (row_count, scan_cost) = estimate(
+                            statistics_meta.collect_count(range)?,
+                            scan_op.limit,
+                            read_cost,
+                        );
 
-                        if !matches!(index_info.meta.ty, IndexType::PrimaryKey) {
-                            // need to return table query(non-covering index)
-                            row_count *= 2;
-                        }
-                        cost = Some(row_count);
+                        rows = Some(row_count);
+                        cost = Some(scan_cost);
                     }
                 }
 
                 group_expr.append_expr(Expression {
-                    op: PhysicalOption::IndexScan(index_info.clone()),
+                    op: PhysicalOption::IndexScan(IndexInfo {
+                        covered,
+                        ..index_info.clone()
+                    }),
                     cost,
+                    rows,
                 })
             }
 
@@ -107,3 +132,93 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
         }
     }
 }
+
+pub struct IndexMergeImplementation;
+
+impl MatchPattern for IndexMergeImplementation {
+    fn pattern(&self) -> &Pattern {
+        &TABLE_SCAN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for IndexMergeImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::TableScan(scan_op) = op {
+            for index_merge in scan_op.index_merges.iter() {
+                let mut index_rows = Some(Vec::with_capacity(index_merge.index_infos.len()));
+
+                for index_info in index_merge.index_infos.iter() {
+                    let (Some(range), Some(rows)) = (&index_info.range, index_rows.as_mut()) else {
+                        break;
+                    };
+                    match loader.load(&scan_op.table_name, index_info.meta.id)? {
+                        Some(statistics_meta) => rows.push((
+                            statistics_meta.collect_count(range)?,
+                            statistics_meta.histogram().values_len(),
+                        )),
+                        None => index_rows = None,
+                    }
+                }
+                let (rows, cost) = index_rows
+                    .map(|index_rows| {
+                        let read_rows = index_rows.iter().map(|(rows, _)| rows).sum::<usize>();
+                        let merged_rows = if index_merge.is_union {
+                            read_rows
+                        } else {
+                            // the ranges are assumed to be independent
+                            let total = index_rows.iter().map(|(_, len)| *len).max().unwrap_or(0);
+                            let selectivity = index_rows
+                                .iter()
+                                .map(|(rows, _)| *rows as f64 / total.max(1) as f64)
+                                .product::<f64>();
+
+                            (total as f64 * selectivity).ceil() as usize
+                        };
+                        // the tuple ids are read from the indexes, then the merged tuples are
+                        // read by the primary key one by one
+                        let (rows, lookup_cost) =
+                            estimate(merged_rows, scan_op.limit, RANDOM_READ_COST);
+
+                        (
+                            rows,
+                            Cost::new(
+                                read_rows.saturating_mul(CPU_TUPLE_COST),
+                                read_rows.saturating_mul(SEQ_READ_COST),
+                            ) + lookup_cost,
+                        )
+                    })
+                    .unzip();
+
+                group_expr.append_expr(Expression {
+                    op: PhysicalOption::IndexMerge(index_merge.clone()),
+                    cost,
+                    rows,
+                })
+            }
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
+
+/// Estimates the output rows and the cost of reading `rows` tuples, each costs `read_cost`,
+/// with the pushed down limit.
+fn estimate(rows: usize, (offset, limit): Bounds, read_cost: usize) -> (usize, Cost) {
+    let offset = offset.unwrap_or(0);
+    let read_rows = limit.map_or(rows, |limit| rows.min(offset.saturating_add(limit)));
+
+    (
+        read_rows.saturating_sub(offset),
+        Cost::new(
+            read_rows.saturating_mul(CPU_TUPLE_COST),
+            read_rows.saturating_mul(read_cost),
+        ),
+    )
+}
@@ -0,0 +1,72 @@
+use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::{sort_cost, Cost};
+use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::top_n::TopNOperator;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::storage::Transaction;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref TOP_N_PATTERN: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::TopN(_)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+#[derive(Clone)]
+pub struct TopNImplementation;
+
+impl MatchPattern for TopNImplementation {
+    fn pattern(&self) -> &Pattern {
+        &TOP_N_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for TopNImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        let Operator::TopN(TopNOperator {
+            sort_fields,
+            limit,
+            offset,
+        }) = op
+        else {
+            unreachable!("invalid operator!")
+        };
+        let children = group_expr.children_properties();
+        let (input_rows, is_sorted) = (children[0].rows, children[0].is_sorted_by(sort_fields));
+        let offset = offset.unwrap_or(0);
+        let rows = input_rows
+            .map(|rows| rows.saturating_sub(offset).min(*limit))
+            .or(Some(*limit));
+        let heap_size = offset.saturating_add(*limit);
+
+        // the input is already in order, so the first tuples are taken as a `Limit`, appended
+        // first to win the tie when the costs are unknown
+        if is_sorted {
+            group_expr.append_expr(Expression {
+                op: PhysicalOption::Limit,
+                cost: Some(Cost::cpu(
+                    input_rows.map_or(heap_size, |rows| rows.min(heap_size)),
+                )),
+                rows,
+            });
+        }
+        group_expr.append_expr(Expression {
+            op: PhysicalOption::TopN,
+            cost: input_rows.map(|rows| sort_cost(rows, heap_size)),
+            rows,
+        });
+
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::cost_model::Cost;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::single_mapping;
 use crate::storage::Transaction;
@@ -20,4 +22,14 @@ lazy_static! {
 #[derive(Clone)]
 pub struct ValuesImplementation;
 
-single_mapping!(ValuesImplementation, VALUES_PATTERN, PhysicalOption::Values);
+single_mapping!(
+    ValuesImplementation,
+    VALUES_PATTERN,
+    PhysicalOption::Values,
+    |op, _| {
+        let Operator::Values(ValuesOperator { rows, .. }) = op else {
+            unreachable!("invalid operator!")
+        };
+        (Some(rows.len()), Some(Cost::cpu(rows.len())))
+    }
+);
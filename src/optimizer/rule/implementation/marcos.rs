@@ -1,6 +1,11 @@
 #[macro_export]
 macro_rules! single_mapping {
     ($ty:ty, $pattern:expr, $option:expr) => {
+        single_mapping!($ty, $pattern, $option, |_, _| (None, None));
+    };
+    // `$estimate` estimates the output rows and the cost of the operator by the properties
+    // of its children
+    ($ty:ty, $pattern:expr, $option:expr, $estimate:expr) => {
         impl MatchPattern for $ty {
             fn pattern(&self) -> &Pattern {
                 &$pattern
@@ -10,14 +15,23 @@ macro_rules! single_mapping {
         impl<T: Transaction> ImplementationRule<T> for $ty {
             fn to_expression(
                 &self,
-                _: &Operator,
+                op: &Operator,
                 _: &StatisticMetaLoader<'_, T>,
                 group_expr: &mut GroupExpression,
             ) -> Result<(), DatabaseError> {
-                //TODO: CostModel
+                let estimate: fn(
+                    &Operator,
+                    &[$crate::optimizer::core::memo::PhysicalProperties],
+                ) -> (
+                    Option<usize>,
+                    Option<$crate::optimizer::core::cost_model::Cost>,
+                ) = $estimate;
+                let (rows, cost) = estimate(op, group_expr.children_properties());
+
                 group_expr.append_expr(Expression {
                     op: $option,
-                    cost: None,
+                    cost,
+                    rows,
                 });
 
                 Ok(())
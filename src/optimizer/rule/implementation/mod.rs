@@ -6,7 +6,7 @@ pub(crate) mod marcos;
 use crate::errors::DatabaseError;
 use crate::optimizer::core::memo::GroupExpression;
 use crate::optimizer::core::pattern::Pattern;
-use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
+use crate::optimizer::core::rule::{DynImplementationRule, ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::optimizer::rule::implementation::ddl::add_column::AddColumnImplementation;
 use crate::optimizer::rule::implementation::ddl::create_table::CreateTableImplementation;
@@ -30,13 +30,15 @@ use crate::optimizer::rule::implementation::dql::limit::LimitImplementation;
 use crate::optimizer::rule::implementation::dql::projection::ProjectionImplementation;
 use crate::optimizer::rule::implementation::dql::sort::SortImplementation;
 use crate::optimizer::rule::implementation::dql::table_scan::{
-    IndexScanImplementation, SeqScanImplementation,
+    IndexMergeImplementation, IndexScanImplementation, SeqScanImplementation,
 };
+use crate::optimizer::rule::implementation::dql::top_n::TopNImplementation;
 use crate::optimizer::rule::implementation::dql::values::ValuesImplementation;
 use crate::planner::operator::Operator;
 use crate::storage::Transaction;
+use std::sync::Arc;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum ImplementationRuleImpl {
     // DQL
     GroupByAggregate,
@@ -49,7 +51,9 @@ pub enum ImplementationRuleImpl {
     SeqScan,
     FunctionScan,
     IndexScan,
+    IndexMerge,
     Sort,
+    TopN,
     Values,
     // DML
     Analyze,
@@ -64,6 +68,18 @@ pub enum ImplementationRuleImpl {
     DropColumn,
     DropTable,
     Truncate,
+    // Registered by the embedder with its name
+    Custom(String, Arc<dyn DynImplementationRule>),
+}
+
+impl ImplementationRuleImpl {
+    /// The name to disable the rule by, which is the name of the variant for the builtin rules.
+    pub fn name(&self) -> String {
+        match self {
+            ImplementationRuleImpl::Custom(name, _) => name.clone(),
+            rule => format!("{:?}", rule),
+        }
+    }
 }
 
 impl MatchPattern for ImplementationRuleImpl {
@@ -78,8 +94,10 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::Projection => ProjectionImplementation.pattern(),
             ImplementationRuleImpl::SeqScan => SeqScanImplementation.pattern(),
             ImplementationRuleImpl::IndexScan => IndexScanImplementation.pattern(),
+            ImplementationRuleImpl::IndexMerge => IndexMergeImplementation.pattern(),
             ImplementationRuleImpl::FunctionScan => FunctionScanImplementation.pattern(),
             ImplementationRuleImpl::Sort => SortImplementation.pattern(),
+            ImplementationRuleImpl::TopN => TopNImplementation.pattern(),
             ImplementationRuleImpl::Values => ValuesImplementation.pattern(),
             ImplementationRuleImpl::CopyFromFile => CopyFromFileImplementation.pattern(),
             ImplementationRuleImpl::CopyToFile => CopyToFileImplementation.pattern(),
@@ -92,6 +110,7 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::DropTable => DropTableImplementation.pattern(),
             ImplementationRuleImpl::Truncate => TruncateImplementation.pattern(),
             ImplementationRuleImpl::Analyze => AnalyzeImplementation.pattern(),
+            ImplementationRuleImpl::Custom(_, rule) => rule.pattern(),
         }
     }
 }
@@ -131,12 +150,18 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::IndexScan => {
                 IndexScanImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::IndexMerge => {
+                IndexMergeImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::FunctionScan => {
                 FunctionScanImplementation.to_expression(operator, loader, group_expr)?
             }
             ImplementationRuleImpl::Sort => {
                 SortImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::TopN => {
+                TopNImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Values => {
                 ValuesImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -173,6 +198,9 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::Analyze => {
                 AnalyzeImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::Custom(_, rule) => {
+                rule.to_expression(operator, loader, group_expr)?
+            }
         }
 
         Ok(())
@@ -109,6 +109,7 @@ impl ColumnPruning {
             }
             Operator::Sort(_)
             | Operator::Limit(_)
+            | Operator::TopN(_)
             | Operator::Join(_)
             | Operator::Filter(_)
             | Operator::Union(_) => {
@@ -126,7 +127,7 @@ impl ColumnPruning {
             }
             // Last Operator
             Operator::Dummy | Operator::Values(_) | Operator::FunctionScan(_) => (),
-            Operator::Explain => {
+            Operator::Explain(_) => {
                 if let Some(child_id) = graph.eldest_child_at(node_id) {
                     Self::_apply(column_references, true, child_id, graph);
                 } else {
@@ -4,6 +4,9 @@ use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::optimizer::rule::normalization::is_subset_exprs;
+use crate::planner::operator::limit::LimitOperator;
+use crate::planner::operator::sort::SortOperator;
+use crate::planner::operator::top_n::TopNOperator;
 use crate::planner::operator::Operator;
 use crate::types::LogicalType;
 use lazy_static::lazy_static;
@@ -28,6 +31,15 @@ lazy_static! {
             }]),
         }
     };
+    static ref COMBINE_SORT_LIMIT_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Limit(LimitOperator { limit: Some(_), .. })),
+            children: PatternChildrenPredicate::Predicate(vec![Pattern {
+                predicate: |op| matches!(op, Operator::Sort(SortOperator { limit: None, .. })),
+                children: PatternChildrenPredicate::None,
+            }]),
+        }
+    };
     static ref COLLAPSE_GROUP_BY_AGG: Pattern = {
         Pattern {
             predicate: |op| match op {
@@ -102,6 +114,44 @@ impl NormalizationRule for CombineFilter {
     }
 }
 
+/// Combine a limit over a sort into a top-n, which only keeps `offset + limit` tuples
+/// in memory instead of sorting the whole input.
+pub struct CombineSortLimit;
+
+impl MatchPattern for CombineSortLimit {
+    fn pattern(&self) -> &Pattern {
+        &COMBINE_SORT_LIMIT_RULE
+    }
+}
+
+impl NormalizationRule for CombineSortLimit {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        if let Operator::Limit(LimitOperator {
+            offset,
+            limit: Some(limit),
+        }) = graph.operator(node_id).clone()
+        {
+            if let Some(child_id) = graph.eldest_child_at(node_id) {
+                if let Operator::Sort(SortOperator { sort_fields, .. }) =
+                    graph.operator(child_id).clone()
+                {
+                    graph.replace_node(
+                        node_id,
+                        Operator::TopN(TopNOperator {
+                            sort_fields,
+                            limit,
+                            offset,
+                        }),
+                    );
+                    graph.remove_node(child_id, false);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct CollapseGroupByAgg;
 
 impl MatchPattern for CollapseGroupByAgg {
@@ -260,4 +310,35 @@ mod tests {
         }
         unreachable!("Should be a agg operator")
     }
+
+    #[test]
+    fn test_combine_sort_limit() -> Result<(), DatabaseError> {
+        let plan = select_sql_run("select c1, c2 from t1 order by c2 desc limit 10 offset 5")?;
+
+        let best_plan = HepOptimizer::new(plan.clone())
+            .batch(
+                "test_combine_sort_limit".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::LimitProjectTranspose,
+                    NormalizationRuleImpl::CombineSortLimit,
+                ],
+            )
+            .find_best::<RocksTransaction>(None)?;
+
+        if let Operator::TopN(op) = &best_plan.childrens[0].operator {
+            debug_assert_eq!(op.limit, 10);
+            debug_assert_eq!(op.offset, Some(5));
+            debug_assert_eq!(op.sort_fields.len(), 1);
+            debug_assert!(!op.sort_fields[0].asc);
+        } else {
+            unreachable!("Should be a top-n operator")
+        }
+        if let Operator::TableScan(_) = &best_plan.childrens[0].childrens[0].operator {
+        } else {
+            unreachable!("Should be a scan operator")
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,410 @@
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::ScalarFunction;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::sort::SortOperator;
+use crate::planner::operator::top_n::TopNOperator;
+use crate::planner::operator::Operator;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref ELIMINATE_COMMON_SUBEXPRESSION_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Project(_) | Operator::Aggregate(_)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Computes the subexpressions repeated in a `Project` or an `Aggregate` and the filters and
+/// sorts below it only once, by a `Project` added below the lowest operator using them, e.g.
+/// `price * qty` of `SELECT price * qty FROM t WHERE price * qty > 10 ORDER BY price * qty`.
+///
+/// The operators above keep their expressions, `ExpressionRemapper` turns the repeated ones into
+/// the references to the outputs of the added `Project`.
+#[derive(Copy, Clone)]
+pub struct EliminateCommonSubexpression;
+
+impl EliminateCommonSubexpression {
+    /// The expressions of the operators evaluated on the input tuples of the operator.
+    fn exprs(operator: &Operator) -> Vec<&ScalarExpression> {
+        match operator {
+            Operator::Project(op) => op.exprs.iter().collect_vec(),
+            Operator::Aggregate(op) => op
+                .agg_calls
+                .iter()
+                .chain(op.groupby_exprs.iter())
+                .collect_vec(),
+            Operator::Filter(op) => vec![&op.predicate],
+            Operator::Sort(SortOperator { sort_fields, .. })
+            | Operator::TopN(TopNOperator { sort_fields, .. }) => {
+                sort_fields.iter().map(|field| &field.expr).collect_vec()
+            }
+            _ => vec![],
+        }
+    }
+
+    fn output_exprs(graph: &HepGraph, node_id: HepNodeId) -> Option<Vec<ScalarExpression>> {
+        match graph.operator(node_id) {
+            Operator::Join(op) => {
+                let mut exprs = Vec::new();
+
+                for (i, child_id) in graph.children_at(node_id).enumerate() {
+                    // the semi and anti joins only output the left tuples
+                    if i > 0 && matches!(op.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
+                        break;
+                    }
+                    exprs.append(&mut Self::output_exprs(graph, child_id)?);
+                }
+                Some(exprs)
+            }
+            Operator::Filter(_) | Operator::Sort(_) | Operator::TopN(_) | Operator::Limit(_) => {
+                Self::output_exprs(graph, graph.eldest_child_at(node_id)?)
+            }
+            operator => operator.output_exprs(),
+        }
+    }
+
+    /// Counts the subexpressions evaluated for every tuple, except the ones `provided` by the
+    /// input and their subexpressions.
+    fn count<'a>(
+        expr: &'a ScalarExpression,
+        provided: &[ScalarExpression],
+        is_aggregate: bool,
+        counts: &mut Vec<(&'a ScalarExpression, usize)>,
+    ) {
+        let column = expr.output_column();
+
+        if provided
+            .iter()
+            .any(|provided| provided.output_column().summary() == column.summary())
+        {
+            return;
+        }
+        if is_candidate(expr) {
+            match counts.iter_mut().find(|(other, _)| *other == expr) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((expr, 1)),
+            }
+        }
+        // the arguments of the aggregate functions above an `Aggregate` are not in its input
+        if matches!(expr, ScalarExpression::AggCall { .. }) && !is_aggregate {
+            return;
+        }
+        for child in children(expr).0 {
+            Self::count(child, provided, is_aggregate, counts);
+        }
+    }
+}
+
+impl MatchPattern for EliminateCommonSubexpression {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_COMMON_SUBEXPRESSION_RULE
+    }
+}
+
+impl NormalizationRule for EliminateCommonSubexpression {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        // the operators evaluating their expressions on the same input tuples
+        let mut chain = vec![node_id];
+        let mut input_id = graph.eldest_child_at(node_id);
+
+        while let Some(child_id) = input_id {
+            if !matches!(
+                graph.operator(child_id),
+                Operator::Filter(_) | Operator::Sort(_) | Operator::TopN(_) | Operator::Limit(_)
+            ) {
+                break;
+            }
+            chain.push(child_id);
+            input_id = graph.eldest_child_at(child_id);
+        }
+        let Some(input_exprs) = input_id.and_then(|input_id| Self::output_exprs(graph, input_id))
+        else {
+            return Ok(());
+        };
+        let is_aggregate = matches!(graph.operator(node_id), Operator::Aggregate(_));
+        let mut provided = input_exprs.clone();
+        let mut common_exprs = Vec::new();
+
+        // the largest repeated subexpression is taken first, and the ones inside it are only
+        // counted out of it
+        loop {
+            let mut counts = Vec::new();
+
+            for id in chain.iter() {
+                for expr in Self::exprs(graph.operator(*id)) {
+                    Self::count(expr, &provided, is_aggregate, &mut counts);
+                }
+            }
+            let Some((expr, _)) = counts
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .max_by_key(|(expr, _)| size(expr))
+            else {
+                break;
+            };
+            let expr = expr.clone();
+
+            provided.push(expr.clone());
+            common_exprs.push(expr);
+        }
+        if common_exprs.is_empty() {
+            return Ok(());
+        }
+        let Some(lowest_id) = chain.iter().rev().find(|id| {
+            Self::exprs(graph.operator(**id)).into_iter().any(|expr| {
+                common_exprs
+                    .iter()
+                    .any(|common_expr| contains(expr, common_expr))
+            })
+        }) else {
+            return Ok(());
+        };
+        let Some(child_id) = graph.eldest_child_at(*lowest_id) else {
+            return Ok(());
+        };
+        let exprs = input_exprs.into_iter().chain(common_exprs).collect_vec();
+
+        graph.add_node(
+            *lowest_id,
+            Some(child_id),
+            Operator::Project(ProjectOperator { exprs }),
+        );
+
+        Ok(())
+    }
+}
+
+/// The subexpressions evaluated for every evaluation of the expression, and the ones evaluated
+/// only on some conditions.
+fn children(expr: &ScalarExpression) -> (Vec<&ScalarExpression>, Vec<&ScalarExpression>) {
+    match expr {
+        ScalarExpression::Constant(_)
+        | ScalarExpression::ColumnRef(_)
+        | ScalarExpression::Reference { .. }
+        | ScalarExpression::Empty
+        | ScalarExpression::TableFunction(_) => (vec![], vec![]),
+        ScalarExpression::Alias { expr, .. }
+        | ScalarExpression::TypeCast { expr, .. }
+        | ScalarExpression::IsNull { expr, .. }
+        | ScalarExpression::Unary { expr, .. } => (vec![expr], vec![]),
+        ScalarExpression::Binary {
+            left_expr,
+            right_expr,
+            ..
+        }
+        | ScalarExpression::NullIf {
+            left_expr,
+            right_expr,
+            ..
+        }
+        | ScalarExpression::Position {
+            expr: left_expr,
+            in_expr: right_expr,
+        } => (vec![left_expr, right_expr], vec![]),
+        ScalarExpression::AggCall { args, .. }
+        | ScalarExpression::Tuple(args)
+        | ScalarExpression::ScalaFunction(ScalarFunction { args, .. }) => {
+            (args.iter().collect_vec(), vec![])
+        }
+        ScalarExpression::In { expr, args, .. } => (
+            Some(expr.as_ref()).into_iter().chain(args).collect_vec(),
+            vec![],
+        ),
+        ScalarExpression::Between {
+            expr,
+            left_expr,
+            right_expr,
+            ..
+        } => (vec![expr, left_expr, right_expr], vec![]),
+        ScalarExpression::SubString {
+            expr,
+            for_expr,
+            from_expr,
+        } => (
+            Some(expr)
+                .into_iter()
+                .chain(for_expr)
+                .chain(from_expr)
+                .map(AsRef::as_ref)
+                .collect_vec(),
+            vec![],
+        ),
+        ScalarExpression::Trim {
+            expr,
+            trim_what_expr,
+            ..
+        } => (
+            Some(expr)
+                .into_iter()
+                .chain(trim_what_expr)
+                .map(AsRef::as_ref)
+                .collect_vec(),
+            vec![],
+        ),
+        ScalarExpression::If {
+            condition,
+            left_expr,
+            right_expr,
+            ..
+        } => (vec![condition], vec![left_expr, right_expr]),
+        ScalarExpression::IfNull {
+            left_expr,
+            right_expr,
+            ..
+        } => (vec![left_expr], vec![right_expr]),
+        ScalarExpression::Coalesce { exprs, .. } => {
+            let (first, rest) = exprs
+                .split_first()
+                .map_or((None, &[][..]), |(first, rest)| (Some(first), rest));
+            (first.into_iter().collect_vec(), rest.iter().collect_vec())
+        }
+        ScalarExpression::CaseWhen {
+            operand_expr,
+            expr_pairs,
+            else_expr,
+            ..
+        } => {
+            let mut exprs = operand_expr.iter().map(AsRef::as_ref).collect_vec();
+            let mut conditional_exprs = Vec::new();
+
+            for (i, (when_expr, then_expr)) in expr_pairs.iter().enumerate() {
+                if i == 0 {
+                    exprs.push(when_expr);
+                } else {
+                    conditional_exprs.push(when_expr);
+                }
+                conditional_exprs.push(then_expr);
+            }
+            conditional_exprs.extend(else_expr.iter().map(AsRef::as_ref));
+
+            (exprs, conditional_exprs)
+        }
+    }
+}
+
+/// Whether the expression can be computed once for each tuple by a `Project`: it refers to the
+/// columns and gives the same result for the same tuple.
+fn is_candidate(expr: &ScalarExpression) -> bool {
+    fn is_pure(expr: &ScalarExpression, has_column: &mut bool) -> bool {
+        match expr {
+            ScalarExpression::AggCall { .. }
+            | ScalarExpression::ScalaFunction(_)
+            | ScalarExpression::TableFunction(_)
+            | ScalarExpression::Reference { .. }
+            | ScalarExpression::Empty => false,
+            ScalarExpression::ColumnRef(_) => {
+                *has_column = true;
+                true
+            }
+            expr => {
+                let (exprs, conditional_exprs) = children(expr);
+
+                exprs
+                    .into_iter()
+                    .chain(conditional_exprs)
+                    .all(|child| is_pure(child, has_column))
+            }
+        }
+    }
+    if matches!(
+        expr,
+        ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Alias { .. }
+            | ScalarExpression::Tuple(_)
+    ) {
+        return false;
+    }
+    let mut has_column = false;
+
+    is_pure(expr, &mut has_column) && has_column
+}
+
+fn contains(expr: &ScalarExpression, target: &ScalarExpression) -> bool {
+    if expr == target {
+        return true;
+    }
+    let (exprs, conditional_exprs) = children(expr);
+
+    exprs
+        .into_iter()
+        .chain(conditional_exprs)
+        .any(|child| contains(child, target))
+}
+
+fn size(expr: &ScalarExpression) -> usize {
+    let (exprs, conditional_exprs) = children(expr);
+
+    1 + exprs
+        .into_iter()
+        .chain(conditional_exprs)
+        .map(size)
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn eliminate(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        HepOptimizer::new(select_sql_run(sql)?)
+            .batch(
+                "test_eliminate_common_subexpression".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::EliminateCommonSubexpression],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    #[test]
+    fn test_eliminate_common_subexpression() -> Result<(), DatabaseError> {
+        let plan =
+            eliminate("select c1 + c2, c3 from t1 left join t2 on c1 = c3 where c1 + c2 > 1")?;
+
+        // project -> filter -> project -> join
+        let filter_plan = &plan.childrens[0];
+        debug_assert!(matches!(filter_plan.operator, Operator::Filter(_)));
+        if let Operator::Project(op) = &filter_plan.childrens[0].operator {
+            debug_assert_eq!(
+                op.exprs
+                    .iter()
+                    .map(|expr| expr.to_string())
+                    .collect::<Vec<_>>(),
+                vec!["t1.c1", "t1.c2", "t2.c3", "t2.c4", "(t1.c1 + t1.c2)"]
+            );
+        } else {
+            unreachable!("Should be a project operator")
+        }
+        debug_assert!(matches!(
+            filter_plan.childrens[0].childrens[0].operator,
+            Operator::Join(_)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_conditional_subexpression() -> Result<(), DatabaseError> {
+        let plan = eliminate(
+            "select case when c1 > 0 then c1 * c2 else 0 end, case when c2 > 0 then c1 * c2 else 0 end from t1",
+        )?;
+
+        debug_assert!(matches!(plan.childrens[0].operator, Operator::TableScan(_)));
+
+        Ok(())
+    }
+}
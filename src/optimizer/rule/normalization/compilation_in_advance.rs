@@ -4,6 +4,8 @@ use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::sort::SortOperator;
+use crate::planner::operator::top_n::TopNOperator;
 use crate::planner::operator::Operator;
 use lazy_static::lazy_static;
 
@@ -79,8 +81,9 @@ impl ExpressionRemapper {
                     expr.try_reference(output_exprs);
                 }
             }
-            Operator::Sort(op) => {
-                for sort_field in op.sort_fields.iter_mut() {
+            Operator::Sort(SortOperator { sort_fields, .. })
+            | Operator::TopN(TopNOperator { sort_fields, .. }) => {
+                for sort_field in sort_fields.iter_mut() {
                     sort_field.expr.try_reference(output_exprs);
                 }
             }
@@ -94,7 +97,7 @@ impl ExpressionRemapper {
             | Operator::Limit(_)
             | Operator::Values(_)
             | Operator::Show
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::Insert(_)
             | Operator::Update(_)
@@ -180,8 +183,9 @@ impl EvaluatorBind {
                     expr.bind_evaluator()?;
                 }
             }
-            Operator::Sort(op) => {
-                for sort_field in op.sort_fields.iter_mut() {
+            Operator::Sort(SortOperator { sort_fields, .. })
+            | Operator::TopN(TopNOperator { sort_fields, .. }) => {
+                for sort_field in sort_fields.iter_mut() {
                     sort_field.expr.bind_evaluator()?;
                 }
             }
@@ -195,7 +199,7 @@ impl EvaluatorBind {
             | Operator::Limit(_)
             | Operator::Values(_)
             | Operator::Show
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::Insert(_)
             | Operator::Update(_)
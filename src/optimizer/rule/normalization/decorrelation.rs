@@ -0,0 +1,400 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::batch::HepMatchOrder;
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates,
+};
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::Operator;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref DECORRELATE_JOIN_RULE: Pattern = {
+        Pattern {
+            predicate: |op| match op {
+                Operator::Join(join_op) => matches!(
+                    join_op.join_type,
+                    JoinType::Inner | JoinType::LeftSemi | JoinType::LeftAnti
+                ),
+                _ => false,
+            },
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Removes the correlation of the subqueries in `WHERE`, which are bound as the right side of a
+/// join whose predicates refer to the columns of its left side, that is, a dependent join.
+///
+/// Following the unnesting of Neumann & Kemper, the correlated predicates are pulled up from
+/// the subquery into the condition of the join, so the join becomes a regular one:
+/// - `Filter`: the correlated conjuncts are detached from it.
+/// - `Project`: the inner columns of the pulled predicates are projected too.
+/// - `Aggregate`: the inner columns of the correlated equalities become grouping keys, e.g.
+///   `x > (SELECT avg(x) FROM t2 WHERE t2.k = t1.k)` is joined with
+///   `SELECT k, avg(x) FROM t2 GROUP BY k` on `t1.k = t2.k`.
+///
+/// The subquery is kept as is when its correlation can't be pulled up, e.g. through a `Limit`,
+/// or through a `COUNT` whose empty groups would be lost.
+pub struct DecorrelateJoin;
+
+impl MatchPattern for DecorrelateJoin {
+    fn pattern(&self) -> &Pattern {
+        &DECORRELATE_JOIN_RULE
+    }
+}
+
+impl NormalizationRule for DecorrelateJoin {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let [left_id, right_id] = graph.children_at(node_id).collect_vec()[..] else {
+            return Ok(());
+        };
+        let right_columns = subtree_columns(graph, right_id, true);
+        let outer_columns = subtree_columns(graph, left_id, false)
+            .into_iter()
+            .filter(|column| !contains(&right_columns, column))
+            .collect_vec();
+        if outer_columns.is_empty() {
+            return Ok(());
+        }
+        // the subquery is rewritten on a copy, which is dropped if the correlation remains
+        let mut new_graph = graph.clone();
+        let Some(predicates) = pull_up(&mut new_graph, right_id, &outer_columns) else {
+            return Ok(());
+        };
+        if predicates.is_empty() {
+            return Ok(());
+        }
+        let Operator::Join(join_op) = new_graph.operator_mut(node_id) else {
+            unreachable!()
+        };
+        let (mut on, mut filters) = match &mut join_op.on {
+            JoinCondition::On { on, filter } => (
+                std::mem::take(on),
+                filter
+                    .take()
+                    .map(|filter| split_conjunctive_predicates(&filter))
+                    .unwrap_or_default(),
+            ),
+            JoinCondition::None => (vec![], vec![]),
+        };
+        for predicate in predicates {
+            match join_keys(predicate, &outer_columns) {
+                Ok(keys) => on.push(keys),
+                Err(predicate) => filters.push(predicate),
+            }
+        }
+        join_op.on = JoinCondition::On {
+            on,
+            filter: reduce_filters(filters, false).map(|filter_op| filter_op.predicate),
+        };
+        new_graph.version += 1;
+        *graph = new_graph;
+
+        Ok(())
+    }
+}
+
+/// Pulls the correlated predicates of the subtree up to `node_id`, whose output then contains
+/// the inner columns of them. Returns None if the correlation can't be pulled up.
+fn pull_up(
+    graph: &mut HepGraph,
+    node_id: HepNodeId,
+    outer_columns: &[ColumnRef],
+) -> Option<Vec<ScalarExpression>> {
+    let is_correlated = |expr: &ScalarExpression| {
+        expr.referenced_columns(true)
+            .iter()
+            .any(|column| contains(outer_columns, column))
+    };
+    let fn_pull_up_child = |graph: &mut HepGraph| match graph.eldest_child_at(node_id) {
+        Some(child_id) => pull_up(graph, child_id, outer_columns),
+        None => Some(vec![]),
+    };
+
+    match graph.operator(node_id) {
+        Operator::Filter(op) => {
+            let (correlated, rest): (Vec<_>, Vec<_>) = split_conjunctive_predicates(&op.predicate)
+                .into_iter()
+                .partition(is_correlated);
+            if correlated.is_empty() {
+                return fn_pull_up_child(graph);
+            }
+            // the predicate of `HAVING` refers to the aggregated values
+            if op.having {
+                return None;
+            }
+            let mut predicates = fn_pull_up_child(graph)?;
+
+            match reduce_filters(rest, false) {
+                Some(filter_op) => graph.replace_node(node_id, Operator::Filter(filter_op)),
+                None => {
+                    graph.remove_node(node_id, false);
+                }
+            }
+            predicates.extend(correlated);
+
+            Some(predicates)
+        }
+        Operator::Project(op) => {
+            if op.exprs.iter().any(is_correlated) {
+                return None;
+            }
+            let predicates = fn_pull_up_child(graph)?;
+            let Operator::Project(op) = graph.operator_mut(node_id) else {
+                unreachable!()
+            };
+            for column in inner_columns(&predicates, outer_columns) {
+                if !op
+                    .exprs
+                    .iter()
+                    .any(|expr| expr.output_column().summary() == column.summary())
+                {
+                    op.exprs.push(ScalarExpression::ColumnRef(column));
+                }
+            }
+
+            Some(predicates)
+        }
+        Operator::Aggregate(op) => {
+            if op
+                .agg_calls
+                .iter()
+                .chain(op.groupby_exprs.iter())
+                .any(is_correlated)
+            {
+                return None;
+            }
+            let has_count = op.agg_calls.iter().any(|expr| {
+                matches!(
+                    expr,
+                    ScalarExpression::AggCall {
+                        kind: AggKind::Count,
+                        ..
+                    }
+                )
+            });
+            let predicates = fn_pull_up_child(graph)?;
+            if predicates.is_empty() {
+                return Some(predicates);
+            }
+            // the count of an empty group is 0 rather than missing
+            if has_count {
+                return None;
+            }
+            let mut group_keys = Vec::with_capacity(predicates.len());
+
+            for predicate in predicates.iter() {
+                match join_keys(predicate.clone(), outer_columns) {
+                    Ok((_, inner_expr @ ScalarExpression::ColumnRef(_))) => {
+                        group_keys.push(inner_expr)
+                    }
+                    _ => return None,
+                }
+            }
+            let Operator::Aggregate(op) = graph.operator_mut(node_id) else {
+                unreachable!()
+            };
+            for group_key in group_keys {
+                if !op.groupby_exprs.contains(&group_key) {
+                    op.groupby_exprs.push(group_key);
+                }
+            }
+
+            Some(predicates)
+        }
+        Operator::Sort(op) => {
+            if op
+                .sort_fields
+                .iter()
+                .any(|field| is_correlated(&field.expr))
+            {
+                return None;
+            }
+            fn_pull_up_child(graph)
+        }
+        _ => {
+            let is_correlated = graph
+                .nodes_iter(HepMatchOrder::TopDown, Some(node_id))
+                .any(|id| {
+                    graph
+                        .operator(id)
+                        .referenced_columns(true)
+                        .iter()
+                        .any(|column| contains(outer_columns, column))
+                });
+
+            (!is_correlated).then(Vec::new)
+        }
+    }
+}
+
+/// Splits an equality between an outer expression and an inner one into the keys of the join.
+fn join_keys(
+    predicate: ScalarExpression,
+    outer_columns: &[ColumnRef],
+) -> Result<(ScalarExpression, ScalarExpression), ScalarExpression> {
+    let is_outer = |expr: &ScalarExpression| {
+        let columns = expr.referenced_columns(true);
+
+        !columns.is_empty() && columns.iter().all(|column| contains(outer_columns, column))
+    };
+    let is_inner = |expr: &ScalarExpression| {
+        let columns = expr.referenced_columns(true);
+
+        !columns.is_empty() && !columns.iter().any(|column| contains(outer_columns, column))
+    };
+
+    match predicate {
+        ScalarExpression::Binary {
+            op: BinaryOperator::Eq,
+            left_expr,
+            right_expr,
+            ..
+        } if is_outer(&left_expr) && is_inner(&right_expr) => Ok((*left_expr, *right_expr)),
+        ScalarExpression::Binary {
+            op: BinaryOperator::Eq,
+            left_expr,
+            right_expr,
+            ..
+        } if is_inner(&left_expr) && is_outer(&right_expr) => Ok((*right_expr, *left_expr)),
+        predicate => Err(predicate),
+    }
+}
+
+/// The columns of the pulled predicates that come from the subquery.
+fn inner_columns(predicates: &[ScalarExpression], outer_columns: &[ColumnRef]) -> Vec<ColumnRef> {
+    predicates
+        .iter()
+        .flat_map(|predicate| predicate.referenced_columns(true))
+        .filter(|column| !contains(outer_columns, column))
+        .unique_by(|column| column.summary().clone())
+        .collect_vec()
+}
+
+/// The columns referenced in the subtree of `node_id`, or only the columns read by its scans.
+fn subtree_columns(graph: &HepGraph, node_id: HepNodeId, only_scan: bool) -> Vec<ColumnRef> {
+    graph
+        .nodes_iter(HepMatchOrder::TopDown, Some(node_id))
+        .filter(|id| {
+            !only_scan
+                || matches!(
+                    graph.operator(*id),
+                    Operator::TableScan(_) | Operator::Values(_)
+                )
+        })
+        .flat_map(|id| graph.operator(id).referenced_columns(true))
+        .collect_vec()
+}
+
+fn contains(columns: &[ColumnRef], column: &ColumnRef) -> bool {
+    columns
+        .iter()
+        .any(|other| other.summary() == column.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator};
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn decorrelate(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        HepOptimizer::new(select_sql_run(sql)?)
+            .batch(
+                "test_decorrelate_join".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::DecorrelateJoin],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    fn find_join(plan: &LogicalPlan) -> Option<(&JoinOperator, &LogicalPlan)> {
+        match &plan.operator {
+            Operator::Join(op) => Some((op, &plan.childrens[1])),
+            _ => plan.childrens.iter().find_map(find_join),
+        }
+    }
+
+    fn operators(plan: &LogicalPlan) -> Vec<&Operator> {
+        let mut operators = vec![&plan.operator];
+
+        for child in plan.childrens.iter() {
+            operators.append(&mut self::operators(child));
+        }
+        operators
+    }
+
+    #[test]
+    fn test_decorrelate_aggregate() -> Result<(), DatabaseError> {
+        let plan =
+            decorrelate("select * from t1 where c2 > (select avg(c4) from t2 where c3 = c1)")?;
+        let (join_op, subquery) = find_join(&plan).unwrap();
+
+        let JoinCondition::On { on, filter } = &join_op.on else {
+            unreachable!()
+        };
+        debug_assert_eq!(on.len(), 1);
+        debug_assert_eq!(on[0].0.output_name(), "t1.c1");
+        debug_assert_eq!(on[0].1.output_name(), "t2.c3");
+        debug_assert!(filter.is_some());
+
+        let operators = operators(subquery);
+        debug_assert!(!operators.iter().any(|op| matches!(op, Operator::Filter(_))));
+        debug_assert!(operators.iter().any(|op| matches!(
+            op,
+            Operator::Aggregate(agg_op) if agg_op.groupby_exprs.len() == 1
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decorrelate_in_subquery() -> Result<(), DatabaseError> {
+        let plan = decorrelate(
+            "select * from t1 where c2 in (select c4 from t2 where c3 = c1 and c4 > 1)",
+        )?;
+        let (join_op, subquery) = find_join(&plan).unwrap();
+
+        let JoinCondition::On { on, .. } = &join_op.on else {
+            unreachable!()
+        };
+        debug_assert_eq!(on.len(), 2);
+        // the uncorrelated conjunct is kept in the subquery
+        debug_assert!(operators(subquery).iter().any(|op| matches!(
+            op,
+            Operator::Filter(filter_op) if filter_op.predicate.output_name() == "(t2.c4 > 1)"
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_count_correlated() -> Result<(), DatabaseError> {
+        let plan =
+            decorrelate("select * from t1 where c2 > (select count(*) from t2 where c3 = c1)")?;
+        let (join_op, subquery) = find_join(&plan).unwrap();
+
+        let JoinCondition::On { on, .. } = &join_op.on else {
+            unreachable!()
+        };
+        debug_assert!(on.is_empty());
+        debug_assert!(operators(subquery)
+            .iter()
+            .any(|op| matches!(op, Operator::Filter(_))));
+
+        Ok(())
+    }
+}
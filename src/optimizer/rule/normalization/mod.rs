@@ -5,65 +5,101 @@ use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::optimizer::rule::normalization::column_pruning::ColumnPruning;
 use crate::optimizer::rule::normalization::combine_operators::{
-    CollapseGroupByAgg, CollapseProject, CombineFilter,
+    CollapseGroupByAgg, CollapseProject, CombineFilter, CombineSortLimit,
 };
+use crate::optimizer::rule::normalization::common_subexpression::EliminateCommonSubexpression;
 use crate::optimizer::rule::normalization::compilation_in_advance::{
     EvaluatorBind, ExpressionRemapper,
 };
+use crate::optimizer::rule::normalization::decorrelation::DecorrelateJoin;
 use crate::optimizer::rule::normalization::pushdown_limit::{
     LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
 };
+use crate::optimizer::rule::normalization::pushdown_predicates::InferPredicatesThroughJoin;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoScan;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateThroughJoin;
 use crate::optimizer::rule::normalization::simplification::ConstantCalculation;
 use crate::optimizer::rule::normalization::simplification::SimplifyFilter;
+use crate::optimizer::rule::normalization::simplification::SimplifyOuterJoin;
+use std::sync::Arc;
 
 mod column_pruning;
 mod combine_operators;
+mod common_subexpression;
 mod compilation_in_advance;
+mod decorrelation;
 mod pushdown_limit;
-mod pushdown_predicates;
+pub(crate) mod pushdown_predicates;
 mod simplification;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum NormalizationRuleImpl {
+    // Unnesting subqueries
+    DecorrelateJoin,
     ColumnPruning,
     // Combine operators
     CollapseProject,
     CollapseGroupByAgg,
     CombineFilter,
+    CombineSortLimit,
     // PushDown limit
     LimitProjectTranspose,
     PushLimitThroughJoin,
     PushLimitIntoTableScan,
     // PushDown predicates
+    SimplifyOuterJoin,
+    InferPredicatesThroughJoin,
     PushPredicateThroughJoin,
     // Tips: need to be used with `SimplifyFilter`
     PushPredicateIntoScan,
     // Simplification
     SimplifyFilter,
     ConstantCalculation,
+    // Common subexpression elimination
+    EliminateCommonSubexpression,
     // CompilationInAdvance
     ExpressionRemapper,
     EvaluatorBind,
+    // Registered by the embedder with its name
+    Custom(String, Arc<dyn NormalizationRule + Send + Sync>),
+}
+
+impl NormalizationRuleImpl {
+    /// The name to disable the rule by, which is the name of the variant for the builtin rules.
+    pub fn name(&self) -> String {
+        match self {
+            NormalizationRuleImpl::Custom(name, _) => name.clone(),
+            rule => format!("{:?}", rule),
+        }
+    }
 }
 
 impl MatchPattern for NormalizationRuleImpl {
     fn pattern(&self) -> &Pattern {
         match self {
+            NormalizationRuleImpl::DecorrelateJoin => DecorrelateJoin.pattern(),
             NormalizationRuleImpl::ColumnPruning => ColumnPruning.pattern(),
             NormalizationRuleImpl::CollapseProject => CollapseProject.pattern(),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.pattern(),
             NormalizationRuleImpl::CombineFilter => CombineFilter.pattern(),
+            NormalizationRuleImpl::CombineSortLimit => CombineSortLimit.pattern(),
             NormalizationRuleImpl::LimitProjectTranspose => LimitProjectTranspose.pattern(),
             NormalizationRuleImpl::PushLimitThroughJoin => PushLimitThroughJoin.pattern(),
             NormalizationRuleImpl::PushLimitIntoTableScan => PushLimitIntoScan.pattern(),
+            NormalizationRuleImpl::SimplifyOuterJoin => SimplifyOuterJoin.pattern(),
+            NormalizationRuleImpl::InferPredicatesThroughJoin => {
+                InferPredicatesThroughJoin.pattern()
+            }
             NormalizationRuleImpl::PushPredicateThroughJoin => PushPredicateThroughJoin.pattern(),
             NormalizationRuleImpl::PushPredicateIntoScan => PushPredicateIntoScan.pattern(),
             NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.pattern(),
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.pattern(),
+            NormalizationRuleImpl::EliminateCommonSubexpression => {
+                EliminateCommonSubexpression.pattern()
+            }
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.pattern(),
             NormalizationRuleImpl::EvaluatorBind => EvaluatorBind.pattern(),
+            NormalizationRuleImpl::Custom(_, rule) => rule.pattern(),
         }
     }
 }
@@ -71,10 +107,12 @@ impl MatchPattern for NormalizationRuleImpl {
 impl NormalizationRule for NormalizationRuleImpl {
     fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
         match self {
+            NormalizationRuleImpl::DecorrelateJoin => DecorrelateJoin.apply(node_id, graph),
             NormalizationRuleImpl::ColumnPruning => ColumnPruning.apply(node_id, graph),
             NormalizationRuleImpl::CollapseProject => CollapseProject.apply(node_id, graph),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.apply(node_id, graph),
             NormalizationRuleImpl::CombineFilter => CombineFilter.apply(node_id, graph),
+            NormalizationRuleImpl::CombineSortLimit => CombineSortLimit.apply(node_id, graph),
             NormalizationRuleImpl::LimitProjectTranspose => {
                 LimitProjectTranspose.apply(node_id, graph)
             }
@@ -84,6 +122,10 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::PushLimitIntoTableScan => {
                 PushLimitIntoScan.apply(node_id, graph)
             }
+            NormalizationRuleImpl::SimplifyOuterJoin => SimplifyOuterJoin.apply(node_id, graph),
+            NormalizationRuleImpl::InferPredicatesThroughJoin => {
+                InferPredicatesThroughJoin.apply(node_id, graph)
+            }
             NormalizationRuleImpl::PushPredicateThroughJoin => {
                 PushPredicateThroughJoin.apply(node_id, graph)
             }
@@ -92,8 +134,12 @@ impl NormalizationRule for NormalizationRuleImpl {
                 PushPredicateIntoScan.apply(node_id, graph)
             }
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateCommonSubexpression => {
+                EliminateCommonSubexpression.apply(node_id, graph)
+            }
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.apply(node_id, graph),
             NormalizationRuleImpl::EvaluatorBind => EvaluatorBind.apply(node_id, graph),
+            NormalizationRuleImpl::Custom(_, rule) => rule.apply(node_id, graph),
         }
     }
 }
@@ -1,18 +1,20 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
-use crate::expression::range_detacher::RangeDetacher;
+use crate::expression::range_detacher::{Range, RangeDetacher};
 use crate::expression::{BinaryOperator, ScalarExpression};
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::filter::FilterOperator;
-use crate::planner::operator::join::JoinType;
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
-use crate::types::index::{IndexInfo, IndexType};
+use crate::types::index::{IndexInfo, IndexMergeInfo, IndexMeta, IndexType};
 use crate::types::LogicalType;
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use std::collections::Bound;
 
 lazy_static! {
     static ref PUSH_PREDICATE_THROUGH_JOIN: Pattern = {
@@ -47,7 +49,7 @@ lazy_static! {
     };
 }
 
-fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
+pub(crate) fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
     match expr {
         ScalarExpression::Binary {
             op: BinaryOperator::And,
@@ -62,9 +64,27 @@ fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression
     }
 }
 
+fn split_disjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
+    match expr {
+        ScalarExpression::Binary {
+            op: BinaryOperator::Or,
+            left_expr,
+            right_expr,
+            ..
+        } => split_disjunctive_predicates(left_expr)
+            .into_iter()
+            .chain(split_disjunctive_predicates(right_expr))
+            .collect_vec(),
+        _ => vec![expr.clone()],
+    }
+}
+
 /// reduce filters into a filter, and then build a new LogicalFilter node with input child.
 /// if filters is empty, return the input child.
-fn reduce_filters(filters: Vec<ScalarExpression>, having: bool) -> Option<FilterOperator> {
+pub(crate) fn reduce_filters(
+    filters: Vec<ScalarExpression>,
+    having: bool,
+) -> Option<FilterOperator> {
     filters
         .into_iter()
         .reduce(|a, b| ScalarExpression::Binary {
@@ -206,6 +226,194 @@ impl NormalizationRule for PushPredicateThroughJoin {
     }
 }
 
+/// Infers the comparisons with constants on the other side of the equal join keys, e.g.
+/// `t2.a = 5` from `t1.a = t2.a` and `t1.a = 5`, so that the other side can also be filtered
+/// before the join and scanned by its indexes.
+///
+/// The inferred predicates are added below the join, to the inputs whose tuples are not kept by
+/// the join when they don't match.
+pub struct InferPredicatesThroughJoin;
+
+impl InferPredicatesThroughJoin {
+    /// Whether the tuples of the input may be filtered by the predicates inferred from the join
+    /// keys.
+    fn can_infer(join_type: JoinType, is_left: bool) -> bool {
+        match join_type {
+            JoinType::Inner | JoinType::LeftSemi => true,
+            JoinType::LeftOuter | JoinType::LeftAnti => !is_left,
+            JoinType::RightOuter => is_left,
+            JoinType::Full | JoinType::Cross => false,
+        }
+    }
+
+    fn add_filters(
+        graph: &mut HepGraph,
+        join_id: HepNodeId,
+        child_id: HepNodeId,
+        predicates: Vec<ScalarExpression>,
+    ) {
+        match graph.operator(child_id) {
+            Operator::Filter(op) if !op.having => {
+                let mut filters = split_conjunctive_predicates(&op.predicate);
+                let len = filters.len();
+
+                for predicate in predicates {
+                    if !filters.contains(&predicate) {
+                        filters.push(predicate);
+                    }
+                }
+                if filters.len() > len {
+                    if let Some(filter_op) = reduce_filters(filters, false) {
+                        graph.replace_node(child_id, Operator::Filter(filter_op));
+                    }
+                }
+            }
+            _ => {
+                if let Some(filter_op) = reduce_filters(predicates, false) {
+                    graph.add_node(join_id, Some(child_id), Operator::Filter(filter_op));
+                }
+            }
+        }
+    }
+}
+
+impl MatchPattern for InferPredicatesThroughJoin {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_PREDICATE_THROUGH_JOIN
+    }
+}
+
+impl NormalizationRule for InferPredicatesThroughJoin {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Some(join_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let (Operator::Filter(filter_op), Operator::Join(join_op)) =
+            (graph.operator(node_id), graph.operator(join_id))
+        else {
+            return Ok(());
+        };
+        let JoinCondition::On { on, .. } = &join_op.on else {
+            return Ok(());
+        };
+        let join_type = join_op.join_type;
+        let equal_columns = on
+            .iter()
+            .filter_map(|(left_expr, right_expr)| match (left_expr, right_expr) {
+                (ScalarExpression::ColumnRef(left), ScalarExpression::ColumnRef(right))
+                    if left.datatype() == right.datatype() =>
+                {
+                    Some((left.clone(), right.clone()))
+                }
+                _ => None,
+            })
+            .collect_vec();
+        if equal_columns.is_empty() {
+            return Ok(());
+        }
+        let mut predicates = split_conjunctive_predicates(&filter_op.predicate)
+            .into_iter()
+            .filter(|expr| compared_column(expr).is_some())
+            .collect_vec();
+        let given_len = predicates.len();
+        let mut inferred = (Vec::new(), Vec::new());
+
+        // the equal keys may be chained, e.g. `t1.a = t2.a AND t1.b = t2.a`
+        let mut i = 0;
+        while i < predicates.len() {
+            let column = compared_column(&predicates[i]).cloned();
+
+            for (left, right) in equal_columns.iter() {
+                for (from, to, is_left) in [(left, right, false), (right, left, true)] {
+                    if column.as_ref().map(|column| column.summary()) != Some(from.summary()) {
+                        continue;
+                    }
+                    let predicate = replace_compared_column(&predicates[i], to);
+
+                    if predicates.contains(&predicate) {
+                        continue;
+                    }
+                    if Self::can_infer(join_type, is_left) {
+                        if is_left {
+                            inferred.0.push(predicate.clone());
+                        } else {
+                            inferred.1.push(predicate.clone());
+                        }
+                    }
+                    predicates.push(predicate);
+                }
+            }
+            i += 1;
+        }
+        if predicates.len() == given_len {
+            return Ok(());
+        }
+        let join_childs = graph.children_at(join_id).collect_vec();
+
+        if !inferred.0.is_empty() {
+            Self::add_filters(graph, join_id, join_childs[0], inferred.0);
+        }
+        if !inferred.1.is_empty() {
+            Self::add_filters(graph, join_id, join_childs[1], inferred.1);
+        }
+
+        Ok(())
+    }
+}
+
+/// The column of a comparison between a column and a constant.
+fn compared_column(expr: &ScalarExpression) -> Option<&ColumnRef> {
+    match expr {
+        ScalarExpression::Binary {
+            op:
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq,
+            left_expr,
+            right_expr,
+            ..
+        } => match (left_expr.as_ref(), right_expr.as_ref()) {
+            (ScalarExpression::ColumnRef(column), ScalarExpression::Constant(_))
+            | (ScalarExpression::Constant(_), ScalarExpression::ColumnRef(column)) => Some(column),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn replace_compared_column(expr: &ScalarExpression, column: &ColumnRef) -> ScalarExpression {
+    let mut expr = expr.clone();
+
+    if let ScalarExpression::Binary {
+        left_expr,
+        right_expr,
+        evaluator,
+        ..
+    } = &mut expr
+    {
+        for child_expr in [left_expr, right_expr] {
+            if matches!(child_expr.as_ref(), ScalarExpression::ColumnRef(_)) {
+                *child_expr = Box::new(ScalarExpression::ColumnRef(column.clone()));
+            }
+        }
+        *evaluator = None;
+    }
+    expr
+}
+
+/// Whether the filter implies the predicate of a partial index, that is, each conjunct of the
+/// predicate is also a conjunct of the filter.
+fn is_implied(predicate: &ScalarExpression, filter: &ScalarExpression) -> bool {
+    let filters = split_conjunctive_predicates(filter);
+
+    split_conjunctive_predicates(predicate)
+        .iter()
+        .all(|expr| filters.contains(expr))
+}
+
 pub struct PushPredicateIntoScan;
 
 impl MatchPattern for PushPredicateIntoScan {
@@ -219,42 +427,7 @@ impl NormalizationRule for PushPredicateIntoScan {
         if let Operator::Filter(op) = graph.operator(node_id).clone() {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::TableScan(child_op) = graph.operator_mut(child_id) {
-                    //FIXME: now only support `unique` and `primary key`
-                    for IndexInfo { meta, range } in &mut child_op.index_infos {
-                        if range.is_some() {
-                            continue;
-                        }
-                        *range = match meta.ty {
-                            IndexType::PrimaryKey | IndexType::Unique | IndexType::Normal => {
-                                RangeDetacher::new(meta.table_name.as_str(), &meta.column_ids[0])
-                                    .detach(&op.predicate)
-                            }
-                            IndexType::Composite => {
-                                let mut res = None;
-                                let mut eq_ranges = Vec::with_capacity(meta.column_ids.len());
-
-                                for column_id in meta.column_ids.iter() {
-                                    if let Some(range) =
-                                        RangeDetacher::new(meta.table_name.as_str(), column_id)
-                                            .detach(&op.predicate)
-                                    {
-                                        if range.only_eq() {
-                                            eq_ranges.push(range);
-                                            continue;
-                                        }
-                                        res = range.combining_eqs(&eq_ranges);
-                                    }
-                                    break;
-                                }
-                                if res.is_none() {
-                                    if let Some(range) = eq_ranges.pop() {
-                                        res = range.combining_eqs(&eq_ranges);
-                                    }
-                                }
-                                res
-                            }
-                        }
-                    }
+                    push_predicate_into_scan(&op.predicate, child_op);
                 }
             }
         }
@@ -263,6 +436,146 @@ impl NormalizationRule for PushPredicateIntoScan {
     }
 }
 
+/// Detaches the ranges of the indexes of the scan from the predicate of the filter above it,
+/// which also recomputes them when a cached plan is re-bound to other literals.
+pub(crate) fn push_predicate_into_scan(
+    predicate: &ScalarExpression,
+    scan_op: &mut TableScanOperator,
+) {
+    //FIXME: now only support `unique` and `primary key`
+    for IndexInfo { meta, range, .. } in &mut scan_op.index_infos {
+        if range.is_some() {
+            continue;
+        }
+        // the partial index only has the tuples satisfying its predicate
+        if let Some(index_predicate) = &meta.predicate {
+            if !is_implied(index_predicate, predicate) {
+                continue;
+            }
+        }
+        *range = detach_range(meta, predicate);
+        // scanning the whole partial index may still read fewer tuples than the table
+        if range.is_none() && meta.predicate.is_some() {
+            *range = Some(Range::Scope {
+                min: Bound::Unbounded,
+                max: Bound::Unbounded,
+            });
+        }
+    }
+    if scan_op.index_merges.is_empty() {
+        scan_op.index_merges = index_merges(&scan_op.index_infos, predicate);
+    }
+}
+
+fn detach_range(meta: &IndexMeta, predicate: &ScalarExpression) -> Option<Range> {
+    let table_name = meta.table_name.as_str();
+    let mut detachers = match &meta.exprs {
+        Some(exprs) => exprs
+            .iter()
+            .map(|expr| RangeDetacher::with_expr(table_name, expr))
+            .collect_vec(),
+        None => meta
+            .column_ids
+            .iter()
+            .map(|column_id| RangeDetacher::new(table_name, column_id))
+            .collect_vec(),
+    };
+    match meta.ty {
+        IndexType::PrimaryKey | IndexType::Unique | IndexType::Normal => {
+            detachers[0].detach(predicate)
+        }
+        IndexType::Composite => {
+            let mut res = None;
+            let mut eq_ranges = Vec::with_capacity(detachers.len());
+
+            for detacher in detachers.iter_mut() {
+                if let Some(range) = detacher.detach(predicate) {
+                    if range.only_eq() {
+                        eq_ranges.push(range);
+                        continue;
+                    }
+                    res = range.combining_eqs(&eq_ranges);
+                }
+                break;
+            }
+            if res.is_none() {
+                if let Some(range) = eq_ranges.pop() {
+                    res = range.combining_eqs(&eq_ranges);
+                }
+            }
+            res
+        }
+    }
+}
+
+/// Builds the index merges of the predicate:
+/// 1. Union: each disjunct of the predicate has the range of an index, but no index has a range
+/// for the whole predicate, e.g. `a = 1 OR b = 2` with the indexes on `a` and `b`.
+/// 2. Intersection: several secondary indexes have the ranges, e.g. `a = 1 AND b = 2`.
+fn index_merges(index_infos: &[IndexInfo], predicate: &ScalarExpression) -> Vec<IndexMergeInfo> {
+    let mut index_merges = Vec::new();
+    let ranged_infos = index_infos
+        .iter()
+        .filter(|info| info.range.is_some() && info.meta.ty != IndexType::PrimaryKey)
+        .cloned()
+        .collect_vec();
+
+    if ranged_infos.len() > 1 {
+        index_merges.push(IndexMergeInfo {
+            index_infos: ranged_infos,
+            is_union: false,
+        });
+    }
+    if index_infos.iter().all(|info| info.range.is_none()) {
+        // the disjuncts looked up by the same index are detached together
+        let mut disjuncts_by_index: Vec<(usize, Vec<ScalarExpression>)> = Vec::new();
+
+        for disjunct in split_disjunctive_predicates(predicate) {
+            let Some(position) = index_infos.iter().position(|info| {
+                info.meta.predicate.is_none() && detach_range(&info.meta, &disjunct).is_some()
+            }) else {
+                return index_merges;
+            };
+            match disjuncts_by_index.iter_mut().find(|(i, _)| *i == position) {
+                Some((_, disjuncts)) => disjuncts.push(disjunct),
+                None => disjuncts_by_index.push((position, vec![disjunct])),
+            }
+        }
+        if disjuncts_by_index.len() > 1 {
+            let union_infos = disjuncts_by_index
+                .into_iter()
+                .map(|(position, disjuncts)| {
+                    let meta = index_infos[position].meta.clone();
+                    let predicate = disjuncts
+                        .into_iter()
+                        .reduce(|a, b| ScalarExpression::Binary {
+                            op: BinaryOperator::Or,
+                            left_expr: Box::new(a),
+                            right_expr: Box::new(b),
+                            evaluator: None,
+                            ty: LogicalType::Boolean,
+                        })
+                        .unwrap();
+                    let range = detach_range(&meta, &predicate);
+
+                    IndexInfo {
+                        meta,
+                        range,
+                        covered: false,
+                    }
+                })
+                .collect_vec();
+
+            index_merges.push(IndexMergeInfo {
+                index_infos: union_infos,
+                is_union: true,
+            });
+        }
+    }
+
+    index_merges
+}
+
 #[cfg(test)]
 mod tests {
     use crate::binder::test::select_sql_run;
@@ -441,4 +754,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_infer_predicates_through_join() -> Result<(), DatabaseError> {
+        let plan = select_sql_run("select * from t1 left join t2 on c1 = c3 where c1 = 1")?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_infer_predicates_through_join".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::InferPredicatesThroughJoin,
+                    NormalizationRuleImpl::PushPredicateThroughJoin,
+                ],
+            )
+            .find_best::<RocksTransaction>(None)?;
+
+        let join_plan = &best_plan.childrens[0];
+        debug_assert!(matches!(join_plan.operator, Operator::Join(_)));
+
+        for (child, column_name) in join_plan.childrens.iter().zip(["c1", "c3"]) {
+            if let Operator::Filter(op) = &child.operator {
+                match &op.predicate {
+                    ScalarExpression::Binary {
+                        op: BinaryOperator::Eq,
+                        left_expr,
+                        right_expr,
+                        ..
+                    } => {
+                        debug_assert!(matches!(
+                            left_expr.as_ref(),
+                            ScalarExpression::ColumnRef(column) if column.name() == column_name
+                        ));
+                        debug_assert_eq!(
+                            right_expr.as_ref(),
+                            &ScalarExpression::Constant(Arc::new(DataValue::Int32(Some(1))))
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            } else {
+                unreachable!("Should be a filter operator")
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -1,8 +1,13 @@
+use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::expression::{BinaryOperator, ScalarExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::batch::HepMatchOrder;
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
-use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::sort::SortOperator;
+use crate::planner::operator::top_n::TopNOperator;
 use crate::planner::operator::Operator;
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -13,6 +18,15 @@ lazy_static! {
             children: PatternChildrenPredicate::None,
         }
     };
+    static ref SIMPLIFY_OUTER_JOIN_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Filter(_)),
+            children: PatternChildrenPredicate::Predicate(vec![Pattern {
+                predicate: |op| matches!(op, Operator::Join(_)),
+                children: PatternChildrenPredicate::None,
+            }]),
+        }
+    };
     static ref SIMPLIFY_FILTER_RULE: Pattern = {
         Pattern {
             predicate: |op| matches!(op, Operator::Filter(_)),
@@ -56,8 +70,9 @@ impl ConstantCalculation {
                     expr.constant_calculation()?;
                 }
             }
-            Operator::Sort(op) => {
-                for field in &mut op.sort_fields {
+            Operator::Sort(SortOperator { sort_fields, .. })
+            | Operator::TopN(TopNOperator { sort_fields, .. }) => {
+                for field in sort_fields {
                     field.expr.constant_calculation()?;
                 }
             }
@@ -109,6 +124,144 @@ impl NormalizationRule for SimplifyFilter {
     }
 }
 
+/// Turns an outer join into an inner join when the filter above it rejects the tuples padded with
+/// nulls, e.g. `t1 LEFT JOIN t2 ON t1.a = t2.a WHERE t2.b > 1`. A full join becomes a left or right
+/// join when the nulls of only one side are rejected.
+#[derive(Copy, Clone)]
+pub struct SimplifyOuterJoin;
+
+impl SimplifyOuterJoin {
+    /// Whether the predicate is not true when all the columns are null.
+    fn is_null_rejecting(predicate: &ScalarExpression, columns: &[ColumnRef]) -> bool {
+        match predicate {
+            ScalarExpression::Alias { expr, .. } => Self::is_null_rejecting(expr, columns),
+            ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::is_null_rejecting(left_expr, columns)
+                    || Self::is_null_rejecting(right_expr, columns)
+            }
+            ScalarExpression::Binary {
+                op: BinaryOperator::Or,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::is_null_rejecting(left_expr, columns)
+                    && Self::is_null_rejecting(right_expr, columns)
+            }
+            ScalarExpression::IsNull {
+                negated: true,
+                expr,
+            }
+            | ScalarExpression::In {
+                negated: false,
+                expr,
+                ..
+            }
+            | ScalarExpression::Between {
+                negated: false,
+                expr,
+                ..
+            } => Self::is_null_propagating(expr, columns),
+            expr => Self::is_null_propagating(expr, columns),
+        }
+    }
+
+    /// Whether the expression is null when all the columns are null.
+    fn is_null_propagating(expr: &ScalarExpression, columns: &[ColumnRef]) -> bool {
+        match expr {
+            ScalarExpression::ColumnRef(column) => columns
+                .iter()
+                .any(|other| other.summary() == column.summary()),
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::Unary { expr, .. } => Self::is_null_propagating(expr, columns),
+            ScalarExpression::Binary {
+                op:
+                    BinaryOperator::Plus
+                    | BinaryOperator::Minus
+                    | BinaryOperator::Multiply
+                    | BinaryOperator::Divide
+                    | BinaryOperator::Modulo
+                    | BinaryOperator::Gt
+                    | BinaryOperator::Lt
+                    | BinaryOperator::GtEq
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::Like(_)
+                    | BinaryOperator::NotLike(_),
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::is_null_propagating(left_expr, columns)
+                    || Self::is_null_propagating(right_expr, columns)
+            }
+            _ => false,
+        }
+    }
+
+    fn subtree_columns(graph: &HepGraph, node_id: HepNodeId) -> Vec<ColumnRef> {
+        graph
+            .nodes_iter(HepMatchOrder::TopDown, Some(node_id))
+            .flat_map(|id| graph.operator(id).referenced_columns(false))
+            .collect_vec()
+    }
+}
+
+impl MatchPattern for SimplifyOuterJoin {
+    fn pattern(&self) -> &Pattern {
+        &SIMPLIFY_OUTER_JOIN_RULE
+    }
+}
+
+impl NormalizationRule for SimplifyOuterJoin {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Some(join_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let (Operator::Filter(filter_op), Operator::Join(join_op)) =
+            (graph.operator(node_id), graph.operator(join_id))
+        else {
+            return Ok(());
+        };
+        if !matches!(
+            join_op.join_type,
+            JoinType::LeftOuter | JoinType::RightOuter | JoinType::Full
+        ) {
+            return Ok(());
+        }
+        let join_childs = graph.children_at(join_id).collect_vec();
+        let fn_rejects = |child_id: HepNodeId| {
+            Self::is_null_rejecting(
+                &filter_op.predicate,
+                &Self::subtree_columns(graph, child_id),
+            )
+        };
+        let join_type = match join_op.join_type {
+            JoinType::LeftOuter if fn_rejects(join_childs[1]) => JoinType::Inner,
+            JoinType::RightOuter if fn_rejects(join_childs[0]) => JoinType::Inner,
+            JoinType::Full => match (fn_rejects(join_childs[0]), fn_rejects(join_childs[1])) {
+                (true, true) => JoinType::Inner,
+                (true, false) => JoinType::LeftOuter,
+                (false, true) => JoinType::RightOuter,
+                (false, false) => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let mut join_op = join_op.clone();
+        join_op.join_type = join_type;
+        graph.replace_node(join_id, Operator::Join(join_op));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::binder::test::select_sql_run;
@@ -119,6 +272,7 @@ mod test {
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::optimizer::HepOptimizer;
     use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::join::JoinType;
     use crate::planner::operator::Operator;
     use crate::planner::LogicalPlan;
     use crate::storage::rocksdb::RocksTransaction;
@@ -475,4 +629,45 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_simplify_outer_join() -> Result<(), DatabaseError> {
+        let fn_join_type = |sql: &str| -> Result<JoinType, DatabaseError> {
+            let plan = HepOptimizer::new(select_sql_run(sql)?)
+                .batch(
+                    "test_simplify_outer_join".to_string(),
+                    HepBatchStrategy::once_topdown(),
+                    vec![NormalizationRuleImpl::SimplifyOuterJoin],
+                )
+                .find_best::<RocksTransaction>(None)?;
+
+            match &plan.childrens[0].childrens[0].operator {
+                Operator::Join(op) => Ok(op.join_type),
+                _ => unreachable!("Should be a join operator"),
+            }
+        };
+
+        debug_assert_eq!(
+            fn_join_type("select * from t1 left join t2 on c1 = c3 where c4 + 1 > 2")?,
+            JoinType::Inner
+        );
+        debug_assert_eq!(
+            fn_join_type("select * from t1 left join t2 on c1 = c3 where c4 is null")?,
+            JoinType::LeftOuter
+        );
+        debug_assert_eq!(
+            fn_join_type("select * from t1 left join t2 on c1 = c3 where c2 > 1 or c4 > 1")?,
+            JoinType::LeftOuter
+        );
+        debug_assert_eq!(
+            fn_join_type("select * from t1 right join t2 on c1 = c3 where c2 in (1, 2)")?,
+            JoinType::Inner
+        );
+        debug_assert_eq!(
+            fn_join_type("select * from t1 full join t2 on c1 = c3 where c2 is not null")?,
+            JoinType::LeftOuter
+        );
+
+        Ok(())
+    }
 }
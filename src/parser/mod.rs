@@ -1,8 +1,197 @@
-use sqlparser::parser::ParserError;
-use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
+use sqlparser::ast::{AnalyzeFormat, Expr, Ident, Statement};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
 
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
+/// The clauses of a statement that sqlparser does not support, they are parsed by FnckSQL and
+/// bound together with the statement.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatementExtension {
+    /// `CREATE INDEX ... INCLUDE (column, ..)`
+    pub include: Vec<Ident>,
+    /// `CREATE INDEX ... WHERE predicate`
+    pub predicate: Option<Expr>,
+    /// `SELECT /*+ NAME(arg ..) .. */ ...`, the hints of all the `SELECT`s of the statement
+    pub hints: Vec<Hint>,
+}
+
+fn parse_create_index(
+    parser: &mut Parser,
+    unique: bool,
+) -> Result<(Statement, StatementExtension), ParserError> {
+    let statement = parser.parse_create_index(unique)?;
+    let mut extension = StatementExtension::default();
+
+    if matches!(parser.peek_token().token, Token::Word(word) if word.value.eq_ignore_ascii_case("INCLUDE"))
+    {
+        parser.next_token();
+        parser.expect_token(&Token::LParen)?;
+        extension.include = parser.parse_comma_separated(Parser::parse_identifier)?;
+        parser.expect_token(&Token::RParen)?;
+    }
+    if parser.parse_keyword(Keyword::WHERE) {
+        extension.predicate = Some(parser.parse_expr()?);
+    }
+    Ok((statement, extension))
+}
+
+/// Parses the PostgreSQL style options of `EXPLAIN (ANALYZE [boolean], FORMAT { TEXT | JSON |
+/// DOT }) statement` into the options that sqlparser parses without the parentheses, the
+/// parenthesized query that follows `EXPLAIN` is left to sqlparser.
+fn parse_explain(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let is_option = |token: Token| {
+        matches!(token, Token::Word(word) if ["ANALYZE", "FORMAT"]
+            .iter()
+            .any(|option| word.value.eq_ignore_ascii_case(option)))
+    };
+    if !(parser.peek_token() == Token::LParen && is_option(parser.peek_nth_token(1).token)) {
+        return parser.parse_explain(false);
+    }
+    parser.expect_token(&Token::LParen)?;
+    let mut analyze = false;
+    let mut format = None;
+
+    loop {
+        let option = parser.parse_identifier()?;
+
+        match option.value.to_uppercase().as_str() {
+            "ANALYZE" => {
+                analyze = !parser.parse_keyword(Keyword::FALSE);
+                let _ = parser.parse_keyword(Keyword::TRUE);
+            }
+            "FORMAT" => {
+                let token = parser.peek_token();
+                format = Some(
+                    match parser.parse_identifier()?.value.to_uppercase().as_str() {
+                        "TEXT" => AnalyzeFormat::TEXT,
+                        "JSON" => AnalyzeFormat::JSON,
+                        "DOT" | "GRAPHVIZ" => AnalyzeFormat::GRAPHVIZ,
+                        _ => return parser.expected("TEXT, JSON or DOT", token),
+                    },
+                );
+            }
+            _ => {
+                return Err(ParserError::ParserError(format!(
+                    "unrecognized EXPLAIN option: {}",
+                    option
+                )))
+            }
+        }
+        if !parser.consume_token(&Token::Comma) {
+            break;
+        }
+    }
+    parser.expect_token(&Token::RParen)?;
+
+    match parser.parse_statement()? {
+        Statement::Explain { .. } | Statement::ExplainTable { .. } => Err(
+            ParserError::ParserError("Explain must be root of the plan".to_string()),
+        ),
+        statement => Ok(Statement::Explain {
+            describe_alias: false,
+            analyze,
+            verbose: false,
+            statement: Box::new(statement),
+            format,
+        }),
+    }
+}
+
+/// An optimizer hint written in the comment that follows a `SELECT`, the name and the arguments
+/// are interpreted by the optimizer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hint {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+/// Collects the hints of every statement of `sql`, in the order of the statements.
+fn parse_hints(sql: &str) -> Result<Vec<Vec<Hint>>, ParserError> {
+    let tokens = Tokenizer::new(&DIALECT, sql).tokenize()?;
+    let mut statements_hints = vec![Vec::new()];
+    let mut is_empty = true;
+    let mut is_after_select = false;
+
+    for token in tokens {
+        match token {
+            Token::Whitespace(Whitespace::MultiLineComment(comment)) => {
+                if let (true, Some(hints)) = (is_after_select, comment.strip_prefix('+')) {
+                    statements_hints
+                        .last_mut()
+                        .unwrap()
+                        .append(&mut parse_hint_list(hints));
+                }
+            }
+            Token::Whitespace(_) => (),
+            // empty statements are skipped as `parse_sql_with_extensions` does
+            Token::SemiColon if !is_empty => {
+                statements_hints.push(Vec::new());
+                is_empty = true;
+                is_after_select = false;
+            }
+            Token::SemiColon => (),
+            token => {
+                is_empty = false;
+                is_after_select =
+                    matches!(token, Token::Word(word) if word.keyword == Keyword::SELECT);
+            }
+        }
+    }
+    Ok(statements_hints)
+}
+
+/// Parses `NAME NAME(arg, ..) NAME(arg arg ..)`, the arguments are identifiers separated by
+/// commas or spaces. Hints are only comments to the other databases, so the malformed rest of
+/// the hints is ignored with a warning instead of failing the statement.
+fn parse_hint_list(hints: &str) -> Vec<Hint> {
+    let mut hint_list = Vec::new();
+    let tokens = match Tokenizer::new(&DIALECT, hints).tokenize() {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            log::warn!("ignored the malformed hints `{}`: {}", hints.trim(), err);
+            return hint_list;
+        }
+    };
+    let mut tokens = tokens
+        .into_iter()
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .peekable();
+
+    while let Some(token) = tokens.next() {
+        let Token::Word(word) = token else {
+            log::warn!("ignored the hints after `{}`: expected a hint name", token);
+            break;
+        };
+        let mut args = Vec::new();
+
+        if tokens.next_if_eq(&Token::LParen).is_some() {
+            loop {
+                match tokens.next() {
+                    Some(Token::Word(word)) => args.push(word.value),
+                    Some(Token::Number(number, _)) => args.push(number),
+                    Some(Token::Comma) => (),
+                    Some(Token::RParen) => break,
+                    _ => {
+                        log::warn!(
+                            "ignored the hint `{}` without the closing parenthesis",
+                            word.value
+                        );
+                        return hint_list;
+                    }
+                }
+            }
+        }
+        hint_list.push(Hint {
+            name: word.value.to_uppercase(),
+            args,
+        });
+    }
+    hint_list
+}
+
 /// Parse a string to a collection of statements.
 ///
 /// # Example
@@ -18,3 +207,165 @@ const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 pub fn parse_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
     Parser::parse_sql(&DIALECT, sql.as_ref())
 }
+
+/// Parse a string to a collection of statements with the clauses that sqlparser does not
+/// support, see [`StatementExtension`].
+///
+/// # Example
+/// ```rust
+/// use kip_sql::parser::parse_sql_with_extensions;
+/// let sql = "CREATE INDEX i_b ON table_1 (b) INCLUDE (c) WHERE c > 0";
+/// let ast = parse_sql_with_extensions(sql).unwrap();
+/// println!("{:?}", ast);
+/// ```
+pub fn parse_sql_with_extensions<S: AsRef<str>>(
+    sql: S,
+) -> Result<Vec<(Statement, StatementExtension)>, ParserError> {
+    let mut parser = Parser::new(&DIALECT).try_with_sql(sql.as_ref())?;
+    let mut statements_hints = parse_hints(sql.as_ref())?.into_iter();
+    let mut stmts = Vec::new();
+    let mut expecting_statement_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+        if parser.peek_token() == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+        // the statements with extensions are parsed here, as `Dialect::parse_statement` would
+        // lose the PostgreSQL specific syntax that sqlparser checks by the type of the dialect
+        let (stmt, mut extension) = if parser.parse_keywords(&[Keyword::CREATE, Keyword::INDEX]) {
+            parse_create_index(&mut parser, false)?
+        } else if parser.parse_keywords(&[Keyword::CREATE, Keyword::UNIQUE, Keyword::INDEX]) {
+            parse_create_index(&mut parser, true)?
+        } else if parser.parse_keyword(Keyword::EXPLAIN) {
+            (parse_explain(&mut parser)?, StatementExtension::default())
+        } else {
+            (parser.parse_statement()?, StatementExtension::default())
+        };
+        extension.hints = statements_hints.next().unwrap_or_default();
+        stmts.push((stmt, extension));
+        expecting_statement_delimiter = true;
+    }
+    Ok(stmts)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{parse_sql, parse_sql_with_extensions, Hint};
+    use sqlparser::ast::{AnalyzeFormat, Ident, Statement};
+    use sqlparser::parser::ParserError;
+
+    #[test]
+    fn test_parse_create_index_include() -> Result<(), ParserError> {
+        let stmts = parse_sql_with_extensions(
+            "create unique index u_a on t1 (a) include (b, c); create index i_b on t1 (b)",
+        )?;
+
+        debug_assert_eq!(stmts.len(), 2);
+        debug_assert!(matches!(
+            stmts[0].0,
+            Statement::CreateIndex { unique: true, .. }
+        ));
+        debug_assert_eq!(stmts[0].1.include, vec![Ident::new("b"), Ident::new("c")]);
+        debug_assert!(matches!(
+            stmts[1].0,
+            Statement::CreateIndex { unique: false, .. }
+        ));
+        debug_assert!(stmts[1].1.include.is_empty());
+        debug_assert!(parse_sql_with_extensions("create index i_b on t1 (b) include b").is_err());
+        // the statements are still parsed without the extensions
+        debug_assert!(matches!(
+            parse_sql("create index i_b on t1 (b)")?[..],
+            [Statement::CreateIndex { unique: false, .. }]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_create_index_where() -> Result<(), ParserError> {
+        let stmts = parse_sql_with_extensions(
+            "create index i_b on t1 (lower(b)) include (c) where c > 0; create index i_c on t1 (c)",
+        )?;
+
+        debug_assert_eq!(stmts[0].1.include, vec![Ident::new("c")]);
+        debug_assert_eq!(
+            stmts[0].1.predicate.as_ref().map(|expr| expr.to_string()),
+            Some("c > 0".to_string())
+        );
+        debug_assert_eq!(stmts[1].1.predicate, None);
+        debug_assert!(parse_sql_with_extensions("create index i_b on t1 (b) where").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_hints() -> Result<(), ParserError> {
+        let hint = |name: &str, args: &[&str]| Hint {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        };
+        let stmts = parse_sql_with_extensions(
+            "select /*+ use_index(t1 i_a) LEADING(t2, t1) hash_join */ * from t1, t2;; \
+             select /* use_index(t1 i_a) */ * from t1 /*+ nl_join */; \
+             select a from (select /*+ no_index(t1) */ a from t1) /*+ nl_join(t1 */",
+        )?;
+
+        debug_assert_eq!(stmts.len(), 3);
+        debug_assert_eq!(
+            stmts[0].1.hints,
+            vec![
+                hint("USE_INDEX", &["t1", "i_a"]),
+                hint("LEADING", &["t2", "t1"]),
+                hint("HASH_JOIN", &[]),
+            ]
+        );
+        debug_assert!(stmts[1].1.hints.is_empty());
+        debug_assert_eq!(stmts[2].1.hints, vec![hint("NO_INDEX", &["t1"])]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_explain_options() -> Result<(), ParserError> {
+        let stmts = parse_sql_with_extensions(
+            "explain (format json) select * from t1; \
+             explain (analyze, format dot) select * from t1; \
+             explain analyze format json select * from t1; \
+             explain (analyze false) select * from t1; \
+             explain select * from t1",
+        )?;
+        let options = stmts
+            .iter()
+            .map(|(stmt, _)| match stmt {
+                Statement::Explain {
+                    analyze, format, ..
+                } => (*analyze, *format),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        debug_assert_eq!(
+            options,
+            vec![
+                (false, Some(AnalyzeFormat::JSON)),
+                (true, Some(AnalyzeFormat::GRAPHVIZ)),
+                (true, Some(AnalyzeFormat::JSON)),
+                (false, None),
+                (false, None),
+            ]
+        );
+        debug_assert!(parse_sql_with_extensions("explain (format yaml) select * from t1").is_err());
+        debug_assert!(parse_sql_with_extensions("explain (costs) select * from t1").is_err());
+        debug_assert!(
+            parse_sql_with_extensions("explain (format json) explain select * from t1").is_err()
+        );
+
+        Ok(())
+    }
+}
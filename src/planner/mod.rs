@@ -1,12 +1,18 @@
 pub mod operator;
+pub(crate) mod plan_cache;
 
 use crate::catalog::{ColumnCatalog, TableName};
+use crate::expression::range_detacher::Range;
+use crate::optimizer::core::cost_model::Estimate;
 use crate::planner::operator::join::JoinType;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
+use crate::types::index::{IndexInfo, IndexMetaRef};
 use crate::types::tuple::SchemaRef;
+use crate::types::value::DataValue;
 use itertools::Itertools;
+use std::collections::Bound;
 use std::sync::Arc;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -14,6 +20,7 @@ pub struct LogicalPlan {
     pub(crate) operator: Operator,
     pub(crate) childrens: Vec<LogicalPlan>,
     pub(crate) physical_option: Option<PhysicalOption>,
+    pub(crate) estimate: Option<Estimate>,
 
     pub(crate) _output_schema_ref: Option<SchemaRef>,
 }
@@ -24,6 +31,7 @@ impl LogicalPlan {
             operator,
             childrens,
             physical_option: None,
+            estimate: None,
             _output_schema_ref: None,
         }
     }
@@ -32,6 +40,54 @@ impl LogicalPlan {
         self.childrens.get(index)
     }
 
+    /// Reverses the table scan that the plan reads in order, see
+    /// [`PhysicalProperties::is_reversible`].
+    ///
+    /// [`PhysicalProperties::is_reversible`]: crate::optimizer::core::memo::PhysicalProperties::is_reversible
+    pub(crate) fn reverse_scan(&mut self) {
+        match &mut self.operator {
+            Operator::TableScan(op) => op.is_reverse = true,
+            Operator::Filter(_) | Operator::Project(_) => self.childrens[0].reverse_scan(),
+            operator => unreachable!("the order of {} is not reversible", operator),
+        }
+    }
+
+    /// Makes the table scan read only the first key of the index, or the last one with
+    /// `is_reverse`, which is the minimum or the maximum of its leading column. The NULLs are
+    /// skipped since they are in front of the keys.
+    pub(crate) fn seek_index(&mut self, index_meta: IndexMetaRef, is_reverse: bool) {
+        let Operator::TableScan(op) = &mut self.operator else {
+            unreachable!("only the table scan can seek the index")
+        };
+        let is_nullable = op
+            .columns
+            .iter()
+            .any(|(_, column)| column.id() == Some(index_meta.column_ids[0]) && column.nullable);
+        let covered = op.columns.iter().all(|(_, column)| {
+            column
+                .id()
+                .is_some_and(|column_id| index_meta.is_covering(&column_id, &op.primary_key))
+        });
+        let range = Range::Scope {
+            min: if is_nullable {
+                Bound::Excluded(Arc::new(DataValue::Null))
+            } else {
+                Bound::Unbounded
+            },
+            max: Bound::Unbounded,
+        };
+
+        op.limit = (None, Some(1));
+        op.is_reverse = is_reverse;
+        // the seek is estimated by the aggregate above
+        self.estimate = None;
+        self.physical_option = Some(PhysicalOption::IndexScan(IndexInfo {
+            meta: index_meta,
+            range: Some(range),
+            covered,
+        }));
+    }
+
     pub fn referenced_table(&self) -> Vec<TableName> {
         fn collect_table(plan: &LogicalPlan, results: &mut Vec<TableName>) {
             if let Operator::TableScan(op) = &plan.operator {
@@ -50,9 +106,10 @@ impl LogicalPlan {
     pub fn output_schema(&mut self) -> &SchemaRef {
         self._output_schema_ref
             .get_or_insert_with(|| match &self.operator {
-                Operator::Filter(_) | Operator::Sort(_) | Operator::Limit(_) => {
-                    self.childrens[0].output_schema().clone()
-                }
+                Operator::Filter(_)
+                | Operator::Sort(_)
+                | Operator::Limit(_)
+                | Operator::TopN(_) => self.childrens[0].output_schema().clone(),
                 Operator::Aggregate(op) => {
                     let out_columns = op
                         .agg_calls
@@ -100,7 +157,7 @@ impl LogicalPlan {
                 Operator::Show => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "TABLE".to_string(),
                 ))]),
-                Operator::Explain => {
+                Operator::Explain(_) => {
                     Arc::new(vec![Arc::new(ColumnCatalog::new_dummy("PLAN".to_string()))])
                 }
                 Operator::Describe(_) => Arc::new(vec![
@@ -149,11 +206,12 @@ impl LogicalPlan {
     }
 
     pub fn explain(&self, indentation: usize) -> String {
-        let mut result = format!("{:indent$}{}", "", self.operator, indent = indentation);
-
-        if let Some(physical_option) = &self.physical_option {
-            result.push_str(&format!(" [{}]", physical_option));
-        }
+        let mut result = format!(
+            "{:indent$}{}",
+            "",
+            self.explain_node(),
+            indent = indentation
+        );
 
         for child in &self.childrens {
             result.push('\n');
@@ -162,4 +220,17 @@ impl LogicalPlan {
 
         result
     }
+
+    /// The line of this node in the output of `EXPLAIN`, without the children.
+    pub(crate) fn explain_node(&self) -> String {
+        let mut result = self.operator.to_string();
+
+        if let Some(physical_option) = &self.physical_option {
+            result.push_str(&format!(" [{}]", physical_option));
+        }
+        if let Some(estimate) = &self.estimate {
+            result.push_str(&format!(" {}", estimate));
+        }
+        result
+    }
 }
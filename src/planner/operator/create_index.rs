@@ -1,4 +1,5 @@
 use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
 use crate::types::index::IndexType;
 use itertools::Itertools;
 use std::fmt;
@@ -7,8 +8,12 @@ use std::fmt::Formatter;
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct CreateIndexOperator {
     pub table_name: TableName,
-    /// List of columns of the index
-    pub columns: Vec<ColumnRef>,
+    /// List of the columns or expressions of the index
+    pub exprs: Vec<ScalarExpression>,
+    /// List of columns stored in the index value
+    pub include_columns: Vec<ColumnRef>,
+    /// Only the tuples satisfying the predicate are indexed
+    pub predicate: Option<ScalarExpression>,
     pub index_name: String,
     pub if_not_exists: bool,
     pub ty: IndexType,
@@ -16,16 +21,20 @@ pub struct CreateIndexOperator {
 
 impl fmt::Display for CreateIndexOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let columns = self
-            .columns
-            .iter()
-            .map(|column| column.name().to_string())
-            .join(", ");
-        write!(
-            f,
-            "Create Index On {} -> [{}], If Not Exists: {}",
-            self.table_name, columns, self.if_not_exists
-        )?;
+        let exprs = self.exprs.iter().map(|expr| format!("{}", expr)).join(", ");
+        write!(f, "Create Index On {} -> [{}]", self.table_name, exprs)?;
+        if !self.include_columns.is_empty() {
+            let include_columns = self
+                .include_columns
+                .iter()
+                .map(|column| column.name().to_string())
+                .join(", ");
+            write!(f, ", Include: [{}]", include_columns)?;
+        }
+        if let Some(predicate) = &self.predicate {
+            write!(f, ", Where: {}", predicate)?;
+        }
+        write!(f, ", If Not Exists: {}", self.if_not_exists)?;
 
         Ok(())
     }
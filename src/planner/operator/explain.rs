@@ -0,0 +1,34 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ExplainFormat {
+    /// The indented tree of the operators
+    Text,
+    Json,
+    /// Graphviz DOT, in which the operators point to their children
+    Dot,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ExplainOperator {
+    /// `EXPLAIN ANALYZE` executes the plan and reports the actual rows and time of each operator
+    pub analyze: bool,
+    pub format: ExplainFormat,
+}
+
+impl fmt::Display for ExplainOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Explain")?;
+        if self.analyze {
+            write!(f, " Analyze")?;
+        }
+        match self.format {
+            ExplainFormat::Text => (),
+            ExplainFormat::Json => write!(f, ", Format: JSON")?,
+            ExplainFormat::Dot => write!(f, ", Format: DOT")?,
+        }
+
+        Ok(())
+    }
+}
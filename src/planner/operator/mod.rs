@@ -8,6 +8,7 @@ pub mod create_table;
 pub mod delete;
 pub mod describe;
 pub mod drop_table;
+pub mod explain;
 pub mod filter;
 pub mod function_scan;
 pub mod insert;
@@ -16,6 +17,7 @@ pub mod limit;
 pub mod project;
 pub mod sort;
 pub mod table_scan;
+pub mod top_n;
 pub mod truncate;
 pub mod union;
 pub mod update;
@@ -37,14 +39,16 @@ use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::describe::DescribeOperator;
 use crate::planner::operator::drop_table::DropTableOperator;
+use crate::planner::operator::explain::ExplainOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::top_n::TopNOperator;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::values::ValuesOperator;
-use crate::types::index::IndexInfo;
+use crate::types::index::{IndexInfo, IndexMergeInfo, IndexMetaRef};
 use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
@@ -61,9 +65,10 @@ pub enum Operator {
     FunctionScan(FunctionScanOperator),
     Sort(SortOperator),
     Limit(LimitOperator),
+    TopN(TopNOperator),
     Values(ValuesOperator),
     Show,
-    Explain,
+    Explain(ExplainOperator),
     Describe(DescribeOperator),
     Union(UnionOperator),
     // DML
@@ -88,15 +93,27 @@ pub enum PhysicalOption {
     Dummy,
     SimpleAggregate,
     HashAggregate,
+    /// `MIN` or `MAX` of the leading column of the index, which reads the first or the last
+    /// key of it.
+    IndexSeek(IndexMetaRef),
+    /// `COUNT(*)` of the table, which reads the row count instead of the tuples.
+    RowCount,
     Filter,
     HashJoin,
+    SortMergeJoin,
     NestLoopJoin,
+    IndexNestLoopJoin(IndexMetaRef),
     Project,
     SeqScan,
     FunctionScan,
     IndexScan(IndexInfo),
+    IndexMerge(IndexMergeInfo),
     Sort,
+    /// The operator is redundant and removed from the plan, e.g. a sort on the input that is
+    /// already in order.
+    Eliminated,
     Limit,
+    TopN,
     Values,
     Insert,
     Update,
@@ -132,7 +149,7 @@ impl Operator {
                     .map(|(_, column)| ScalarExpression::ColumnRef(column))
                     .collect_vec(),
             ),
-            Operator::Sort(_) | Operator::Limit(_) => None,
+            Operator::Sort(_) | Operator::Limit(_) | Operator::TopN(_) => None,
             Operator::Values(ValuesOperator { schema_ref, .. })
             | Operator::Union(UnionOperator {
                 left_schema_ref: schema_ref,
@@ -153,7 +170,7 @@ impl Operator {
                     .collect_vec(),
             ),
             Operator::Show
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::Insert(_)
             | Operator::Update(_)
@@ -211,8 +228,8 @@ impl Operator {
                 .iter()
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
                 .collect_vec(),
-            Operator::Sort(op) => op
-                .sort_fields
+            Operator::Sort(SortOperator { sort_fields, .. })
+            | Operator::TopN(TopNOperator { sort_fields, .. }) => sort_fields
                 .iter()
                 .map(|field| &field.expr)
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
@@ -231,7 +248,7 @@ impl Operator {
             Operator::Dummy
             | Operator::Limit(_)
             | Operator::Show
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::Insert(_)
             | Operator::Update(_)
@@ -259,9 +276,10 @@ impl fmt::Display for Operator {
             Operator::FunctionScan(op) => write!(f, "{}", op),
             Operator::Sort(op) => write!(f, "{}", op),
             Operator::Limit(op) => write!(f, "{}", op),
+            Operator::TopN(op) => write!(f, "{}", op),
             Operator::Values(op) => write!(f, "{}", op),
             Operator::Show => write!(f, "Show Tables"),
-            Operator::Explain => unreachable!(),
+            Operator::Explain(op) => write!(f, "{}", op),
             Operator::Describe(op) => write!(f, "{}", op),
             Operator::Insert(op) => write!(f, "{}", op),
             Operator::Update(op) => write!(f, "{}", op),
@@ -286,15 +304,24 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::Dummy => write!(f, "Dummy"),
             PhysicalOption::SimpleAggregate => write!(f, "SimpleAggregate"),
             PhysicalOption::HashAggregate => write!(f, "HashAggregate"),
+            PhysicalOption::IndexSeek(index_meta) => write!(f, "IndexSeek By {}", index_meta.name),
+            PhysicalOption::RowCount => write!(f, "RowCount"),
             PhysicalOption::Filter => write!(f, "Filter"),
             PhysicalOption::HashJoin => write!(f, "HashJoin"),
+            PhysicalOption::SortMergeJoin => write!(f, "SortMergeJoin"),
             PhysicalOption::NestLoopJoin => write!(f, "NestLoopJoin"),
+            PhysicalOption::IndexNestLoopJoin(index_meta) => {
+                write!(f, "IndexNestLoopJoin By {}", index_meta.name)
+            }
             PhysicalOption::Project => write!(f, "Project"),
             PhysicalOption::SeqScan => write!(f, "SeqScan"),
             PhysicalOption::FunctionScan => write!(f, "FunctionScan"),
             PhysicalOption::IndexScan(index) => write!(f, "IndexScan By {}", index),
+            PhysicalOption::IndexMerge(index_merge) => write!(f, "IndexMerge {}", index_merge),
             PhysicalOption::Sort => write!(f, "Sort"),
+            PhysicalOption::Eliminated => write!(f, "Eliminated"),
             PhysicalOption::Limit => write!(f, "Limit"),
+            PhysicalOption::TopN => write!(f, "TopN"),
             PhysicalOption::Values => write!(f, "Values"),
             PhysicalOption::Insert => write!(f, "Insert"),
             PhysicalOption::Update => write!(f, "Update"),
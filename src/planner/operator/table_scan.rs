@@ -1,7 +1,8 @@
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
+use crate::execution::dql::join::runtime_filter::RuntimeFilterRef;
 use crate::planner::LogicalPlan;
 use crate::storage::Bounds;
-use crate::types::index::IndexInfo;
+use crate::types::index::{IndexInfo, IndexMergeInfo};
 use crate::types::ColumnId;
 use itertools::Itertools;
 use std::fmt;
@@ -20,6 +21,13 @@ pub struct TableScanOperator {
     // Support push down predicate.
     // If pre_where is simple predicate, for example:  a > 1 then can calculate directly when read data.
     pub(crate) index_infos: Vec<IndexInfo>,
+    // The indexes looking up the tuples together, when the predicate is a disjunction of the
+    // ranges of different indexes, or narrowed by the ranges of several indexes.
+    pub(crate) index_merges: Vec<IndexMergeInfo>,
+    // Scans in the descending order, when a descending sort above is eliminated.
+    pub(crate) is_reverse: bool,
+    // Filters from the build sides of the hash joins above, attached when they are executed.
+    pub(crate) runtime_filters: Vec<RuntimeFilterRef>,
 }
 
 impl TableScanOperator {
@@ -43,16 +51,20 @@ impl TableScanOperator {
             .map(|meta| IndexInfo {
                 meta: meta.clone(),
                 range: None,
+                covered: false,
             })
             .collect_vec();
 
         LogicalPlan::new(
             Operator::TableScan(TableScanOperator {
                 index_infos,
+                index_merges: vec![],
                 table_name,
                 primary_key: primary_key_option.unwrap(),
                 columns,
                 limit: (None, None),
+                is_reverse: false,
+                runtime_filters: vec![],
             }),
             vec![],
         )
@@ -79,6 +91,9 @@ impl fmt::Display for TableScanOperator {
         if let Some(offset) = offset {
             write!(f, ", Offset: {}", offset)?;
         }
+        if self.is_reverse {
+            write!(f, ", Reverse")?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,31 @@
+use crate::planner::operator::sort::SortField;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// `Sort` followed by a `Limit`, only the first `offset + limit` tuples of the
+/// sorted input are kept.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct TopNOperator {
+    pub sort_fields: Vec<SortField>,
+    pub limit: usize,
+    pub offset: Option<usize>,
+}
+
+impl fmt::Display for TopNOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let sort_fields = self
+            .sort_fields
+            .iter()
+            .map(|sort_field| format!("{}", sort_field))
+            .join(", ");
+        write!(f, "Top {}", self.limit)?;
+
+        if let Some(offset) = self.offset {
+            write!(f, ", Offset {}", offset)?;
+        }
+        write!(f, ", Sort By {}", sort_fields)?;
+
+        Ok(())
+    }
+}
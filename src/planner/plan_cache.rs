@@ -0,0 +1,349 @@
+use crate::errors::DatabaseError;
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::optimizer::rule::normalization::pushdown_predicates::push_predicate_into_scan;
+use crate::parser::{Hint, StatementExtension};
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::planner::LogicalPlan;
+use crate::types::value::{DataValue, ValueRef};
+use crate::utils::lru::ShardingLruCache;
+use sqlparser::ast::{Statement, Value};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::collections::Bound;
+use std::hash::RandomState;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const PLAN_CACHE_SHARDING_SIZE: usize = 16;
+
+/// The statement printed back by sqlparser, which normalizes the whitespaces, the comments and
+/// the case of the keywords, with its numeric and string literals replaced by `?` and `'?'`,
+/// together with the hints dropped from it. The statements only differing in their literals,
+/// e.g. the point lookups of different keys, share the plan re-bound to their literals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct PlanCacheKey {
+    shape: String,
+    hints: Vec<Hint>,
+    // the literals replaced in the shape, in the order of the text
+    literals: Vec<Value>,
+}
+
+/// The shape and the hints of the statement, with the literals of a plan that can't be
+/// re-bound to others, or `None` for the plan shared by all the literals.
+type PlanEntryKey = (String, Vec<Hint>, Option<Vec<Value>>);
+
+/// A cached plan, with the constants bound from the literals of its statement.
+///
+/// The plan is shared by the statements of the same shape when the constants map to the
+/// literals one by one, i.e. the literals are distinct and each of them is bound to a constant
+/// only used by the filters, and by the ranges of the scans right below them. Re-binding then
+/// substitutes the constants in the filters and detaches the ranges again, while the join order
+/// and the access paths chosen by the statistics of the first literals are kept.
+struct CachedPlan {
+    version: usize,
+    plan: LogicalPlan,
+    // the constants of the literals, in the order of the text
+    parameters: Vec<ValueRef>,
+}
+
+/// The best plans of the statements, which skip the binder and the optimizers when a statement
+/// of the same shape runs again. Only queries are cached, the plans of the writes are cheap
+/// compared with executing them, and `INSERT ... VALUES` rarely repeats its literals.
+///
+/// A cached plan embeds the catalogs of its tables and was chosen by the statistics of the
+/// moment, so [`PlanCache::invalidate`] is called after DDL, `ANALYZE` and the updates of the
+/// cached statistics. Invalidating bumps the version instead of clearing the shards, the plans
+/// of older versions are never returned and are evicted as any other entry. A plan built
+/// concurrently with the invalidation is not cached, as it may be planned by the stale catalogs
+/// or statistics.
+pub(crate) struct PlanCache {
+    plans: ShardingLruCache<PlanEntryKey, CachedPlan>,
+    version: AtomicUsize,
+}
+
+impl PlanCache {
+    pub(crate) fn new(capacity: usize) -> Result<Self, DatabaseError> {
+        let capacity = capacity.div_ceil(PLAN_CACHE_SHARDING_SIZE) * PLAN_CACHE_SHARDING_SIZE;
+
+        Ok(PlanCache {
+            plans: ShardingLruCache::new(capacity, PLAN_CACHE_SHARDING_SIZE, RandomState::new())?,
+            version: AtomicUsize::new(0),
+        })
+    }
+
+    pub(crate) fn key(stmt: &Statement, extension: &StatementExtension) -> PlanCacheKey {
+        let sql = stmt.to_string();
+        let mut literals = Vec::new();
+        let shape = match Tokenizer::new(&PostgreSqlDialect {}, &sql).tokenize() {
+            Ok(tokens) => tokens
+                .into_iter()
+                .map(|token| match token {
+                    Token::Number(number, long) => {
+                        literals.push(Value::Number(number, long));
+                        "?".to_string()
+                    }
+                    Token::SingleQuotedString(string) => {
+                        literals.push(Value::SingleQuotedString(string));
+                        "'?'".to_string()
+                    }
+                    token => token.to_string(),
+                })
+                .collect(),
+            // the literals stay in the text
+            Err(_) => sql,
+        };
+
+        PlanCacheKey {
+            shape,
+            hints: extension.hints.clone(),
+            literals,
+        }
+    }
+
+    /// The version to pass to [`PlanCache::put`], read before the statement is planned.
+    pub(crate) fn version(&self) -> usize {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn get(&self, key: &PlanCacheKey) -> Option<LogicalPlan> {
+        if let Some(cached) = self.cached(key, Some(key.literals.clone())) {
+            return Some(cached.plan.clone());
+        }
+        self.cached(key, None)
+            .and_then(|cached| cached.rebind(&key.literals))
+    }
+
+    fn cached(&self, key: &PlanCacheKey, literals: Option<Vec<Value>>) -> Option<&CachedPlan> {
+        let version = self.version();
+
+        self.plans
+            .get(&(key.shape.clone(), key.hints.clone(), literals))
+            .filter(|cached| cached.version == version)
+    }
+
+    /// Caches the plan built by the version read before, and returns it to be executed.
+    /// `literals` are the constants bound from the literals of the statement, which tell
+    /// whether the plan can be re-bound to other literals.
+    pub(crate) fn put(
+        &self,
+        key: PlanCacheKey,
+        version: usize,
+        mut plan: LogicalPlan,
+        literals: Vec<ValueRef>,
+    ) -> LogicalPlan {
+        if version != self.version() {
+            return plan;
+        }
+        let parameters = parameters(&key.literals, &mut plan, literals);
+        let PlanCacheKey {
+            shape,
+            hints,
+            literals,
+        } = key;
+
+        self.plans.put(
+            (shape, hints, parameters.is_none().then_some(literals)),
+            CachedPlan {
+                version,
+                plan: plan.clone(),
+                parameters: parameters.unwrap_or_default(),
+            },
+        );
+        plan
+    }
+
+    pub(crate) fn invalidate(&self) {
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+impl CachedPlan {
+    fn rebind(&self, literals: &[Value]) -> Option<LogicalPlan> {
+        let values = literals
+            .iter()
+            .map(|literal| Arc::new(DataValue::from(literal)))
+            .collect::<Vec<ValueRef>>();
+
+        if values.len() != self.parameters.len()
+            || values
+                .iter()
+                .zip(self.parameters.iter())
+                .any(|(value, parameter)| value.logical_type() != parameter.logical_type())
+        {
+            return None;
+        }
+        if values == self.parameters {
+            return Some(self.plan.clone());
+        }
+        let mut plan = self.plan.clone();
+
+        visit_filters(&mut plan, &mut |predicate, scan| {
+            predicate.visit_constants_mut(&mut |value| {
+                if let Some(i) = self
+                    .parameters
+                    .iter()
+                    .position(|parameter| Arc::ptr_eq(parameter, value))
+                {
+                    *value = values[i].clone();
+                }
+            });
+            match scan {
+                Some(scan) => rebind_scan(predicate, scan),
+                None => Some(()),
+            }
+        })?;
+        Some(plan)
+    }
+}
+
+/// The constants bound from the literals, in the order of the text, if the plan can be re-bound
+/// to other literals. The plan and the bound constants are the only owners of the constants,
+/// so the references counted tell that a constant is not used elsewhere than the filters and
+/// the ranges of the scans below them, e.g. folded into another constant or projected.
+fn parameters(
+    literals: &[Value],
+    plan: &mut LogicalPlan,
+    bound_literals: Vec<ValueRef>,
+) -> Option<Vec<ValueRef>> {
+    let values = literals.iter().map(DataValue::from).collect::<Vec<_>>();
+
+    if values.len() != bound_literals.len()
+        || (0..values.len()).any(|i| values[..i].iter().any(|value| is_same(value, &values[i])))
+    {
+        return None;
+    }
+    let mut parameters = Vec::with_capacity(values.len());
+
+    for value in values.iter() {
+        let mut bound = bound_literals
+            .iter()
+            .filter(|literal| is_same(literal, value));
+        let (Some(literal), None) = (bound.next(), bound.next()) else {
+            return None;
+        };
+        let (mut in_filters, mut in_ranges) = (0, 0);
+
+        visit_filters(plan, &mut |predicate, scan| {
+            predicate.visit_constants_mut(&mut |value| {
+                in_filters += usize::from(Arc::ptr_eq(value, literal));
+            });
+            if let Some(scan) = scan {
+                in_ranges += scan_ranges(scan)
+                    .into_iter()
+                    .map(|range| count_in_range(range, literal))
+                    .sum::<usize>();
+            }
+            Some(())
+        })?;
+        if in_filters == 0 || Arc::strong_count(literal) != 1 + in_filters + in_ranges {
+            return None;
+        }
+        parameters.push(literal.clone());
+    }
+
+    Some(parameters)
+}
+
+fn is_same(a: &DataValue, b: &DataValue) -> bool {
+    a == b && a.logical_type() == b.logical_type()
+}
+
+/// Visits the predicates of the filters, with the scans right below them, until `f` gives up.
+fn visit_filters<F>(plan: &mut LogicalPlan, f: &mut F) -> Option<()>
+where
+    F: FnMut(&mut ScalarExpression, Option<&mut LogicalPlan>) -> Option<()>,
+{
+    if let Operator::Filter(op) = &mut plan.operator {
+        let scan = plan
+            .childrens
+            .first_mut()
+            .filter(|child| matches!(child.operator, Operator::TableScan(_)));
+        f(&mut op.predicate, scan)?;
+    }
+    for child in plan.childrens.iter_mut() {
+        visit_filters(child, f)?;
+    }
+    Some(())
+}
+
+/// Detaches the ranges of the scan again from the re-bound predicate above it. Gives up if the
+/// chosen index has no range for the new literals, or the scan merges indexes, which may no
+/// longer be built.
+fn rebind_scan(predicate: &ScalarExpression, scan: &mut LogicalPlan) -> Option<()> {
+    let Operator::TableScan(op) = &mut scan.operator else {
+        return None;
+    };
+    for info in op.index_infos.iter_mut() {
+        info.range = None;
+    }
+    op.index_merges.clear();
+    push_predicate_into_scan(predicate, op);
+
+    match &mut scan.physical_option {
+        Some(PhysicalOption::IndexScan(info)) => {
+            info.range = op
+                .index_infos
+                .iter()
+                .find(|new_info| new_info.meta.id == info.meta.id)?
+                .range
+                .clone();
+            info.range.as_ref().map(|_| ())
+        }
+        Some(PhysicalOption::IndexMerge(_)) => None,
+        _ => Some(()),
+    }
+}
+
+fn scan_ranges(scan: &LogicalPlan) -> Vec<&Range> {
+    let Operator::TableScan(op) = &scan.operator else {
+        return vec![];
+    };
+    let option_infos = match &scan.physical_option {
+        Some(PhysicalOption::IndexScan(info)) => slice::from_ref(info),
+        Some(PhysicalOption::IndexMerge(index_merge)) => index_merge.index_infos.as_slice(),
+        _ => &[],
+    };
+
+    op.index_infos
+        .iter()
+        .chain(
+            op.index_merges
+                .iter()
+                .flat_map(|merge| merge.index_infos.iter()),
+        )
+        .chain(option_infos)
+        .filter_map(|info| info.range.as_ref())
+        .collect()
+}
+
+fn count_in_range(range: &Range, literal: &ValueRef) -> usize {
+    match range {
+        Range::Scope { min, max } => [min, max]
+            .into_iter()
+            .map(|bound| match bound {
+                Bound::Included(value) | Bound::Excluded(value) => count_in_value(value, literal),
+                Bound::Unbounded => 0,
+            })
+            .sum(),
+        Range::Eq(value) => count_in_value(value, literal),
+        Range::Dummy => 0,
+        Range::SortedRanges(ranges) => ranges
+            .iter()
+            .map(|range| count_in_range(range, literal))
+            .sum(),
+    }
+}
+
+fn count_in_value(value: &ValueRef, literal: &ValueRef) -> usize {
+    let in_tuple = match value.as_ref() {
+        DataValue::Tuple(Some(values)) => values
+            .iter()
+            .map(|value| count_in_value(value, literal))
+            .sum(),
+        _ => 0,
+    };
+
+    usize::from(Arc::ptr_eq(value, literal)) + in_tuple
+}
@@ -4,7 +4,9 @@ mod table_codec;
 use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
 use crate::optimizer::core::statistics_meta::{StatisticMetaLoader, StatisticsMeta};
+use crate::optimizer::core::statistics_tracker::StatisticsChanges;
 use crate::storage::table_codec::TableCodec;
 use crate::types::index::{Index, IndexId, IndexMetaRef, IndexType};
 use crate::types::tuple::{Tuple, TupleId};
@@ -15,8 +17,9 @@ use bytes::Bytes;
 use itertools::Itertools;
 use std::collections::{Bound, VecDeque};
 use std::ops::SubAssign;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
-use std::{mem, slice};
+use std::{iter, mem, slice};
 
 pub(crate) type StatisticsMetaCache = ShardingLruCache<(TableName, IndexId), StatisticsMeta>;
 pub(crate) type TableCache = ShardingLruCache<String, TableCatalog>;
@@ -40,12 +43,15 @@ pub trait Transaction: Sized {
     /// The bounds is applied to the whole data batches, not per batch.
     ///
     /// The projections is column indices.
+    ///
+    /// With `is_reverse`, the tuples are read in the descending order of the primary key.
     fn read(
         &self,
         table_cache: &TableCache,
         table_name: TableName,
         bounds: Bounds,
         mut columns: Vec<(usize, ColumnRef)>,
+        is_reverse: bool,
     ) -> Result<TupleIter<'_, Self>, DatabaseError> {
         debug_assert!(columns.is_sorted_by_key(|(i, _)| i));
         debug_assert!(columns.iter().map(|(i, _)| i).all_unique());
@@ -66,7 +72,7 @@ pub trait Transaction: Sized {
         }
 
         let (min, max) = TableCodec::tuple_bound(&table_name);
-        let iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+        let iter = self.range(Bound::Included(&min), Bound::Included(&max), is_reverse)?;
 
         Ok(TupleIter {
             offset: bounds.0.unwrap_or(0),
@@ -78,6 +84,13 @@ pub trait Transaction: Sized {
         })
     }
 
+    /// With `covered`, the columns are decoded from the index entries instead of the tuples,
+    /// so they must be covered by the index, see [`IndexMeta::is_covering`].
+    ///
+    /// With `is_reverse`, the ranges are read in the descending order of the index.
+    ///
+    /// [`IndexMeta::is_covering`]: crate::types::index::IndexMeta::is_covering
+    #[allow(clippy::too_many_arguments)]
     fn read_by_index<'a>(
         &'a self,
         table_cache: &'a TableCache,
@@ -86,6 +99,8 @@ pub trait Transaction: Sized {
         columns: Vec<(usize, ColumnRef)>,
         index_meta: IndexMetaRef,
         ranges: Vec<Range>,
+        covered: bool,
+        is_reverse: bool,
     ) -> Result<IndexIter<'a, Self>, DatabaseError> {
         debug_assert!(columns.is_sorted_by_key(|(i, _)| i));
         debug_assert!(columns.iter().map(|(i, _)| i).all_unique());
@@ -103,6 +118,48 @@ pub trait Transaction: Sized {
             tuple_columns.push(column);
             projections.push(projection);
         }
+        let column_type = |column_id: &ColumnId| {
+            table
+                .get_column_by_id(column_id)
+                .map(|column| *column.datatype())
+                .ok_or_else(|| DatabaseError::NotFound("Column by id", column_id.to_string()))
+        };
+        let value_types = iter::once(Ok(index_meta.pk_ty))
+            .chain(index_meta.include_column_ids.iter().map(column_type))
+            .try_collect()?;
+        let covering = if covered {
+            let key_types = index_meta
+                .column_exprs(table)?
+                .iter()
+                .map(ScalarExpression::return_type)
+                .collect_vec();
+            let primary_key_id = table.primary_key()?.1.id();
+            // the entry of the index is decoded as: index keys, primary key, include columns
+            let entry_ids = index_meta
+                .key_column_ids()
+                .into_iter()
+                .chain(iter::once(primary_key_id))
+                .chain(index_meta.include_column_ids.iter().cloned().map(Some))
+                .collect_vec();
+            let projections = tuple_columns
+                .iter()
+                .map(|column| {
+                    entry_ids
+                        .iter()
+                        .position(|id| id.is_some() && *id == column.id())
+                        .ok_or_else(|| {
+                            DatabaseError::NotFound("covered column", column.name().to_string())
+                        })
+                })
+                .try_collect()?;
+
+            Some(Covering {
+                key_types,
+                projections,
+            })
+        } else {
+            None
+        };
         let inner = IndexImplEnum::instance(index_meta.ty);
 
         Ok(IndexIter {
@@ -114,6 +171,9 @@ pub trait Transaction: Sized {
                 index_meta,
                 table_name,
                 table_types,
+                value_types,
+                covering,
+                is_reverse,
                 tx: self,
             },
             inner,
@@ -122,16 +182,27 @@ pub trait Transaction: Sized {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn add_index_meta(
         &mut self,
         table_cache: &TableCache,
         table_name: &TableName,
         index_name: String,
         column_ids: Vec<ColumnId>,
+        exprs: Option<Vec<ScalarExpression>>,
+        include_column_ids: Vec<ColumnId>,
+        predicate: Option<ScalarExpression>,
         ty: IndexType,
     ) -> Result<IndexId, DatabaseError> {
         if let Some(mut table) = self.table(table_cache, table_name.clone()).cloned() {
-            let index_meta = table.add_index_meta(index_name, column_ids, ty)?;
+            let index_meta = table.add_index_meta(
+                index_name,
+                column_ids,
+                exprs,
+                include_column_ids,
+                predicate,
+                ty,
+            )?;
             let (key, value) = TableCodec::encode_index_meta(table_name, index_meta)?;
             self.set(key, value)?;
             table_cache.remove(table_name);
@@ -181,6 +252,9 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
+    /// Tips: an overwriting tuple is not counted into the rows of the table, as it replaces
+    /// the tuple read by an update. The callers overwriting the tuples that may not exist
+    /// count them with [`Transaction::contains_tuple`].
     fn append(
         &mut self,
         table_name: &str,
@@ -190,17 +264,70 @@ pub trait Transaction: Sized {
     ) -> Result<(), DatabaseError> {
         let (key, value) = TableCodec::encode_tuple(table_name, &tuple, types)?;
 
-        if !is_overwrite && self.get(&key)?.is_some() {
-            return Err(DatabaseError::DuplicatePrimaryKey);
+        if !is_overwrite {
+            if self.get(&key)?.is_some() {
+                return Err(DatabaseError::DuplicatePrimaryKey);
+            }
+            self.change_row_count(table_name, 1);
         }
         self.set(key, value)?;
 
         Ok(())
     }
 
+    fn contains_tuple(&self, table_name: &str, tuple_id: &TupleId) -> Result<bool, DatabaseError> {
+        let key = TableCodec::encode_tuple_key(table_name, tuple_id)?;
+
+        Ok(self.get(&key)?.is_some())
+    }
+
+    /// Tips: the tuple must exist, it is counted out of the rows of the table.
     fn delete(&mut self, table_name: &str, tuple_id: TupleId) -> Result<(), DatabaseError> {
         let key = TableCodec::encode_tuple_key(table_name, &tuple_id)?;
         self.remove(&key)?;
+        self.change_row_count(table_name, -1);
+
+        Ok(())
+    }
+
+    /// The number of rows in the table, `None` if the table was created before its rows were
+    /// counted and has not been analyzed since.
+    fn row_count(&self, table_name: &str) -> Result<Option<usize>, DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        let (base_key, _) = TableCodec::encode_row_count(table_name, None, 0);
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max), false)?;
+
+        // the base count is the first one, the changes of the transactions follow it
+        match iter.try_next()? {
+            Some((key, value)) if key == base_key => {
+                let mut rows = TableCodec::decode_row_count(&value);
+
+                while let Some((_, value)) = iter.try_next()? {
+                    rows += TableCodec::decode_row_count(&value);
+                }
+                rows += self.row_count_change(table_name) as i64;
+
+                Ok(Some(rows.max(0) as usize))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Replaces the row count of the table and the changes of the transactions before with
+    /// `rows`, or stops counting the rows with `None`.
+    fn reset_row_count(
+        &mut self,
+        table_name: &str,
+        rows: Option<usize>,
+    ) -> Result<(), DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        self._drop_data(&min, &max)?;
+        self.change_row_count(table_name, -self.row_count_change(table_name));
+
+        if let Some(rows) = rows {
+            let (key, value) = TableCodec::encode_row_count(table_name, None, rows as i64);
+            self.set(key, value)?;
+        }
 
         Ok(())
     }
@@ -232,6 +359,9 @@ pub trait Transaction: Sized {
                 let meta_ref = table.add_index_meta(
                     format!("uk_{}", column.name()),
                     vec![col_id],
+                    None,
+                    vec![],
+                    None,
                     IndexType::Unique,
                 )?;
                 let (key, value) = TableCodec::encode_index_meta(table_name, meta_ref)?;
@@ -262,7 +392,9 @@ pub trait Transaction: Sized {
             self.remove(&key)?;
 
             for index_meta in table_catalog.indexes.iter() {
-                if !index_meta.column_ids.contains(&column.id().unwrap()) {
+                let column_id = column.id().unwrap();
+
+                if !index_meta.is_referenced(&column_id) {
                     continue;
                 }
                 let (index_meta_key, _) = TableCodec::encode_index_meta(table_name, index_meta)?;
@@ -301,6 +433,7 @@ pub trait Transaction: Sized {
         }
         self.create_index_meta_for_table(&mut table_catalog)?;
         self.set(table_key, value)?;
+        self.reset_row_count(&table_name, Some(0))?;
 
         for column in table_catalog.columns() {
             let (key, value) = TableCodec::encode_column(&table_name, column)?;
@@ -325,6 +458,7 @@ pub trait Transaction: Sized {
             }
         }
         self.drop_data(table_name.as_str())?;
+        self.reset_row_count(table_name.as_str(), None)?;
 
         let (column_min, column_max) = TableCodec::columns_bound(table_name.as_str());
         self._drop_data(&column_min, &column_max)?;
@@ -344,6 +478,7 @@ pub trait Transaction: Sized {
 
         let (index_min, index_max) = TableCodec::all_index_bound(table_name);
         self._drop_data(&index_min, &index_max)?;
+        self.reset_row_count(table_name, Some(0))?;
 
         Ok(())
     }
@@ -365,7 +500,7 @@ pub trait Transaction: Sized {
     fn table_metas(&self) -> Result<Vec<TableMeta>, DatabaseError> {
         let mut metas = vec![];
         let (min, max) = TableCodec::root_table_bound();
-        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max), false)?;
 
         while let Some((_, value)) = iter.try_next().ok().flatten() {
             let meta = TableCodec::decode_root_table(&value)?;
@@ -416,8 +551,11 @@ pub trait Transaction: Sized {
         table_name: TableName,
     ) -> Result<(Vec<ColumnCatalog>, Vec<IndexMetaRef>), DatabaseError> {
         let (table_min, table_max) = TableCodec::table_bound(&table_name);
-        let mut column_iter =
-            self.range(Bound::Included(&table_min), Bound::Included(&table_max))?;
+        let mut column_iter = self.range(
+            Bound::Included(&table_min),
+            Bound::Included(&table_max),
+            false,
+        )?;
 
         let mut columns = Vec::new();
         let mut index_metas = Vec::new();
@@ -435,7 +573,7 @@ pub trait Transaction: Sized {
     }
 
     fn _drop_data(&mut self, min: &[u8], max: &[u8]) -> Result<(), DatabaseError> {
-        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max), false)?;
         let mut data_keys = vec![];
 
         while let Some((key, _)) = iter.try_next()? {
@@ -476,6 +614,9 @@ pub trait Transaction: Sized {
             let meta_ref = table.add_index_meta(
                 format!("{}_{}", prefix, col.name()),
                 vec![col_id],
+                None,
+                vec![],
+                None,
                 index_ty,
             )?;
             let (key, value) = TableCodec::encode_index_meta(&table_name, meta_ref)?;
@@ -490,19 +631,37 @@ pub trait Transaction: Sized {
 
     fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError>;
 
+    /// Rows added to the table in the transaction, negative when more are deleted, which are
+    /// written along with the commit.
+    fn row_count_change(&self, table_name: &str) -> isize;
+
+    fn change_row_count(&mut self, table_name: &str, rows: isize);
+
+    /// Iterates the keys between the bounds in the ascending order, or in the descending order
+    /// with `is_reverse`.
     fn range<'a>(
         &'a self,
         min: Bound<&[u8]>,
         max: Bound<&[u8]>,
+        is_reverse: bool,
     ) -> Result<Self::IterType<'a>, DatabaseError>;
 
+    /// Counter of the keys read by `get` and by the iterators of the transaction, which
+    /// `EXPLAIN ANALYZE` samples around each operator.
+    fn keys_read(&self) -> Arc<AtomicUsize>;
+
+    /// Changes of the statistics recorded by the DML executors when the tables are analyzed
+    /// automatically, which are taken before the commit.
+    fn statistics_changes(&mut self) -> &mut StatisticsChanges;
+
     fn commit(self) -> Result<(), DatabaseError>;
 }
 
 trait IndexImpl<T: Transaction> {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        key: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError>;
 
@@ -550,9 +709,20 @@ struct IndexImplParams<'a, T: Transaction> {
     index_meta: IndexMetaRef,
     table_name: &'a str,
     table_types: Vec<LogicalType>,
+    // types of the index value: primary key, include columns
+    value_types: Vec<LogicalType>,
+    covering: Option<Covering>,
+    is_reverse: bool,
     tx: &'a T,
 }
 
+/// Decodes the tuples from the index entries, without going back to the table.
+struct Covering {
+    key_types: Vec<LogicalType>,
+    // positions of the columns in the index entry
+    projections: Vec<usize>,
+}
+
 impl<T: Transaction> IndexImplParams<'_, T> {
     fn get_tuple_by_id(&self, tuple_id: &TupleId) -> Result<Option<Tuple>, DatabaseError> {
         let key = TableCodec::encode_tuple_key(self.table_name, tuple_id)?;
@@ -569,21 +739,23 @@ impl<T: Transaction> IndexImplParams<'_, T> {
 }
 
 enum IndexResult<'a, T: Transaction + 'a> {
-    Tuple(Tuple),
+    /// The tuple of the unique key, `None` if no key equals the value.
+    Tuple(Option<Tuple>),
     Scope(T::IterType<'a>),
 }
 
 impl<T: Transaction> IndexImpl<T> for IndexImplEnum {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        key: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
         match self {
-            IndexImplEnum::PrimaryKey(inner) => inner.index_lookup(bytes, params),
-            IndexImplEnum::Unique(inner) => inner.index_lookup(bytes, params),
-            IndexImplEnum::Normal(inner) => inner.index_lookup(bytes, params),
-            IndexImplEnum::Composite(inner) => inner.index_lookup(bytes, params),
+            IndexImplEnum::PrimaryKey(inner) => inner.index_lookup(key, value, params),
+            IndexImplEnum::Unique(inner) => inner.index_lookup(key, value, params),
+            IndexImplEnum::Normal(inner) => inner.index_lookup(key, value, params),
+            IndexImplEnum::Composite(inner) => inner.index_lookup(key, value, params),
         }
     }
 
@@ -618,14 +790,15 @@ impl<T: Transaction> IndexImpl<T> for IndexImplEnum {
 impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        _: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
         Ok(TableCodec::decode_tuple(
             &params.table_types,
             &params.projections,
             &params.tuple_schema_ref,
-            bytes,
+            value,
         ))
     }
 
@@ -634,18 +807,17 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
         value: &ValueRef,
         params: &IndexImplParams<'a, T>,
     ) -> Result<IndexResult<'a, T>, DatabaseError> {
-        let bytes = params
+        let tuple = params
             .tx
             .get(&TableCodec::encode_tuple_key(params.table_name, value)?)?
-            .ok_or_else(|| {
-                DatabaseError::NotFound("secondary index", format!("tuple_id -> {}", value))
-            })?;
-        let tuple = TableCodec::decode_tuple(
-            &params.table_types,
-            &params.projections,
-            &params.tuple_schema_ref,
-            &bytes,
-        );
+            .map(|bytes| {
+                TableCodec::decode_tuple(
+                    &params.table_types,
+                    &params.projections,
+                    &params.tuple_schema_ref,
+                    &bytes,
+                )
+            });
         Ok(IndexResult::Tuple(tuple))
     }
 
@@ -660,10 +832,26 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
 }
 
 fn secondary_index_lookup<T: Transaction>(
-    bytes: &Bytes,
+    key: &[u8],
+    value: &[u8],
     params: &IndexImplParams<T>,
 ) -> Result<Tuple, DatabaseError> {
-    let tuple_id = TableCodec::decode_index(bytes, &params.index_meta.pk_ty);
+    let mut values = TableCodec::decode_index(value, &params.value_types);
+    let tuple_id = values[0].clone();
+
+    if let Some(Covering {
+        key_types,
+        projections,
+    }) = &params.covering
+    {
+        let mut entry = TableCodec::decode_index_key(params.table_name, key, key_types)?;
+        entry.append(&mut values);
+
+        return Ok(Tuple {
+            id: Some(tuple_id),
+            values: projections.iter().map(|i| entry[*i].clone()).collect_vec(),
+        });
+    }
     params
         .get_tuple_by_id(&tuple_id)?
         .ok_or_else(|| DatabaseError::NotFound("index's tuple_id", tuple_id.to_string()))
@@ -672,10 +860,11 @@ fn secondary_index_lookup<T: Transaction>(
 impl<T: Transaction> IndexImpl<T> for UniqueIndexImpl {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        key: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
-        secondary_index_lookup(bytes, params)
+        secondary_index_lookup(key, value, params)
     }
 
     fn eq_to_res<'a>(
@@ -683,16 +872,12 @@ impl<T: Transaction> IndexImpl<T> for UniqueIndexImpl {
         value: &ValueRef,
         params: &IndexImplParams<'a, T>,
     ) -> Result<IndexResult<'a, T>, DatabaseError> {
-        let bytes = params
+        let key = self.bound_key(params, value, false)?;
+        let tuple = params
             .tx
-            .get(&self.bound_key(params, value, false)?)?
-            .ok_or_else(|| {
-                DatabaseError::NotFound("secondary index", format!("index_value -> {}", value))
-            })?;
-        let tuple_id = TableCodec::decode_index(&bytes, &params.index_meta.pk_ty);
-        let tuple = params.get_tuple_by_id(&tuple_id)?.ok_or_else(|| {
-            DatabaseError::NotFound("secondary index", format!("tuple_id -> {}", value))
-        })?;
+            .get(&key)?
+            .map(|bytes| secondary_index_lookup(&key, &bytes, params))
+            .transpose()?;
         Ok(IndexResult::Tuple(tuple))
     }
 
@@ -715,10 +900,11 @@ impl<T: Transaction> IndexImpl<T> for UniqueIndexImpl {
 impl<T: Transaction> IndexImpl<T> for NormalIndexImpl {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        key: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
-        secondary_index_lookup(bytes, params)
+        secondary_index_lookup(key, value, params)
     }
 
     fn eq_to_res<'a>(
@@ -732,6 +918,7 @@ impl<T: Transaction> IndexImpl<T> for NormalIndexImpl {
         let iter = params.tx.range(
             Bound::Included(min.as_slice()),
             Bound::Included(max.as_slice()),
+            params.is_reverse,
         )?;
         Ok(IndexResult::Scope(iter))
     }
@@ -755,10 +942,11 @@ impl<T: Transaction> IndexImpl<T> for NormalIndexImpl {
 impl<T: Transaction> IndexImpl<T> for CompositeIndexImpl {
     fn index_lookup(
         &self,
-        bytes: &Bytes,
+        key: &[u8],
+        value: &[u8],
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
-        secondary_index_lookup(bytes, params)
+        secondary_index_lookup(key, value, params)
     }
 
     fn eq_to_res<'a>(
@@ -772,6 +960,7 @@ impl<T: Transaction> IndexImpl<T> for CompositeIndexImpl {
         let iter = params.tx.range(
             Bound::Included(min.as_slice()),
             Bound::Included(max.as_slice()),
+            params.is_reverse,
         )?;
         Ok(IndexResult::Scope(iter))
     }
@@ -879,19 +1068,24 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
         }
 
         if let Some(iter) = &mut self.scope_iter {
-            while let Some((_, bytes)) = iter.try_next()? {
+            while let Some((key, value)) = iter.try_next()? {
                 if Self::offset_move(&mut self.offset) {
                     continue;
                 }
                 Self::limit_sub(&mut self.limit);
-                let tuple = self.inner.index_lookup(&bytes, &self.params)?;
+                let tuple = self.inner.index_lookup(&key, &value, &self.params)?;
 
                 return Ok(Some(tuple));
             }
             self.scope_iter = None;
         }
 
-        if let Some(binary) = self.ranges.pop_front() {
+        let range = if self.params.is_reverse {
+            self.ranges.pop_back()
+        } else {
+            self.ranges.pop_front()
+        };
+        if let Some(binary) = range {
             match binary {
                 Range::Scope { min, max } => {
                     let table_name = self.params.table_name;
@@ -904,10 +1098,11 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                                     &val,
                                     is_upper,
                                 )?)),
+                                // the key of the opposite side skips all entries of the value
                                 Bound::Excluded(val) => Ok(Bound::Excluded(self.inner.bound_key(
                                     &self.params,
                                     &val,
-                                    is_upper,
+                                    !is_upper,
                                 )?)),
                                 Bound::Unbounded => Ok(Bound::Unbounded),
                             }
@@ -932,11 +1127,13 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                     let iter = self.params.tx.range(
                         encode_min.as_ref().map(Vec::as_slice),
                         encode_max.as_ref().map(Vec::as_slice),
+                        self.params.is_reverse,
                     )?;
                     self.scope_iter = Some(iter);
                 }
                 Range::Eq(val) => match self.inner.eq_to_res(&val, &self.params)? {
-                    IndexResult::Tuple(tuple) => {
+                    IndexResult::Tuple(None) => (),
+                    IndexResult::Tuple(Some(tuple)) => {
                         if Self::offset_move(&mut self.offset) {
                             return self.next_tuple();
                         }
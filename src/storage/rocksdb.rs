@@ -1,11 +1,20 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::statistics_tracker::StatisticsChanges;
+use crate::storage::table_codec::{TableCodec, STORAGE_FORMAT_VERSION};
 use crate::storage::{InnerIter, Storage, Transaction};
 use bytes::Bytes;
-use rocksdb::{DBIteratorWithThreadMode, Direction, IteratorMode, OptimisticTransactionDB};
-use std::collections::Bound;
+use rocksdb::{
+    DBIteratorWithThreadMode, Direction, ErrorKind, IteratorMode, OptimisticTransactionDB,
+};
+use std::collections::{Bound, HashMap};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+/// Changes of the row count of a table committed by the transactions, beyond which they are
+/// folded into the base count so that `row_count` reads a bounded number of keys.
+const ROW_COUNT_COMPACTION_THRESHOLD: usize = 16;
+
 #[derive(Clone)]
 pub struct RocksStorage {
     pub inner: Arc<OptimisticTransactionDB>,
@@ -21,11 +30,44 @@ impl RocksStorage {
         opts.create_if_missing(true);
 
         let storage = OptimisticTransactionDB::open(&opts, path.into())?;
+        Self::check_storage_version(&storage)?;
 
         Ok(RocksStorage {
             inner: Arc::new(storage),
         })
     }
+
+    /// Writes the format version into a new store, and rejects the store written in another
+    /// version. The stores written before the version was recorded have data but no version.
+    fn check_storage_version(db: &OptimisticTransactionDB) -> Result<(), DatabaseError> {
+        let tx = db.transaction();
+        let (key, value) = TableCodec::encode_storage_version(STORAGE_FORMAT_VERSION);
+
+        let version = match tx.get(&key)? {
+            Some(bytes) => TableCodec::decode_storage_version(&bytes),
+            None => {
+                let is_empty = tx
+                    .iterator(IteratorMode::Start)
+                    .next()
+                    .transpose()?
+                    .is_none();
+
+                if is_empty {
+                    tx.put(key, value)?;
+                    tx.commit()?;
+                    return Ok(());
+                }
+                0
+            }
+        };
+        if version != STORAGE_FORMAT_VERSION {
+            return Err(DatabaseError::UnsupportedStorageVersion(
+                version,
+                STORAGE_FORMAT_VERSION,
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl Storage for RocksStorage {
@@ -36,12 +78,20 @@ impl Storage for RocksStorage {
     fn transaction(&self) -> Result<Self::TransactionType<'_>, DatabaseError> {
         Ok(RocksTransaction {
             tx: self.inner.transaction(),
+            db: &self.inner,
+            row_count_changes: HashMap::new(),
+            keys_read: Arc::new(AtomicUsize::new(0)),
+            statistics_changes: StatisticsChanges::default(),
         })
     }
 }
 
 pub struct RocksTransaction<'db> {
     tx: rocksdb::Transaction<'db, OptimisticTransactionDB>,
+    db: &'db OptimisticTransactionDB,
+    row_count_changes: HashMap<String, isize>,
+    keys_read: Arc<AtomicUsize>,
+    statistics_changes: StatisticsChanges,
 }
 
 impl<'txn> Transaction for RocksTransaction<'txn> {
@@ -50,6 +100,7 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         Self: 'iter;
 
     fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
+        self.keys_read.fetch_add(1, Ordering::Relaxed);
         Ok(self.tx.get(key)?.map(Bytes::from))
     }
 
@@ -65,11 +116,23 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         Ok(())
     }
 
+    fn row_count_change(&self, table_name: &str) -> isize {
+        self.row_count_changes.get(table_name).copied().unwrap_or(0)
+    }
+
+    fn change_row_count(&mut self, table_name: &str, rows: isize) {
+        *self
+            .row_count_changes
+            .entry(table_name.to_string())
+            .or_insert(0) += rows;
+    }
+
     // Tips: rocksdb has weak support for `Include` and `Exclude`, so precision will be lost
     fn range<'a>(
         &'a self,
         min: Bound<&[u8]>,
         max: Bound<&[u8]>,
+        is_reverse: bool,
     ) -> Result<Self::IterType<'a>, DatabaseError> {
         fn bound_to_include(bound: Bound<&[u8]>) -> Option<&[u8]> {
             match bound {
@@ -78,48 +141,141 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
             }
         }
 
-        let lower = bound_to_include(min)
-            .map(|bytes| IteratorMode::From(bytes, Direction::Forward))
-            .unwrap_or(IteratorMode::Start);
-        let iter = self.tx.iterator(lower);
+        let mode = if is_reverse {
+            bound_to_include(max)
+                .map(|bytes| IteratorMode::From(bytes, Direction::Reverse))
+                .unwrap_or(IteratorMode::End)
+        } else {
+            bound_to_include(min)
+                .map(|bytes| IteratorMode::From(bytes, Direction::Forward))
+                .unwrap_or(IteratorMode::Start)
+        };
+        let iter = self.tx.iterator(mode);
 
         Ok(RocksIter {
             lower: min.map(|bytes| bytes.to_vec()),
             upper: max.map(|bytes| bytes.to_vec()),
+            is_reverse,
             iter,
+            keys_read: &self.keys_read,
         })
     }
 
+    fn keys_read(&self) -> Arc<AtomicUsize> {
+        self.keys_read.clone()
+    }
+
+    fn statistics_changes(&mut self) -> &mut StatisticsChanges {
+        &mut self.statistics_changes
+    }
+
     fn commit(self) -> Result<(), DatabaseError> {
+        let mut changed_tables = Vec::with_capacity(self.row_count_changes.len());
+
+        for (table_name, rows) in self.row_count_changes {
+            if rows == 0 {
+                continue;
+            }
+            // a random id keeps the change apart from the ones of the concurrent transactions
+            let (key, value) =
+                TableCodec::encode_row_count(&table_name, Some(rand::random()), rows as i64);
+            self.tx.put(key, value)?;
+            changed_tables.push(table_name);
+        }
         self.tx.commit()?;
+
+        // the commit is done, a failed compaction only leaves the changes to the next one
+        for table_name in changed_tables {
+            if let Err(err) = RocksTransaction::compact_row_count(self.db, &table_name) {
+                log::warn!("failed to compact the row count of {}: {}", table_name, err);
+            }
+        }
         Ok(())
     }
 }
 
+impl RocksTransaction<'_> {
+    /// Folds the committed changes of the row count into the base count, in a transaction of its
+    /// own after the commit. When another transaction folds them at the same time, the one
+    /// committed later conflicts on the base count and leaves them to the next commit.
+    fn compact_row_count(
+        db: &OptimisticTransactionDB,
+        table_name: &str,
+    ) -> Result<(), DatabaseError> {
+        let tx = db.transaction();
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        let (base_key, _) = TableCodec::encode_row_count(table_name, None, 0);
+        let mut rows = tx
+            .get_for_update(&base_key, true)?
+            .map(|bytes| TableCodec::decode_row_count(&bytes));
+        let mut change_keys = Vec::new();
+
+        for result in tx.iterator(IteratorMode::From(&min, Direction::Forward)) {
+            let (key, value) = result?;
+
+            if key.as_ref() > max.as_slice() {
+                break;
+            }
+            if key.as_ref() == base_key.as_ref() {
+                continue;
+            }
+            if let Some(rows) = rows.as_mut() {
+                *rows += TableCodec::decode_row_count(&value);
+            }
+            change_keys.push(key);
+        }
+        if change_keys.len() < ROW_COUNT_COMPACTION_THRESHOLD {
+            return Ok(());
+        }
+        for key in change_keys {
+            tx.delete(key)?;
+        }
+        // the changes of a table whose rows are not counted are dropped, `ANALYZE` counts them
+        if let Some(rows) = rows {
+            let (key, value) = TableCodec::encode_row_count(table_name, None, rows);
+            tx.put(key, value)?;
+        }
+        match tx.commit() {
+            Err(err) if matches!(err.kind(), ErrorKind::Busy | ErrorKind::TryAgain) => Ok(()),
+            result => Ok(result?),
+        }
+    }
+}
+
 pub struct RocksIter<'txn, 'iter> {
     lower: Bound<Vec<u8>>,
     upper: Bound<Vec<u8>>,
+    is_reverse: bool,
     iter: DBIteratorWithThreadMode<'iter, rocksdb::Transaction<'txn, OptimisticTransactionDB>>,
+    keys_read: &'iter AtomicUsize,
 }
 
 impl InnerIter for RocksIter<'_, '_> {
     fn try_next(&mut self) -> Result<Option<(Bytes, Bytes)>, DatabaseError> {
         for result in self.iter.by_ref() {
             let (key, value) = result?;
+            self.keys_read.fetch_add(1, Ordering::Relaxed);
             let upper_bound_check = match &self.upper {
                 Bound::Included(ref upper) => key.as_ref() <= upper.as_slice(),
                 Bound::Excluded(ref upper) => key.as_ref() < upper.as_slice(),
                 Bound::Unbounded => true,
             };
-            if !upper_bound_check {
-                break;
-            }
             let lower_bound_check = match &self.lower {
                 Bound::Included(ref lower) => key.as_ref() >= lower.as_slice(),
                 Bound::Excluded(ref lower) => key.as_ref() > lower.as_slice(),
                 Bound::Unbounded => true,
             };
-            if lower_bound_check {
+            // the iteration ends at the bound it moves towards, and skips the keys beyond the
+            // bound it starts from
+            let (start_bound_check, end_bound_check) = if self.is_reverse {
+                (upper_bound_check, lower_bound_check)
+            } else {
+                (lower_bound_check, upper_bound_check)
+            };
+            if !end_bound_check {
+                break;
+            }
+            if start_bound_check {
                 return Ok(Some((Bytes::from(key), Bytes::from(value))));
             }
         }
@@ -133,21 +289,63 @@ mod test {
     use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
     use crate::expression::range_detacher::Range;
-    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::rocksdb::{RocksStorage, ROW_COUNT_COMPACTION_THRESHOLD};
+    use crate::storage::table_codec::{TableCodec, STORAGE_FORMAT_VERSION};
     use crate::storage::{
-        IndexImplEnum, IndexImplParams, IndexIter, Iter, PrimaryKeyIndexImpl, Storage, Transaction,
+        IndexImplEnum, IndexImplParams, IndexIter, InnerIter, Iter, PrimaryKeyIndexImpl, Storage,
+        Transaction,
     };
     use crate::types::index::{IndexMeta, IndexType};
     use crate::types::tuple::Tuple;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
     use crate::utils::lru::ShardingLruCache;
+    use bytes::Bytes;
     use itertools::Itertools;
+    use rocksdb::{OptimisticTransactionDB, SingleThreaded};
+    use sqlparser::ast::CharLengthUnits;
     use std::collections::{Bound, VecDeque};
     use std::hash::RandomState;
     use std::sync::Arc;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_storage_version() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let (version_key, _) = TableCodec::encode_storage_version(STORAGE_FORMAT_VERSION);
+
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let mut transaction = storage.transaction()?;
+        transaction.set(Bytes::from("k"), Bytes::from("v"))?;
+        transaction.commit()?;
+        drop(storage);
+        // the version written into the new store is accepted on reopen
+        let storage = RocksStorage::new(temp_dir.path())?;
+
+        let (key, value) = TableCodec::encode_storage_version(STORAGE_FORMAT_VERSION + 1);
+        storage.inner.put(key, value)?;
+        drop(storage);
+        debug_assert!(matches!(
+            RocksStorage::new(temp_dir.path()),
+            Err(DatabaseError::UnsupportedStorageVersion(version, STORAGE_FORMAT_VERSION))
+                if version == STORAGE_FORMAT_VERSION + 1
+        ));
+
+        // the store written before the version was recorded
+        let storage = OptimisticTransactionDB::<SingleThreaded>::open_default(temp_dir.path())?;
+        storage.delete(version_key)?;
+        drop(storage);
+        debug_assert!(matches!(
+            RocksStorage::new(temp_dir.path()),
+            Err(DatabaseError::UnsupportedStorageVersion(
+                0,
+                STORAGE_FORMAT_VERSION
+            ))
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_in_rocksdb_storage_works_with_data() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -215,6 +413,7 @@ mod test {
             Arc::new("test".to_string()),
             (Some(1), Some(1)),
             vec![(0, columns[0].clone())],
+            false,
         )?;
 
         let option_1 = iter.next_tuple()?;
@@ -258,6 +457,9 @@ mod test {
                 index_meta: Arc::new(IndexMeta {
                     id: 0,
                     column_ids: vec![0],
+                    exprs: None,
+                    include_column_ids: vec![],
+                    predicate: None,
                     table_name,
                     pk_ty: LogicalType::Integer,
                     name: "pk_a".to_string(),
@@ -265,6 +467,9 @@ mod test {
                 }),
                 table_name: &table.name,
                 table_types: table.types(),
+                value_types: vec![LogicalType::Integer],
+                covering: None,
+                is_reverse: false,
                 tx: &transaction,
             },
             ranges: VecDeque::from(vec![
@@ -312,6 +517,8 @@ mod test {
                     min: Bound::Excluded(Arc::new(DataValue::Int32(Some(0)))),
                     max: Bound::Unbounded,
                 }],
+                false,
+                false,
             )
             .unwrap();
 
@@ -326,6 +533,425 @@ mod test {
             )
         }
 
+        Ok(())
+    }
+    #[test]
+    fn test_read_in_reverse() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run(format!(
+            "insert into t1 values {}",
+            (0..10).map(|i| format!("({i}, {})", i / 2)).join(", ")
+        ))?;
+        let _ = fnck_sql.run("create index i_b on t1 (b)")?;
+        let transaction = fnck_sql.storage.transaction()?;
+        let table = transaction
+            .table(&fnck_sql.table_cache, Arc::new("t1".to_string()))
+            .unwrap()
+            .clone();
+        let columns = table.columns().cloned().enumerate().collect_vec();
+        let int = |i: i32| Arc::new(DataValue::Int32(Some(i)));
+        let collect = |iter: &mut dyn Iter| -> Result<Vec<_>, DatabaseError> {
+            let mut values = Vec::new();
+
+            while let Some(tuple) = iter.next_tuple()? {
+                values.push(tuple.values[0].clone());
+            }
+            Ok(values)
+        };
+
+        let mut iter = transaction.read(
+            &fnck_sql.table_cache,
+            Arc::new("t1".to_string()),
+            (Some(1), Some(3)),
+            columns.clone(),
+            true,
+        )?;
+        debug_assert_eq!(collect(&mut iter)?, vec![int(8), int(7), int(6)]);
+
+        let index_meta = table
+            .indexes
+            .iter()
+            .find(|meta| meta.name == "i_b")
+            .unwrap()
+            .clone();
+        let mut iter = transaction.read_by_index(
+            &fnck_sql.table_cache,
+            Arc::new("t1".to_string()),
+            (None, None),
+            columns,
+            index_meta,
+            vec![
+                Range::Eq(int(0)),
+                Range::Scope {
+                    min: Bound::Excluded(int(2)),
+                    max: Bound::Included(int(3)),
+                },
+                Range::Scope {
+                    min: Bound::Included(int(4)),
+                    max: Bound::Unbounded,
+                },
+            ],
+            false,
+            true,
+        )?;
+        debug_assert_eq!(
+            collect(&mut iter)?,
+            vec![int(9), int(8), int(7), int(6), int(1), int(0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_by_covering_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int, c varchar, d int)")?;
+        let _ = fnck_sql.run(format!(
+            "insert into t1 values (0, null, 'c0', 0), {}",
+            (1..200)
+                .map(|i| format!("({i}, {}, 'c{i}', {i})", 200 - i))
+                .join(", ")
+        ))?;
+        let _ = fnck_sql.run("create index i_b on t1 (b) include (c)")?;
+        let _ = fnck_sql.run("update t1 set c = 'updated' where a = 199")?;
+        let _ = fnck_sql.run("analyze table t1")?;
+
+        let (_, plan) = fnck_sql.run("explain select a, b, c from t1 where b < 3")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(
+            plan.contains("IndexScan By i_b => (-inf, 3), Covered"),
+            "{}",
+            plan
+        );
+        let (_, plan) = fnck_sql.run("explain select d from t1 where b < 3")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("IndexScan By i_b => (-inf, 3)]"), "{}", plan);
+
+        let (_, tuples) = fnck_sql.run("select a, b, c from t1 where b < 3")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values.iter().map(|value| value.to_string()).join(" "))
+                .collect_vec(),
+            vec!["199 1 updated", "198 2 c198"]
+        );
+
+        let transaction = fnck_sql.storage.transaction()?;
+        let table = transaction
+            .table(&fnck_sql.table_cache, Arc::new("t1".to_string()))
+            .unwrap()
+            .clone();
+        let index_meta = table
+            .indexes
+            .iter()
+            .find(|meta| meta.name == "i_b")
+            .unwrap()
+            .clone();
+        let columns = table.columns().cloned().enumerate().take(3).collect_vec();
+        let mut iter = transaction.read_by_index(
+            &fnck_sql.table_cache,
+            Arc::new("t1".to_string()),
+            (None, Some(2)),
+            columns,
+            index_meta.clone(),
+            vec![Range::Scope {
+                min: Bound::Unbounded,
+                max: Bound::Unbounded,
+            }],
+            true,
+            false,
+        )?;
+        let mut tuples = Vec::new();
+
+        while let Some(tuple) = iter.next_tuple()? {
+            tuples.push(tuple);
+        }
+        // the nulls come first in the index
+        debug_assert_eq!(
+            tuples,
+            vec![
+                Tuple {
+                    id: Some(Arc::new(DataValue::Int32(Some(0)))),
+                    values: vec![
+                        Arc::new(DataValue::Int32(Some(0))),
+                        Arc::new(DataValue::Int32(None)),
+                        Arc::new(DataValue::Utf8 {
+                            value: Some("c0".to_string()),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                    ],
+                },
+                Tuple {
+                    id: Some(Arc::new(DataValue::Int32(Some(199)))),
+                    values: vec![
+                        Arc::new(DataValue::Int32(Some(199))),
+                        Arc::new(DataValue::Int32(Some(1))),
+                        Arc::new(DataValue::Utf8 {
+                            value: Some("updated".to_string()),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                    ],
+                },
+            ]
+        );
+
+        let columns = table.columns().cloned().enumerate().skip(3).collect_vec();
+        debug_assert!(transaction
+            .read_by_index(
+                &fnck_sql.table_cache,
+                Arc::new("t1".to_string()),
+                (None, None),
+                columns,
+                index_meta,
+                vec![],
+                true,
+                false,
+            )
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_by_expression_and_partial_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b varchar, c int, d int)")?;
+        let _ = fnck_sql.run(format!(
+            "insert into t1 values {}",
+            (0..400)
+                .map(|i| format!("({i}, 'k{}_{i}', {}, {i})", i % 10, i % 20))
+                .join(", ")
+        ))?;
+        let _ = fnck_sql.run("create index i_b on t1 (substring(b for 2))")?;
+        let _ = fnck_sql.run("create index i_c on t1 (c) where d >= 280")?;
+        let _ = fnck_sql.run("analyze table t1")?;
+        // moves the tuples into and out of the partial index
+        let _ = fnck_sql.run("update t1 set d = 0 where a = 281")?;
+        let _ = fnck_sql.run("update t1 set d = 280, b = 'k3_updated' where a = 1")?;
+        let _ = fnck_sql.run("delete from t1 where a = 283")?;
+
+        let (_, plan) = fnck_sql.run("explain select a from t1 where substring(b for 2) = 'k3'")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("IndexScan By i_b => k3"), "{}", plan);
+        let (_, tuples) = fnck_sql.run("select a from t1 where substring(b for 2) = 'k3'")?;
+        let values = tuples
+            .iter()
+            .map(|tuple| tuple.values[0].to_string())
+            .collect_vec();
+        debug_assert_eq!(values.len(), 40);
+        debug_assert!(values.contains(&"1".to_string()));
+        debug_assert!(!values.contains(&"283".to_string()));
+
+        let (_, plan) = fnck_sql.run("explain select a from t1 where c = 1 and d >= 280")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(plan.contains("IndexScan By i_c => 1"), "{}", plan);
+        let (_, plan) = fnck_sql.run("explain select a from t1 where d >= 280")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(
+            plan.contains("IndexScan By i_c => (-inf, +inf)"),
+            "{}",
+            plan
+        );
+        // the predicate of the partial index is not implied
+        let (_, plan) = fnck_sql.run("explain select a from t1 where c = 1")?;
+        let plan = format!("{:?}", plan);
+        debug_assert!(!plan.contains("i_c"), "{}", plan);
+
+        let (_, tuples) = fnck_sql.run("select a from t1 where c = 1 and d >= 280")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values[0].to_string())
+                .sorted()
+                .collect_vec(),
+            vec!["1", "301", "321", "341", "361", "381"]
+        );
+        let (_, tuples) = fnck_sql.run("select a from t1 where d >= 280")?;
+        let values = tuples
+            .iter()
+            .map(|tuple| tuple.values[0].to_string())
+            .collect_vec();
+        debug_assert_eq!(values.len(), 119);
+        debug_assert!(values.contains(&"1".to_string()));
+        debug_assert!(!values.contains(&"281".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_by_composite_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int, c int)")?;
+        let _ = fnck_sql.run(format!(
+            "insert into t1 values {}",
+            (0..400)
+                .map(|i| format!("({i}, {}, {})", i % 4, i / 4))
+                .join(", ")
+        ))?;
+        let _ = fnck_sql.run("create index i_bc on t1 (b, c)")?;
+        let _ = fnck_sql.run("analyze table t1")?;
+
+        for (predicate, range, expected) in [
+            (
+                "b = 1 and c between 5 and 10",
+                "[(1, 5), (1, 10)]",
+                vec![21, 25, 29, 33, 37, 41],
+            ),
+            ("b = 1 and c > 96", "((1, 96), (1)]", vec![389, 393, 397]),
+            ("b = 2 and c < 2", "[(2), (2, 2))", vec![2, 6]),
+            (
+                "b in (1, 3) and c >= 98",
+                "[(1, 98), (1)], [(3, 98), (3)]",
+                vec![393, 397, 395, 399],
+            ),
+        ] {
+            let (_, plan) = fnck_sql.run(format!("explain select a from t1 where {predicate}"))?;
+            let plan = format!("{:?}", plan);
+            debug_assert!(
+                plan.contains(&format!("IndexScan By i_bc => {range}")),
+                "{predicate}: {plan}"
+            );
+            let (_, tuples) = fnck_sql.run(format!("select a from t1 where {predicate}"))?;
+            debug_assert_eq!(
+                tuples
+                    .iter()
+                    .map(|tuple| tuple.values[0].clone())
+                    .collect_vec(),
+                expected
+                    .into_iter()
+                    .map(|a| Arc::new(DataValue::Int32(Some(a))))
+                    .collect_vec(),
+                "{predicate}"
+            );
+        }
+
+        // the scan reads exactly the keys in the range, without the filter above it
+        let transaction = fnck_sql.storage.transaction()?;
+        let table = transaction
+            .table(&fnck_sql.table_cache, Arc::new("t1".to_string()))
+            .unwrap()
+            .clone();
+        let index_meta = table
+            .indexes
+            .iter()
+            .find(|meta| meta.name == "i_bc")
+            .unwrap()
+            .clone();
+        let columns = table.columns().cloned().enumerate().collect_vec();
+        let tuple = |values: &[i32]| {
+            Arc::new(DataValue::Tuple(Some(
+                values
+                    .iter()
+                    .map(|value| Arc::new(DataValue::Int32(Some(*value))))
+                    .collect(),
+            )))
+        };
+        let mut iter = transaction.read_by_index(
+            &fnck_sql.table_cache,
+            Arc::new("t1".to_string()),
+            (None, None),
+            columns,
+            index_meta,
+            vec![Range::Scope {
+                min: Bound::Excluded(tuple(&[1, 96])),
+                max: Bound::Excluded(tuple(&[1, 99])),
+            }],
+            false,
+            false,
+        )?;
+        let mut ids = Vec::new();
+
+        while let Some(tuple) = iter.next_tuple()? {
+            ids.push(tuple.values[0].clone());
+        }
+        debug_assert_eq!(
+            ids,
+            vec![
+                Arc::new(DataValue::Int32(Some(389))),
+                Arc::new(DataValue::Int32(Some(393))),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_count() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let row_count = || -> Result<Option<usize>, DatabaseError> {
+            fnck_sql.storage.transaction()?.row_count("t1")
+        };
+        let count = |tuples: Vec<Tuple>| tuples[0].values[0].clone();
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        debug_assert_eq!(row_count()?, Some(0));
+
+        // the concurrent writers do not conflict on the row count
+        let mut tx_1 = fnck_sql.new_transaction()?;
+        let mut tx_2 = fnck_sql.new_transaction()?;
+        let _ = tx_1.run("insert into t1 values (0, 0), (1, 1)")?;
+        let _ = tx_2.run("insert into t1 values (2, 2), (3, 3), (4, 4)")?;
+
+        let (_, tuples) = tx_1.run("select count(*) from t1")?;
+        debug_assert_eq!(count(tuples), Arc::new(DataValue::Int32(Some(2))));
+        tx_1.commit()?;
+        tx_2.commit()?;
+        debug_assert_eq!(row_count()?, Some(5));
+
+        let _ = fnck_sql.run("update t1 set a = 10 where a = 0")?;
+        let _ = fnck_sql.run("update t1 set a = 11 where a = 1")?;
+        let _ = fnck_sql.run("update t1 set b = 1")?;
+        debug_assert_eq!(row_count()?, Some(5));
+        let _ = fnck_sql.run("delete from t1 where a > 10")?;
+        debug_assert_eq!(row_count()?, Some(4));
+        let _ = fnck_sql.run("truncate t1")?;
+        debug_assert_eq!(row_count()?, Some(0));
+        let _ = fnck_sql.run("insert into t1 values (0, 0), (1, 1)")?;
+        debug_assert_eq!(row_count()?, Some(2));
+        // only the tuples not overwritten are counted
+        let _ = fnck_sql.run("insert overwrite t1 values (1, 2), (2, 2)")?;
+        debug_assert_eq!(row_count()?, Some(3));
+
+        // the changes of the transactions are folded into the base count
+        for i in 3..3 + ROW_COUNT_COMPACTION_THRESHOLD * 2 {
+            let _ = fnck_sql.run(format!("insert into t1 values ({i}, {i})"))?;
+        }
+        let _ = fnck_sql.run("delete from t1 where a > 2")?;
+        debug_assert_eq!(row_count()?, Some(3));
+        let transaction = fnck_sql.storage.transaction()?;
+        let (min, max) = TableCodec::row_count_bound("t1");
+        let mut iter = transaction.range(Bound::Included(&min), Bound::Included(&max), false)?;
+        let mut keys = 0;
+        while iter.try_next()?.is_some() {
+            keys += 1;
+        }
+        // the base count and the changes below the threshold
+        debug_assert!(keys <= ROW_COUNT_COMPACTION_THRESHOLD + 1);
+        drop(iter);
+        drop(transaction);
+
+        // the table created before the rows are counted
+        let mut transaction = fnck_sql.storage.transaction()?;
+        transaction.reset_row_count("t1", None)?;
+        transaction.commit()?;
+        let _ = fnck_sql.run(format!(
+            "insert into t1 values {}",
+            (2..102).map(|i| format!("({i}, {i})")).join(", ")
+        ))?;
+        debug_assert_eq!(row_count()?, None);
+
+        let (_, tuples) = fnck_sql.run("select count(*) from t1")?;
+        debug_assert_eq!(count(tuples), Arc::new(DataValue::Int32(Some(102))));
+        let _ = fnck_sql.run("analyze table t1")?;
+        debug_assert_eq!(row_count()?, Some(102));
+
         Ok(())
     }
 }
@@ -2,15 +2,26 @@ use crate::catalog::{ColumnCatalog, TableMeta};
 use crate::errors::DatabaseError;
 use crate::types::index::{Index, IndexId, IndexMeta, IndexType};
 use crate::types::tuple::{Schema, Tuple, TupleId};
-use crate::types::value::DataValue;
+use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
 use bytes::Bytes;
 use integer_encoding::FixedInt;
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use std::sync::Arc;
+use std::{iter, mem};
 
 const BOUND_MIN_TAG: u8 = 0;
 const BOUND_MAX_TAG: u8 = 1;
+const NULL_TAG: u8 = 0;
+const NOTNULL_TAG: u8 = 1;
+
+/// The version of the encoding of the keys and values, a store written in another version is
+/// rejected on open as its indexes and catalog can't be decoded.
+///
+/// - 1: tagged nulls in the index keys, padded bytes and the `INCLUDE` columns of the indexes
+/// - 2: the key expressions and the `WHERE` predicate of the indexes
+pub(crate) const STORAGE_FORMAT_VERSION: u32 = 2;
 
 lazy_static! {
     static ref ROOT_BYTES: Vec<u8> = b"Root".to_vec();
@@ -25,6 +36,7 @@ enum CodecType {
     IndexMeta,
     Index,
     Statistics,
+    RowCount,
     Tuple,
     Root,
 }
@@ -68,6 +80,9 @@ impl TableCodec {
             CodecType::Statistics => {
                 table_bytes.push(b'4');
             }
+            CodecType::RowCount => {
+                table_bytes.push(b'5');
+            }
             CodecType::Tuple => {
                 table_bytes.push(b'8');
             }
@@ -129,6 +144,17 @@ impl TableCodec {
         (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
     }
 
+    pub fn row_count_bound(table_name: &str) -> (Vec<u8>, Vec<u8>) {
+        let op = |bound_id| {
+            let mut key_prefix = Self::key_prefix(CodecType::RowCount, table_name);
+
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
     pub fn root_table_bound() -> (Vec<u8>, Vec<u8>) {
         let op = |bound_id| {
             let mut key_prefix = ROOT_BYTES.clone();
@@ -218,23 +244,31 @@ impl TableCodec {
     }
 
     /// NonUnique Index:
-    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{DataValue1}{BOUND_MIN_TAG}{DataValue2} .. {TupleId}
-    /// Value: TupleID
+    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{NullTag}{DataValue1}{BOUND_MIN_TAG}{NullTag}{DataValue2}{BOUND_MIN_TAG} .. {TupleId}
+    /// Value: {TupleId}{IncludeValue1}{IncludeValue2} ..
     ///
     /// Unique Index:
-    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{DataValue}
-    /// Value: TupleID
+    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{NullTag}{DataValue}{BOUND_MIN_TAG}
+    /// Value: {TupleId}{IncludeValue1}{IncludeValue2} ..
     ///
     /// Tips: The unique index has only one ColumnID and one corresponding DataValue,
-    /// so it can be positioned directly.
+    /// so it can be positioned directly. The value is encoded as a tuple, and the
+    /// DataValues of the key can be decoded for covering scans.
     pub fn encode_index(
         name: &str,
         index: &Index,
         tuple_id: &TupleId,
     ) -> Result<(Bytes, Bytes), DatabaseError> {
         let key = TableCodec::encode_index_key(name, index, Some(tuple_id))?;
-        let mut bytes = Vec::new();
-        tuple_id.to_raw(&mut bytes)?;
+        let values = iter::once(tuple_id)
+            .chain(index.include_values)
+            .cloned()
+            .collect_vec();
+        let types = values
+            .iter()
+            .map(|value| value.logical_type())
+            .collect_vec();
+        let bytes = Tuple { id: None, values }.serialize_to(&types)?;
 
         Ok((Bytes::from(key), Bytes::from(bytes)))
     }
@@ -246,7 +280,12 @@ impl TableCodec {
         key_prefix.push(BOUND_MIN_TAG);
 
         for col_v in index.column_values {
-            col_v.memcomparable_encode(&mut key_prefix)?;
+            if col_v.is_null() {
+                key_prefix.push(NULL_TAG);
+            } else {
+                key_prefix.push(NOTNULL_TAG);
+                col_v.memcomparable_encode(&mut key_prefix)?;
+            }
             key_prefix.push(BOUND_MIN_TAG);
         }
         Ok(key_prefix)
@@ -282,8 +321,37 @@ impl TableCodec {
         Ok(key_prefix)
     }
 
-    pub fn decode_index(bytes: &[u8], primary_key_ty: &LogicalType) -> TupleId {
-        Arc::new(DataValue::from_raw(bytes, primary_key_ty))
+    /// Decodes the DataValues of the index key with the types of the index columns.
+    pub fn decode_index_key(
+        name: &str,
+        bytes: &[u8],
+        types: &[LogicalType],
+    ) -> Result<Vec<ValueRef>, DatabaseError> {
+        // skip {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}
+        let prefix_len =
+            Self::key_prefix(CodecType::Index, name).len() + mem::size_of::<IndexId>() + 2;
+        let mut reader = bytes.get(prefix_len..).ok_or(DatabaseError::InvalidIndex)?;
+        let mut values = Vec::with_capacity(types.len());
+
+        for ty in types {
+            let (tag, bytes) = reader.split_first().ok_or(DatabaseError::InvalidIndex)?;
+            reader = bytes;
+
+            let value = if *tag == NULL_TAG {
+                DataValue::none(ty)
+            } else {
+                DataValue::memcomparable_decode(&mut reader, ty)?
+            };
+            reader = reader.get(1..).ok_or(DatabaseError::InvalidIndex)?;
+            values.push(Arc::new(value));
+        }
+        Ok(values)
+    }
+
+    /// Decodes the index value with the types of the primary key and the include columns,
+    /// the first one is the TupleId.
+    pub fn decode_index(bytes: &[u8], types: &[LogicalType]) -> Vec<ValueRef> {
+        Tuple::deserialize_values(types, bytes)
     }
 
     /// Key: {TableName}{COLUMN_TAG}{BOUND_MIN_TAG}{ColumnId}
@@ -333,6 +401,29 @@ impl TableCodec {
         Ok(String::from_utf8(bytes.to_vec())?)
     }
 
+    /// Key: {TableName}{ROW_COUNT_TAG}{BOUND_MIN_TAG}, or with the {ChangeID} of a transaction
+    /// Value: Rows, the changed rows of the transaction
+    ///
+    /// Tips: Each transaction writes its own change instead of updating the base count, so that
+    /// the concurrent writers of the table do not conflict.
+    pub fn encode_row_count(table_name: &str, change_id: Option<u64>, rows: i64) -> (Bytes, Bytes) {
+        let mut key = Self::key_prefix(CodecType::RowCount, table_name);
+
+        key.push(BOUND_MIN_TAG);
+        if let Some(change_id) = change_id {
+            key.extend_from_slice(&change_id.to_be_bytes());
+        }
+
+        (
+            Bytes::from(key),
+            Bytes::from(rows.encode_fixed_light().to_vec()),
+        )
+    }
+
+    pub fn decode_row_count(bytes: &[u8]) -> i64 {
+        i64::decode_fixed(bytes)
+    }
+
     /// Key: Root{BOUND_MIN_TAG}{TableName}
     /// Value: TableMeta
     pub fn encode_root_table(meta: &TableMeta) -> Result<(Bytes, Bytes), DatabaseError> {
@@ -348,6 +439,27 @@ impl TableCodec {
     pub fn decode_root_table(bytes: &[u8]) -> Result<TableMeta, DatabaseError> {
         Ok(bincode::deserialize(bytes)?)
     }
+
+    /// Key: Root{BOUND_MAX_TAG}Version
+    /// Value: StorageFormatVersion
+    pub fn encode_storage_version(version: u32) -> (Bytes, Bytes) {
+        (
+            Bytes::from(Self::encode_storage_version_key()),
+            Bytes::from(version.encode_fixed_light().to_vec()),
+        )
+    }
+
+    pub fn encode_storage_version_key() -> Vec<u8> {
+        let mut key = ROOT_BYTES.clone();
+        key.push(BOUND_MAX_TAG);
+        key.extend_from_slice(b"Version");
+
+        key
+    }
+
+    pub fn decode_storage_version(bytes: &[u8]) -> u32 {
+        u32::decode_fixed(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -357,11 +469,12 @@ mod tests {
     use crate::storage::table_codec::TableCodec;
     use crate::types::index::{Index, IndexMeta, IndexType};
     use crate::types::tuple::Tuple;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
     use bytes::Bytes;
     use itertools::Itertools;
     use rust_decimal::Decimal;
+    use sqlparser::ast::CharLengthUnits;
     use std::collections::BTreeSet;
     use std::ops::Bound;
     use std::slice;
@@ -436,6 +549,9 @@ mod tests {
         let index_meta = IndexMeta {
             id: 0,
             column_ids: vec![0],
+            exprs: None,
+            include_column_ids: vec![],
+            predicate: None,
             table_name: Arc::new("T1".to_string()),
             pk_ty: LogicalType::Integer,
             name: "index_1".to_string(),
@@ -457,8 +573,51 @@ mod tests {
         let (_, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_id)?;
 
         debug_assert_eq!(
-            TableCodec::decode_index(&bytes, &tuple_id.logical_type()),
-            tuple_id
+            TableCodec::decode_index(&bytes, &[tuple_id.logical_type()]),
+            vec![tuple_id.clone()]
+        );
+
+        let values = vec![
+            Arc::new(DataValue::Int32(None)),
+            Arc::new(DataValue::Utf8 {
+                value: Some("index".to_string()),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }),
+        ];
+        let include_values = vec![
+            Arc::new(DataValue::Int8(Some(1))),
+            Arc::new(DataValue::Utf8 {
+                value: None,
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }),
+        ];
+        let index = Index::new(1, &values, IndexType::Composite).with_include(&include_values);
+        let (key, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_id)?;
+        let key_types = values
+            .iter()
+            .map(|value| value.logical_type())
+            .collect_vec();
+
+        debug_assert_eq!(
+            TableCodec::decode_index_key(&table_catalog.name, &key, &key_types)?,
+            values
+        );
+        debug_assert_eq!(
+            TableCodec::decode_index(
+                &bytes,
+                &[
+                    LogicalType::Integer,
+                    LogicalType::Tinyint,
+                    LogicalType::Varchar(None, CharLengthUnits::Characters)
+                ]
+            ),
+            vec![
+                tuple_id,
+                include_values[0].clone(),
+                include_values[1].clone()
+            ]
         );
 
         Ok(())
@@ -531,6 +690,9 @@ mod tests {
             let index_meta = IndexMeta {
                 id: index_id as u32,
                 column_ids: vec![],
+                exprs: None,
+                include_column_ids: vec![],
+                predicate: None,
                 table_name: Arc::new(table_name.to_string()),
                 pk_ty: LogicalType::Integer,
                 name: "".to_string(),
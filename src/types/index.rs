@@ -4,6 +4,7 @@ use crate::expression::range_detacher::Range;
 use crate::expression::ScalarExpression;
 use crate::types::value::ValueRef;
 use crate::types::{ColumnId, LogicalType};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Formatter;
@@ -24,12 +25,29 @@ pub enum IndexType {
 pub struct IndexInfo {
     pub(crate) meta: IndexMetaRef,
     pub(crate) range: Option<Range>,
+    /// All the columns of the scan are decoded from the index, without going back to the table.
+    pub(crate) covered: bool,
+}
+
+/// Indexes that look up the tuples together, the tuple ids read by them are united, or
+/// intersected, before the tuples are read once by the primary key.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IndexMergeInfo {
+    pub(crate) index_infos: Vec<IndexInfo>,
+    pub(crate) is_union: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct IndexMeta {
     pub id: IndexId,
+    /// The columns of the index, empty when it is an expression index
     pub column_ids: Vec<ColumnId>,
+    /// The keys of an expression index, e.g. `lower(c1)`
+    pub exprs: Option<Vec<ScalarExpression>>,
+    /// The columns that are not indexed, but stored in the index value for covering scans
+    pub include_column_ids: Vec<ColumnId>,
+    /// The predicate of a partial index, only the tuples satisfying it are indexed
+    pub predicate: Option<ScalarExpression>,
     pub table_name: TableName,
     pub pk_ty: LogicalType,
     pub name: String,
@@ -37,13 +55,83 @@ pub struct IndexMeta {
 }
 
 impl IndexMeta {
+    /// The keys of the index, which are ready to be evaluated on the tuples of the table.
     pub(crate) fn column_exprs(
         &self,
         table: &TableCatalog,
     ) -> Result<Vec<ScalarExpression>, DatabaseError> {
-        let mut exprs = Vec::with_capacity(self.column_ids.len());
+        if let Some(exprs) = &self.exprs {
+            let mut exprs = exprs.clone();
+
+            for expr in exprs.iter_mut() {
+                expr.bind_evaluator()?;
+            }
+            return Ok(exprs);
+        }
+        Self::exprs(&self.column_ids, table)
+    }
+
+    /// The predicate of the partial index, which is ready to be evaluated on the tuples of the
+    /// table.
+    pub(crate) fn predicate_expr(&self) -> Result<Option<ScalarExpression>, DatabaseError> {
+        self.predicate
+            .clone()
+            .map(|mut predicate| {
+                predicate.bind_evaluator()?;
+                Ok(predicate)
+            })
+            .transpose()
+    }
 
-        for column_id in self.column_ids.iter() {
+    /// The column of each key of the index, `None` if the key is computed by an expression.
+    pub(crate) fn key_column_ids(&self) -> Vec<Option<ColumnId>> {
+        if let Some(exprs) = &self.exprs {
+            exprs
+                .iter()
+                .map(|expr| match expr {
+                    ScalarExpression::ColumnRef(column) => column.id(),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            self.column_ids.iter().cloned().map(Some).collect()
+        }
+    }
+
+    /// Whether the column is referenced by the keys, include columns or predicate of the index.
+    pub(crate) fn is_referenced(&self, column_id: &ColumnId) -> bool {
+        self.column_ids.contains(column_id)
+            || self.include_column_ids.contains(column_id)
+            || self
+                .exprs
+                .iter()
+                .flatten()
+                .chain(self.predicate.iter())
+                .any(|expr| expr.exist_column(&self.table_name, column_id))
+    }
+
+    pub(crate) fn include_exprs(
+        &self,
+        table: &TableCatalog,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        Self::exprs(&self.include_column_ids, table)
+    }
+
+    /// Whether the column can be decoded from the entries of the secondary index.
+    pub(crate) fn is_covering(&self, column_id: &ColumnId, primary_key: &ColumnId) -> bool {
+        !matches!(self.ty, IndexType::PrimaryKey)
+            && (column_id == primary_key
+                || self.key_column_ids().contains(&Some(*column_id))
+                || self.include_column_ids.contains(column_id))
+    }
+
+    fn exprs(
+        column_ids: &[ColumnId],
+        table: &TableCatalog,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let mut exprs = Vec::with_capacity(column_ids.len());
+
+        for column_id in column_ids.iter() {
             if let Some(column) = table.get_column_by_id(column_id) {
                 exprs.push(ScalarExpression::ColumnRef(column.clone()));
             } else {
@@ -61,6 +149,7 @@ impl IndexMeta {
 pub struct Index<'a> {
     pub id: IndexId,
     pub column_values: &'a [ValueRef],
+    pub include_values: &'a [ValueRef],
     pub ty: IndexType,
 }
 
@@ -69,9 +158,15 @@ impl<'a> Index<'a> {
         Index {
             id,
             column_values,
+            include_values: &[],
             ty,
         }
     }
+
+    pub fn with_include(mut self, include_values: &'a [ValueRef]) -> Self {
+        self.include_values = include_values;
+        self
+    }
 }
 
 impl fmt::Display for IndexInfo {
@@ -84,11 +179,30 @@ impl fmt::Display for IndexInfo {
         } else {
             write!(f, "EMPTY")?;
         }
+        if self.covered {
+            write!(f, ", Covered")?;
+        }
 
         Ok(())
     }
 }
 
+impl fmt::Display for IndexMergeInfo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let index_infos = self
+            .index_infos
+            .iter()
+            .map(|index_info| format!("{}", index_info))
+            .join(", ");
+
+        if self.is_union {
+            write!(f, "Union [{}]", index_infos)
+        } else {
+            write!(f, "Intersection [{}]", index_infos)
+        }
+    }
+}
+
 impl fmt::Display for IndexMeta {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}", self.name)
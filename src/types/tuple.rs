@@ -95,6 +95,33 @@ impl Tuple {
         }
     }
 
+    /// Deserializes all the values that are serialized by `serialize_to` with the `types`.
+    pub fn deserialize_values(types: &[LogicalType], bytes: &[u8]) -> Vec<ValueRef> {
+        let bits_len = (types.len() + BITS_MAX_INDEX) / BITS_MAX_INDEX;
+        let mut values = Vec::with_capacity(types.len());
+        let mut pos = bits_len;
+
+        for (i, logic_type) in types.iter().enumerate() {
+            if bytes[i / BITS_MAX_INDEX] & (1 << (7 - i % BITS_MAX_INDEX)) > 0 {
+                values.push(Arc::new(DataValue::none(logic_type)));
+                continue;
+            }
+            let len = if let Some(len) = logic_type.raw_len() {
+                len
+            } else {
+                let len = u32::decode_fixed(&bytes[pos..pos + 4]) as usize;
+                pos += 4;
+                len
+            };
+            values.push(Arc::new(DataValue::from_raw(
+                &bytes[pos..pos + len],
+                logic_type,
+            )));
+            pos += len;
+        }
+        values
+    }
+
     fn values_push(
         tuple_columns: &Schema,
         tuple_values: &[ValueRef],
@@ -6,9 +6,10 @@ use rust_decimal::Decimal;
 use std::cmp::Ordering;
 use std::fmt::Formatter;
 use std::hash::Hash;
+use std::io::Read;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::{cmp, fmt, mem};
+use std::{cmp, fmt, io, mem};
 
 use crate::errors::DatabaseError;
 use ordered_float::OrderedFloat;
@@ -212,6 +213,14 @@ macro_rules! encode_u {
     };
 }
 
+macro_rules! decode_u {
+    ($r:ident, $ty:ty) => {{
+        let mut buf = [0; mem::size_of::<$ty>()];
+        $r.read_exact(&mut buf)?;
+        <$ty>::from_be_bytes(buf)
+    }};
+}
+
 impl Eq for DataValue {}
 
 impl Hash for DataValue {
@@ -733,8 +742,7 @@ impl DataValue {
     // Refer: https://github.com/facebook/mysql-5.6/wiki/MyRocks-record-format#memcomparable-format
     fn encode_bytes(b: &mut Vec<u8>, data: &[u8]) {
         let d_len = data.len();
-        let realloc_size = (d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1);
-        Self::realloc_bytes(b, realloc_size);
+        b.reserve((d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1));
 
         let mut idx = 0;
         while idx <= d_len {
@@ -755,12 +763,22 @@ impl DataValue {
         }
     }
 
-    fn realloc_bytes(b: &mut Vec<u8>, size: usize) {
-        let len = b.len();
+    fn decode_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>, DatabaseError> {
+        let mut data = Vec::new();
+        let mut group = [0; ENCODE_GROUP_SIZE + 1];
+
+        loop {
+            reader.read_exact(&mut group)?;
 
-        if size > len {
-            b.reserve(size - len);
-            b.resize(size, 0);
+            let pad_count = (ENCODE_MARKER - group[ENCODE_GROUP_SIZE]) as usize;
+            if pad_count > ENCODE_GROUP_SIZE {
+                return Err(io::Error::from(io::ErrorKind::InvalidData).into());
+            }
+            data.extend_from_slice(&group[..ENCODE_GROUP_SIZE - pad_count]);
+
+            if pad_count != 0 {
+                return Ok(data);
+            }
         }
     }
 
@@ -820,6 +838,73 @@ impl DataValue {
         Ok(())
     }
 
+    /// Decodes a value of `ty` encoded by `memcomparable_encode`, the value must not be null.
+    pub fn memcomparable_decode<R: Read>(
+        reader: &mut R,
+        ty: &LogicalType,
+    ) -> Result<DataValue, DatabaseError> {
+        let value = match ty {
+            LogicalType::Tinyint => DataValue::Int8(Some((decode_u!(reader, u8) ^ 0x80_u8) as i8)),
+            LogicalType::Smallint => {
+                DataValue::Int16(Some((decode_u!(reader, u16) ^ 0x8000_u16) as i16))
+            }
+            LogicalType::Integer => {
+                DataValue::Int32(Some((decode_u!(reader, u32) ^ 0x80000000_u32) as i32))
+            }
+            LogicalType::Bigint => DataValue::Int64(Some(
+                (decode_u!(reader, u64) ^ 0x8000000000000000_u64) as i64,
+            )),
+            LogicalType::Date => {
+                DataValue::Date32(Some((decode_u!(reader, u32) ^ 0x80000000_u32) as i32))
+            }
+            LogicalType::DateTime => DataValue::Date64(Some(
+                (decode_u!(reader, u64) ^ 0x8000000000000000_u64) as i64,
+            )),
+            LogicalType::UTinyint => DataValue::UInt8(Some(decode_u!(reader, u8))),
+            LogicalType::USmallint => DataValue::UInt16(Some(decode_u!(reader, u16))),
+            LogicalType::UInteger => DataValue::UInt32(Some(decode_u!(reader, u32))),
+            LogicalType::UBigint => DataValue::UInt64(Some(decode_u!(reader, u64))),
+            LogicalType::Time => DataValue::Time(Some(decode_u!(reader, u32))),
+            LogicalType::Char(len, unit) => DataValue::Utf8 {
+                value: Some(String::from_utf8(Self::decode_bytes(reader)?)?),
+                ty: Utf8Type::Fixed(*len),
+                unit: *unit,
+            },
+            LogicalType::Varchar(len, unit) => DataValue::Utf8 {
+                value: Some(String::from_utf8(Self::decode_bytes(reader)?)?),
+                ty: Utf8Type::Variable(*len),
+                unit: *unit,
+            },
+            LogicalType::Boolean => DataValue::Boolean(Some(decode_u!(reader, u8) == b'1')),
+            LogicalType::Float => {
+                let u = decode_u!(reader, u32);
+                let u = if u & 0x80000000_u32 != 0 {
+                    u & !0x80000000_u32
+                } else {
+                    !u
+                };
+
+                DataValue::Float32(Some(f32::from_bits(u)))
+            }
+            LogicalType::Double => {
+                let u = decode_u!(reader, u64);
+                let u = if u & 0x8000000000000000_u64 != 0 {
+                    u & !0x8000000000000000_u64
+                } else {
+                    !u
+                };
+
+                DataValue::Float64(Some(f64::from_bits(u)))
+            }
+            LogicalType::Invalid
+            | LogicalType::SqlNull
+            | LogicalType::Decimal(_, _)
+            | LogicalType::Tuple => return Err(DatabaseError::InvalidType),
+        };
+
+        Ok(value)
+    }
+
     pub fn is_true(&self) -> Result<bool, DatabaseError> {
         if self.is_null() {
             return Ok(false);
@@ -1612,7 +1697,8 @@ impl fmt::Debug for DataValue {
 #[cfg(test)]
 mod test {
     use crate::errors::DatabaseError;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
+    use sqlparser::ast::CharLengthUnits;
     use std::sync::Arc;
 
     #[test]
@@ -1703,6 +1789,46 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_mem_comparable_decode() -> Result<(), DatabaseError> {
+        let values = vec![
+            DataValue::Int8(Some(i8::MIN)),
+            DataValue::Int16(Some(-1)),
+            DataValue::Int32(Some(i32::MAX)),
+            DataValue::Int64(Some(0)),
+            DataValue::UInt32(Some(u32::MAX)),
+            DataValue::Float32(Some(-1.5)),
+            DataValue::Float64(Some(2.5)),
+            DataValue::Boolean(Some(true)),
+            DataValue::Date32(Some(-7)),
+            DataValue::Utf8 {
+                value: Some("".to_string()),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+            DataValue::Utf8 {
+                value: Some("memcomparable".to_string()),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+        ];
+        let mut bytes = vec![0xAA];
+
+        for value in values.iter() {
+            value.memcomparable_encode(&mut bytes)?;
+        }
+        let mut reader = &bytes[1..];
+
+        for value in values {
+            let decoded = DataValue::memcomparable_decode(&mut reader, &value.logical_type())?;
+
+            debug_assert_eq!(decoded, value);
+        }
+        debug_assert!(reader.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_mem_comparable_tuple() -> Result<(), DatabaseError> {
         let mut key_tuple_1 = Vec::new();
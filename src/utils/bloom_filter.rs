@@ -0,0 +1,77 @@
+use crate::utils::bit_vector::BitVector;
+use std::cmp;
+
+/// Bloom filter over 64-bit hashes, the bits of a hash are located by double hashing on its
+/// high and low halves.
+#[derive(Debug)]
+pub struct BloomFilter {
+    bits: BitVector,
+    bits_len: usize,
+    k_num: usize,
+}
+
+impl BloomFilter {
+    pub fn new(capacity: usize, probability: f64) -> BloomFilter {
+        let bits_len = Self::optimal_bits_len(capacity, probability);
+
+        BloomFilter {
+            bits: BitVector::new(bits_len),
+            bits_len,
+            k_num: Self::optimal_k_num(capacity, bits_len),
+        }
+    }
+
+    pub fn insert(&mut self, hash: u64) {
+        for i in 0..self.k_num {
+            let index = self.bit_index(hash, i);
+
+            self.bits.set_bit(index, true);
+        }
+    }
+
+    /// Returns false only if `hash` has never been inserted.
+    pub fn contains(&self, hash: u64) -> bool {
+        (0..self.k_num).all(|i| self.bits.get_bit(self.bit_index(hash, i)))
+    }
+
+    fn bit_index(&self, hash: u64, i: usize) -> usize {
+        let (high, low) = (hash >> 32, hash & u32::MAX as u64);
+
+        (low.wrapping_add((i as u64).wrapping_mul(high)) % self.bits_len as u64) as usize
+    }
+
+    fn optimal_bits_len(capacity: usize, probability: f64) -> usize {
+        let bits_len = -(cmp::max(1, capacity) as f64) * probability.ln() / 2f64.ln().powi(2);
+
+        cmp::max(64, bits_len.ceil() as usize)
+    }
+
+    fn optimal_k_num(capacity: usize, bits_len: usize) -> usize {
+        let k_num = bits_len as f64 / cmp::max(1, capacity) as f64 * 2f64.ln();
+
+        cmp::max(1, k_num.round() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::bloom_filter::BloomFilter;
+    use std::hash::{BuildHasher, RandomState};
+
+    #[test]
+    fn bloom_filter_simple() {
+        let state = RandomState::new();
+        let mut filter = BloomFilter::new(1000, 0.01);
+
+        for i in 0..1000 {
+            filter.insert(state.hash_one(i));
+        }
+        for i in 0..1000 {
+            debug_assert!(filter.contains(state.hash_one(i)));
+        }
+        let false_positives = (1000..11000)
+            .filter(|i| filter.contains(state.hash_one(i)))
+            .count();
+        debug_assert!(false_positives < 300, "{false_positives}");
+    }
+}